@@ -1,15 +1,24 @@
 use std::{path::PathBuf, str::FromStr};
 
-use anchor_lang::{system_program, AccountDeserialize, InstructionData, ToAccountMetas};
-use clap::{Parser, Subcommand};
-use jito_priority_fee_distribution::state::{ClaimStatus, Config, PriorityFeeDistributionAccount};
+use clap::{Parser, Subcommand, ValueEnum};
+use jito_priority_fee_distribution::state::{Config, VoteCommissionPolicy};
 use jito_priority_fee_distribution_sdk::{
-    derive_config_account_address, derive_priority_fee_distribution_account_address,
+    compliance::compliance_status,
+    derive_claim_status_account_address, derive_config_account_address,
+    derive_merkle_root_upload_config_account_address,
+    derive_priority_fee_distribution_account_address,
+    instruction::{
+        initialize_ix, initialize_merkle_root_upload_config_ix, transfer_priority_fee_tips_ix,
+        update_config_ix, InitializeAccounts, InitializeArgs,
+        InitializeMerkleRootUploadConfigAccounts, InitializeMerkleRootUploadConfigArgs,
+        TransferPriorityFeeTipsAccounts, TransferPriorityFeeTipsArgs, UpdateConfigAccounts,
+        UpdateConfigArgs,
+    },
 };
+use jito_programs_client::blocking::Client;
 use solana_client::{rpc_client::RpcClient, rpc_config::RpcSendTransactionConfig};
 use solana_sdk::{
     compute_budget::ComputeBudgetInstruction,
-    instruction::Instruction,
     pubkey::Pubkey,
     signer::{keypair::read_keypair_file, Signer},
     transaction::Transaction,
@@ -97,10 +106,6 @@ enum Commands {
         #[arg(long)]
         authority_keypair_path: String,
 
-        /// Config account pubkey
-        #[arg(long)]
-        config: String,
-
         /// Authority pubkey
         #[arg(long)]
         authority: String,
@@ -136,6 +141,14 @@ enum Commands {
         ///  _transfer_priority_fee_tips_ instruction)
         #[arg(long)]
         go_live_epoch: u64,
+
+        /// Only allow the validator identity to transfer priority fee tips
+        #[arg(long)]
+        strict_transfer_source: bool,
+
+        /// How validator commissions relate to the commission in their vote account
+        #[arg(long, value_enum, default_value = "unrestricted")]
+        vote_commission_policy: VoteCommissionPolicyArg,
     },
 
     TransferPriorityFeeTips {
@@ -158,20 +171,38 @@ enum Commands {
     },
 }
 
+/// [VoteCommissionPolicy] as a command line value.
+#[derive(Clone, Copy, ValueEnum)]
+enum VoteCommissionPolicyArg {
+    Unrestricted,
+    Cap,
+    Mirror,
+}
+
+impl From<VoteCommissionPolicyArg> for VoteCommissionPolicy {
+    fn from(arg: VoteCommissionPolicyArg) -> Self {
+        match arg {
+            VoteCommissionPolicyArg::Unrestricted => Self::Unrestricted,
+            VoteCommissionPolicyArg::Cap => Self::Cap,
+            VoteCommissionPolicyArg::Mirror => Self::Mirror,
+        }
+    }
+}
+
 fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
 
     let program_id = Pubkey::from_str(&cli.program_id)?;
 
-    let client = RpcClient::new(cli.rpc_url);
+    let client = Client::new(RpcClient::new(cli.rpc_url));
+    let priority_fee_distribution = client.priority_fee_distribution(program_id);
 
     match cli.command {
         Commands::GetConfig => {
             let (config_pda, _) = derive_config_account_address(&program_id);
             println!("Config Account Address: {}", config_pda);
 
-            let config_data = client.get_account(&config_pda)?.data;
-            let config: Config = Config::try_deserialize(&mut config_data.as_slice())?;
+            let config = priority_fee_distribution.get_config()?;
 
             println!("Config Account Data:");
             println!("  Authority: {}", config.authority);
@@ -182,6 +213,14 @@ fn main() -> anyhow::Result<()> {
                 config.max_validator_commission_bps
             );
             println!("  Go Live Epoch: {}", config.go_live_epoch);
+            println!(
+                "  Strict Transfer Source: {}",
+                config.strict_transfer_source
+            );
+            println!(
+                "  Vote Commission Policy: {:?}",
+                config.vote_commission_policy
+            );
             println!("  Bump: {}", config.bump);
         }
 
@@ -197,9 +236,8 @@ fn main() -> anyhow::Result<()> {
                 priority_fee_dist_pda
             );
 
-            let account_data = client.get_account(&priority_fee_dist_pda)?.data;
-            let priority_fee_dist: PriorityFeeDistributionAccount =
-                PriorityFeeDistributionAccount::try_deserialize(&mut account_data.as_slice())?;
+            let priority_fee_dist = priority_fee_distribution
+                .get_priority_fee_distribution_account(&vote_pubkey, epoch)?;
 
             println!("Priority Fee Distribution Account Data:");
             println!(
@@ -222,6 +260,22 @@ fn main() -> anyhow::Result<()> {
             );
             println!("  Bump: {}", priority_fee_dist.bump);
 
+            if let Some(attestation) = priority_fee_dist.priority_fee_revenue_attestation {
+                println!("  Priority Fee Revenue Attestation:");
+                println!(
+                    "    Priority Fee Revenue Lamports: {}",
+                    attestation.priority_fee_revenue_lamports
+                );
+                println!(
+                    "    Committed Share BPS: {}",
+                    attestation.committed_share_bps
+                );
+            }
+            println!(
+                "  Compliance Status: {:?}",
+                compliance_status(&priority_fee_dist)?
+            );
+
             if let Some(merkle_root) = priority_fee_dist.merkle_root {
                 println!("  Merkle Root:");
                 println!("    Root: {:?}", merkle_root.root);
@@ -250,19 +304,15 @@ fn main() -> anyhow::Result<()> {
                 derive_priority_fee_distribution_account_address(&program_id, &vote_pubkey, epoch);
 
             // Then derive claim status PDA using same seeds as in the program
-            let (claim_status_pda, _) = Pubkey::find_program_address(
-                &[
-                    ClaimStatus::SEED,
-                    claimant_pubkey.as_ref(),
-                    priority_fee_dist_pda.as_ref(),
-                ],
+            let (claim_status_pda, _) = derive_claim_status_account_address(
                 &program_id,
+                &claimant_pubkey,
+                &priority_fee_dist_pda,
             );
             println!("Claim Status Account Address: {}", claim_status_pda);
 
-            let account_data = client.get_account(&claim_status_pda)?.data;
-            let claim_status: ClaimStatus =
-                ClaimStatus::try_deserialize(&mut account_data.as_slice())?;
+            let claim_status = priority_fee_distribution
+                .get_claim_status(&claimant_pubkey, &priority_fee_dist_pda)?;
 
             println!("Claim Status Data:");
             println!("  Expires At: {}", claim_status.expires_at);
@@ -275,6 +325,8 @@ fn main() -> anyhow::Result<()> {
             max_validator_commission_bps,
             bump,
             go_live_epoch,
+            strict_transfer_source,
+            vote_commission_policy,
         } => {
             let authority_pubkey = Pubkey::from_str(&authority)?;
             let expired_funds_account_pubkey = Pubkey::from_str(&expired_funds_account)?;
@@ -286,22 +338,17 @@ fn main() -> anyhow::Result<()> {
                 max_validator_commission_bps,
                 bump,
                 go_live_epoch,
+                strict_transfer_source,
+                vote_commission_policy: vote_commission_policy.into(),
             };
 
-            let (config_pda, _) = derive_config_account_address(&program_id);
-
-            let instruction = Instruction {
+            let instruction = update_config_ix(
                 program_id,
-                data: jito_priority_fee_distribution::instruction::UpdateConfig {
-                    new_config: config,
-                }
-                .data(),
-                accounts: jito_priority_fee_distribution::accounts::UpdateConfig {
-                    config: config_pda,
+                UpdateConfigArgs { new_config: config },
+                UpdateConfigAccounts {
                     authority: authority_pubkey,
-                }
-                .to_account_metas(None),
-            };
+                },
+            );
 
             let serialized_data = instruction.data;
             let base58_data = bs58::encode(serialized_data).into_string();
@@ -317,96 +364,80 @@ fn main() -> anyhow::Result<()> {
         } => {
             let authority_keypair = read_keypair_file(authority_keypair_path)
                 .expect("Failed to read authority keypair file");
-            let (config_pda, bump) = derive_config_account_address(&program_id);
+            let (config_pda, _) = derive_config_account_address(&program_id);
             println!("Config Account Address: {}", config_pda);
 
             let authority_pubkey = Pubkey::from_str(&authority)?;
             let expired_funds_account_pubkey = Pubkey::from_str(&expired_funds_account)?;
 
-            let instruction = Instruction {
+            let instruction = initialize_ix(
                 program_id,
-                data: jito_priority_fee_distribution::instruction::Initialize {
+                InitializeArgs {
                     authority: authority_pubkey,
                     expired_funds_account: expired_funds_account_pubkey,
                     num_epochs_valid,
                     max_validator_commission_bps,
-                    bump,
-                }
-                .data(),
-                accounts: jito_priority_fee_distribution::accounts::Initialize {
+                },
+                InitializeAccounts {
                     initializer: authority_pubkey,
-                    config: config_pda,
-                    system_program: solana_sdk::system_program::ID,
-                }
-                .to_account_metas(Some(true)),
-            };
+                },
+            );
 
             let mut transaction =
                 solana_sdk::transaction::Transaction::new_with_payer(&[instruction], None);
-            transaction.sign(&[&authority_keypair], client.get_latest_blockhash()?);
-            let signature = client.send_and_confirm_transaction_with_spinner(&transaction)?;
+            transaction.sign(&[&authority_keypair], client.rpc().get_latest_blockhash()?);
+            let signature = client
+                .rpc()
+                .send_and_confirm_transaction_with_spinner(&transaction)?;
             println!("Transaction Signature: {}", signature);
         }
 
         Commands::InitializeMerkleRootUploadConfig {
             authority_keypair_path,
-            config,
             authority,
             payer,
         } => {
             let authority_keypair = read_keypair_file(authority_keypair_path)
                 .expect("Failed to read authority keypair file");
-            let config_pubkey = Pubkey::from_str(&config)?;
             let authority_pubkey = Pubkey::from_str(&authority)?;
             let payer_pubkey = Pubkey::from_str(&payer)?;
-            let (merkle_root_upload_config, _) =
-                Pubkey::find_program_address(&[b"ROOT_UPLOAD_CONFIG"], &program_id);
 
-            let instruction = Instruction {
+            let instruction = initialize_merkle_root_upload_config_ix(
                 program_id,
-                data:
-                    jito_priority_fee_distribution::instruction::InitializeMerkleRootUploadConfig {
-                        original_authority: authority_pubkey,
-                        authority: authority_pubkey,
-                    }
-                    .data(),
-                accounts:
-                    jito_priority_fee_distribution::accounts::InitializeMerkleRootUploadConfig {
-                        config: config_pubkey,
-                        authority: authority_pubkey,
-                        payer: payer_pubkey,
-                        merkle_root_upload_config,
-                        system_program: solana_sdk::system_program::ID,
-                    }
-                    .to_account_metas(None),
-            };
+                InitializeMerkleRootUploadConfigArgs {
+                    authority: authority_pubkey,
+                    original_authority: authority_pubkey,
+                },
+                InitializeMerkleRootUploadConfigAccounts {
+                    authority: authority_pubkey,
+                    payer: payer_pubkey,
+                },
+            );
             let mut transaction =
                 solana_sdk::transaction::Transaction::new_with_payer(&[instruction], None);
-            transaction.sign(&[&authority_keypair], client.get_latest_blockhash()?);
-            let signature = client.send_and_confirm_transaction_with_spinner_and_config(
-                &transaction,
-                solana_sdk::commitment_config::CommitmentConfig::confirmed(),
-                solana_client::rpc_config::RpcSendTransactionConfig {
-                    skip_preflight: true,
-                    ..Default::default()
-                },
-            )?;
+            transaction.sign(&[&authority_keypair], client.rpc().get_latest_blockhash()?);
+            let signature = client
+                .rpc()
+                .send_and_confirm_transaction_with_spinner_and_config(
+                    &transaction,
+                    solana_sdk::commitment_config::CommitmentConfig::confirmed(),
+                    solana_client::rpc_config::RpcSendTransactionConfig {
+                        skip_preflight: true,
+                        ..Default::default()
+                    },
+                )?;
             println!("Transaction Signature: {}", signature);
         }
 
         Commands::GetMerkleRootUploadConfig => {
             let (merkle_root_upload_config, _) =
-                Pubkey::find_program_address(&[b"ROOT_UPLOAD_CONFIG"], &program_id);
+                derive_merkle_root_upload_config_account_address(&program_id);
             println!(
                 "Merkle Root Upload Config Account Address: {}",
                 merkle_root_upload_config
             );
 
-            let account_data = client.get_account(&merkle_root_upload_config)?.data;
-            let config: jito_priority_fee_distribution::state::MerkleRootUploadConfig =
-                jito_priority_fee_distribution::state::MerkleRootUploadConfig::try_deserialize(
-                    &mut account_data.as_slice(),
-                )?;
+            let config = priority_fee_distribution.get_merkle_root_upload_config()?;
 
             println!("Merkle Root Upload Config Account Data:");
             println!("  Original Authority: {}", config.original_upload_authority);
@@ -422,30 +453,21 @@ fn main() -> anyhow::Result<()> {
         } => {
             let keypair = read_keypair_file(&keypair_path).expect("Failed to read keypair file");
 
-            let (config_pda, _) = derive_config_account_address(&program_id);
-            let vote_pubkey = Pubkey::from_str(&vote_account)?;
-            let (priority_fee_dist_pda, _) =
-                derive_priority_fee_distribution_account_address(&program_id, &vote_pubkey, epoch);
-
-            let instruction = Instruction {
+            let instruction = transfer_priority_fee_tips_ix(
                 program_id,
-                data: jito_priority_fee_distribution::instruction::TransferPriorityFeeTips {
-                    lamports,
-                }
-                .data(),
-                accounts: jito_priority_fee_distribution::accounts::TransferPriorityFeeTips {
-                    config: config_pda,
-                    priority_fee_distribution_account: priority_fee_dist_pda,
+                TransferPriorityFeeTipsArgs { lamports },
+                TransferPriorityFeeTipsAccounts {
+                    validator_vote_account: Pubkey::from_str(&vote_account)?,
+                    epoch,
                     from: keypair.pubkey(),
-                    system_program: system_program::ID,
-                }
-                .to_account_metas(None),
-            };
+                },
+            );
 
-            // tests show ~6,800 before go_live_epoch and ~6,400 after
-            let compute_ix = ComputeBudgetInstruction::set_compute_unit_limit(8_000);
+            // tests show ~6,800 before go_live_epoch and ~6,400 after, with headroom for checking
+            // the vote account when the config has strict_transfer_source set
+            let compute_ix = ComputeBudgetInstruction::set_compute_unit_limit(10_000);
 
-            let blockhash = client.get_latest_blockhash()?;
+            let blockhash = client.rpc().get_latest_blockhash()?;
 
             let tx = Transaction::new_signed_with_payer(
                 &[compute_ix, instruction],
@@ -453,11 +475,13 @@ fn main() -> anyhow::Result<()> {
                 &vec![&keypair],
                 blockhash,
             );
-            let result = client.send_and_confirm_transaction_with_spinner_and_config(
-                &tx,
-                client.commitment(),
-                RpcSendTransactionConfig::default(),
-            )?;
+            let result = client
+                .rpc()
+                .send_and_confirm_transaction_with_spinner_and_config(
+                    &tx,
+                    client.rpc().commitment(),
+                    RpcSendTransactionConfig::default(),
+                )?;
 
             println!("TX Confirmed: {}", result);
         }
@@ -0,0 +1,4 @@
+//! Account fetching over the blocking [RpcClient].
+use solana_client::rpc_client::RpcClient;
+
+client!(RpcClient);
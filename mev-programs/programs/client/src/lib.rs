@@ -0,0 +1,128 @@
+//! Typed account fetching for the tip distribution, priority fee distribution and tip payment
+//! programs.
+//!
+//! [nonblocking::Client] and [blocking::Client] wrap the `solana_client` RPC client of the same
+//! flavour and decode accounts into the programs' types. Batched reads go through
+//! `get_multiple_accounts`, [MAX_MULTIPLE_ACCOUNTS] addresses per request, and report each
+//! account's outcome separately.
+#[macro_use]
+mod macros;
+
+pub mod blocking;
+pub mod nonblocking;
+
+use std::fmt;
+
+use anchor_lang::{prelude::Pubkey, AccountDeserialize, Discriminator};
+use jito_merkle_tree::distribution_tree::{DistributionTree, DistributionTreeError};
+use jito_priority_fee_distribution::state::PriorityFeeDistributionAccount;
+use jito_tip_distribution::state::TipDistributionAccount;
+pub use solana_client::rpc_request::MAX_MULTIPLE_ACCOUNTS;
+
+pub type Result<T> = std::result::Result<T, ClientError>;
+
+#[derive(Debug)]
+pub enum ClientError {
+    /// The RPC request failed.
+    Rpc(Box<solana_client::client_error::ClientError>),
+    /// No account exists at the address.
+    AccountNotFound(Pubkey),
+    /// The account at the address is not of the requested type.
+    AccountDiscriminatorMismatch(Pubkey),
+    /// The account is of the requested type but its data could not be decoded.
+    AccountDidNotDeserialize(Pubkey),
+    /// The distribution tree isn't the one uploaded to the distribution account at the address.
+    InvalidDistributionTree(Pubkey, DistributionTreeError),
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Rpc(e) => write!(f, "rpc request failed: {e}"),
+            Self::AccountNotFound(address) => write!(f, "account {address} not found"),
+            Self::AccountDiscriminatorMismatch(address) => {
+                write!(f, "account {address} has an unexpected discriminator")
+            }
+            Self::AccountDidNotDeserialize(address) => {
+                write!(f, "account {address} failed to deserialize")
+            }
+            Self::InvalidDistributionTree(address, e) => {
+                write!(f, "distribution tree for account {address} is invalid: {e}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ClientError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Rpc(e) => Some(e),
+            Self::InvalidDistributionTree(_, e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<solana_client::client_error::ClientError> for ClientError {
+    fn from(e: solana_client::client_error::ClientError) -> Self {
+        Self::Rpc(Box::new(e))
+    }
+}
+
+/// Decodes the account at `address`, whose `data` is [None] if the account does not exist.
+fn decode<T: AccountDeserialize + Discriminator>(
+    address: &Pubkey,
+    data: Option<&[u8]>,
+) -> Result<T> {
+    let data = data.ok_or(ClientError::AccountNotFound(*address))?;
+    if !data.starts_with(T::DISCRIMINATOR) {
+        return Err(ClientError::AccountDiscriminatorMismatch(*address));
+    }
+    T::try_deserialize_unchecked(&mut &data[..])
+        .map_err(|_| ClientError::AccountDidNotDeserialize(*address))
+}
+
+/// Checks `tree` is the one uploaded to `tip_distribution_account`, which must be the account
+/// `program_id` derives for the tree's epoch.
+fn validate_tip_distribution_tree(
+    program_id: &Pubkey,
+    tree: &DistributionTree,
+    tip_distribution_account: &TipDistributionAccount,
+) -> Result<()> {
+    let (address, _) = jito_tip_distribution_sdk::derive_tip_distribution_account_address(
+        program_id,
+        &tip_distribution_account.validator_vote_account,
+        tree.epoch,
+    );
+    if address != tree.distribution_account {
+        return Err(ClientError::InvalidDistributionTree(
+            tree.distribution_account,
+            DistributionTreeError::Mismatch("distribution_account"),
+        ));
+    }
+    tree.validate_tip_distribution_account(&address, tip_distribution_account)
+        .map_err(|e| ClientError::InvalidDistributionTree(address, e))
+}
+
+/// Checks `tree` is the one uploaded to `priority_fee_distribution_account`, which must be the
+/// account `program_id` derives for the tree's epoch.
+fn validate_priority_fee_distribution_tree(
+    program_id: &Pubkey,
+    tree: &DistributionTree,
+    priority_fee_distribution_account: &PriorityFeeDistributionAccount,
+) -> Result<()> {
+    let (address, _) =
+        jito_priority_fee_distribution_sdk::derive_priority_fee_distribution_account_address(
+            program_id,
+            &priority_fee_distribution_account.validator_vote_account,
+            tree.epoch,
+        );
+    if address != tree.distribution_account {
+        return Err(ClientError::InvalidDistributionTree(
+            tree.distribution_account,
+            DistributionTreeError::Mismatch("distribution_account"),
+        ));
+    }
+    tree.validate_priority_fee_distribution_account(&address, priority_fee_distribution_account)
+        .map_err(|e| ClientError::InvalidDistributionTree(address, e))
+}
@@ -0,0 +1,304 @@
+/// Defines the `Client` over `$rpc_client` and its views of each program's accounts. Given
+/// `async, await`, the functions are `async` and await each RPC call, as the nonblocking
+/// `RpcClient` needs.
+macro_rules! client {
+    ($rpc_client:ty $(, $async:ident, $await:ident)?) => {
+        use anchor_lang::{prelude::Pubkey, AccountDeserialize, Discriminator};
+        use jito_merkle_tree::distribution_tree::DistributionTree;
+        use jito_priority_fee_distribution::state as pfd_state;
+        use jito_priority_fee_distribution_sdk as pfd_sdk;
+        use jito_tip_distribution::state as td_state;
+        use jito_tip_distribution_sdk as td_sdk;
+
+        use crate::{
+            decode, validate_priority_fee_distribution_tree, validate_tip_distribution_tree,
+            Result, MAX_MULTIPLE_ACCOUNTS,
+        };
+
+        pub struct Client {
+            rpc: $rpc_client,
+        }
+
+        impl Client {
+            pub fn new(rpc: $rpc_client) -> Self {
+                Self { rpc }
+            }
+
+            pub fn rpc(&self) -> &$rpc_client {
+                &self.rpc
+            }
+
+            pub $($async)? fn get_account<T: AccountDeserialize + Discriminator>(
+                &self,
+                address: &Pubkey,
+            ) -> Result<T> {
+                let account = self
+                    .rpc
+                    .get_account_with_commitment(address, self.rpc.commitment())
+                    $(.$await)? ?
+                    .value;
+                decode(
+                    address,
+                    account.as_ref().map(|account| account.data.as_slice()),
+                )
+            }
+
+            /// Fetches the accounts in as few requests as possible, returning each one's outcome
+            /// in the order of `addresses`.
+            pub $($async)? fn get_accounts<T: AccountDeserialize + Discriminator>(
+                &self,
+                addresses: &[Pubkey],
+            ) -> Result<Vec<Result<T>>> {
+                let mut accounts = Vec::with_capacity(addresses.len());
+                for chunk in addresses.chunks(MAX_MULTIPLE_ACCOUNTS) {
+                    accounts.extend(self.rpc.get_multiple_accounts(chunk)$(.$await)? ?);
+                }
+                Ok(addresses
+                    .iter()
+                    .zip(accounts)
+                    .map(|(address, account)| {
+                        decode(
+                            address,
+                            account.as_ref().map(|account| account.data.as_slice()),
+                        )
+                    })
+                    .collect())
+            }
+
+            pub fn tip_distribution(&self, program_id: Pubkey) -> TipDistribution<'_> {
+                TipDistribution {
+                    client: self,
+                    program_id,
+                }
+            }
+
+            pub fn priority_fee_distribution(
+                &self,
+                program_id: Pubkey,
+            ) -> PriorityFeeDistribution<'_> {
+                PriorityFeeDistribution {
+                    client: self,
+                    program_id,
+                }
+            }
+
+            pub fn tip_payment(&self, program_id: Pubkey) -> TipPayment<'_> {
+                TipPayment {
+                    client: self,
+                    program_id,
+                }
+            }
+        }
+
+        /// The tip distribution program's accounts.
+        pub struct TipDistribution<'a> {
+            client: &'a Client,
+            program_id: Pubkey,
+        }
+
+        impl TipDistribution<'_> {
+            pub $($async)? fn get_config(&self) -> Result<td_state::Config> {
+                let (address, _) = td_sdk::derive_config_account_address(&self.program_id);
+                self.client.get_account(&address)$(.$await)?
+            }
+
+            pub $($async)? fn get_tip_distribution_account(
+                &self,
+                vote_account: &Pubkey,
+                epoch: u64,
+            ) -> Result<td_state::TipDistributionAccount> {
+                let (address, _) = td_sdk::derive_tip_distribution_account_address(
+                    &self.program_id,
+                    vote_account,
+                    epoch,
+                );
+                self.client.get_account(&address)$(.$await)?
+            }
+
+            pub $($async)? fn get_tip_distribution_accounts(
+                &self,
+                vote_accounts: &[Pubkey],
+                epoch: u64,
+            ) -> Result<Vec<Result<td_state::TipDistributionAccount>>> {
+                let addresses: Vec<Pubkey> = vote_accounts
+                    .iter()
+                    .map(|vote_account| {
+                        td_sdk::derive_tip_distribution_account_address(
+                            &self.program_id,
+                            vote_account,
+                            epoch,
+                        )
+                        .0
+                    })
+                    .collect();
+                self.client.get_accounts(&addresses)$(.$await)?
+            }
+
+            pub $($async)? fn get_claim_status(
+                &self,
+                claimant: &Pubkey,
+                tip_distribution_account: &Pubkey,
+            ) -> Result<td_state::ClaimStatus> {
+                let (address, _) = td_sdk::derive_claim_status_account_address(
+                    &self.program_id,
+                    claimant,
+                    tip_distribution_account,
+                );
+                self.client.get_account(&address)$(.$await)?
+            }
+
+            pub $($async)? fn get_claim_statuses(
+                &self,
+                claimants: &[Pubkey],
+                tip_distribution_account: &Pubkey,
+            ) -> Result<Vec<Result<td_state::ClaimStatus>>> {
+                let addresses: Vec<Pubkey> = claimants
+                    .iter()
+                    .map(|claimant| {
+                        td_sdk::derive_claim_status_account_address(
+                            &self.program_id,
+                            claimant,
+                            tip_distribution_account,
+                        )
+                        .0
+                    })
+                    .collect();
+                self.client.get_accounts(&addresses)$(.$await)?
+            }
+
+            /// Fetches the tip distribution account `tree` is for and checks the tree is the one
+            /// uploaded to it.
+            pub $($async)? fn validate_distribution_tree(
+                &self,
+                tree: &DistributionTree,
+            ) -> Result<()> {
+                let tip_distribution_account =
+                    self.client.get_account(&tree.distribution_account)$(.$await)? ?;
+                validate_tip_distribution_tree(&self.program_id, tree, &tip_distribution_account)
+            }
+
+            pub $($async)? fn get_merkle_root_upload_config(
+                &self,
+            ) -> Result<td_state::MerkleRootUploadConfig> {
+                let (address, _) =
+                    td_sdk::derive_merkle_root_upload_config_account_address(&self.program_id);
+                self.client.get_account(&address)$(.$await)?
+            }
+        }
+
+        /// The priority fee distribution program's accounts.
+        pub struct PriorityFeeDistribution<'a> {
+            client: &'a Client,
+            program_id: Pubkey,
+        }
+
+        impl PriorityFeeDistribution<'_> {
+            pub $($async)? fn get_config(&self) -> Result<pfd_state::Config> {
+                let (address, _) = pfd_sdk::derive_config_account_address(&self.program_id);
+                self.client.get_account(&address)$(.$await)?
+            }
+
+            pub $($async)? fn get_priority_fee_distribution_account(
+                &self,
+                vote_account: &Pubkey,
+                epoch: u64,
+            ) -> Result<pfd_state::PriorityFeeDistributionAccount> {
+                let (address, _) = pfd_sdk::derive_priority_fee_distribution_account_address(
+                    &self.program_id,
+                    vote_account,
+                    epoch,
+                );
+                self.client.get_account(&address)$(.$await)?
+            }
+
+            pub $($async)? fn get_priority_fee_distribution_accounts(
+                &self,
+                vote_accounts: &[Pubkey],
+                epoch: u64,
+            ) -> Result<Vec<Result<pfd_state::PriorityFeeDistributionAccount>>> {
+                let addresses: Vec<Pubkey> = vote_accounts
+                    .iter()
+                    .map(|vote_account| {
+                        pfd_sdk::derive_priority_fee_distribution_account_address(
+                            &self.program_id,
+                            vote_account,
+                            epoch,
+                        )
+                        .0
+                    })
+                    .collect();
+                self.client.get_accounts(&addresses)$(.$await)?
+            }
+
+            pub $($async)? fn get_claim_status(
+                &self,
+                claimant: &Pubkey,
+                priority_fee_distribution_account: &Pubkey,
+            ) -> Result<pfd_state::ClaimStatus> {
+                let (address, _) = pfd_sdk::derive_claim_status_account_address(
+                    &self.program_id,
+                    claimant,
+                    priority_fee_distribution_account,
+                );
+                self.client.get_account(&address)$(.$await)?
+            }
+
+            pub $($async)? fn get_claim_statuses(
+                &self,
+                claimants: &[Pubkey],
+                priority_fee_distribution_account: &Pubkey,
+            ) -> Result<Vec<Result<pfd_state::ClaimStatus>>> {
+                let addresses: Vec<Pubkey> = claimants
+                    .iter()
+                    .map(|claimant| {
+                        pfd_sdk::derive_claim_status_account_address(
+                            &self.program_id,
+                            claimant,
+                            priority_fee_distribution_account,
+                        )
+                        .0
+                    })
+                    .collect();
+                self.client.get_accounts(&addresses)$(.$await)?
+            }
+
+            /// Fetches the priority fee distribution account `tree` is for and checks the tree is
+            /// the one uploaded to it.
+            pub $($async)? fn validate_distribution_tree(
+                &self,
+                tree: &DistributionTree,
+            ) -> Result<()> {
+                let priority_fee_distribution_account =
+                    self.client.get_account(&tree.distribution_account)$(.$await)? ?;
+                validate_priority_fee_distribution_tree(
+                    &self.program_id,
+                    tree,
+                    &priority_fee_distribution_account,
+                )
+            }
+
+            pub $($async)? fn get_merkle_root_upload_config(
+                &self,
+            ) -> Result<pfd_state::MerkleRootUploadConfig> {
+                let (address, _) =
+                    pfd_sdk::derive_merkle_root_upload_config_account_address(&self.program_id);
+                self.client.get_account(&address)$(.$await)?
+            }
+        }
+
+        /// The tip payment program's accounts.
+        pub struct TipPayment<'a> {
+            client: &'a Client,
+            program_id: Pubkey,
+        }
+
+        impl TipPayment<'_> {
+            pub $($async)? fn get_config(&self) -> Result<jito_tip_payment::Config> {
+                let (address, _) = jito_tip_payment_sdk::derive_config_account_address(
+                    &self.program_id,
+                );
+                self.client.get_account(&address)$(.$await)?
+            }
+        }
+    };
+}
@@ -0,0 +1,4 @@
+//! Account fetching over the nonblocking [RpcClient].
+use solana_client::nonblocking::rpc_client::RpcClient;
+
+client!(RpcClient, async, await);
@@ -0,0 +1,191 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use anchor_lang::{prelude::Pubkey, AccountSerialize};
+use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use jito_programs_client::{blocking, nonblocking, ClientError, MAX_MULTIPLE_ACCOUNTS};
+use jito_tip_distribution::state::{ClaimStatus, Config};
+use serde_json::{json, Value};
+use solana_client::{
+    client_error::Result as RpcResult,
+    rpc_client::RpcClientConfig,
+    rpc_request::RpcRequest,
+    rpc_sender::{RpcSender, RpcTransportStats},
+};
+
+/// Serves `getAccountInfo` and `getMultipleAccounts` from `accounts`, recording the number of
+/// addresses in each `getMultipleAccounts` request.
+#[derive(Clone, Default)]
+struct MockSender {
+    accounts: Arc<HashMap<Pubkey, Vec<u8>>>,
+    chunks: Arc<Mutex<Vec<usize>>>,
+}
+
+impl MockSender {
+    fn new(accounts: HashMap<Pubkey, Vec<u8>>) -> Self {
+        Self {
+            accounts: Arc::new(accounts),
+            ..Self::default()
+        }
+    }
+
+    fn account(&self, address: &Value) -> Value {
+        let address: Pubkey = address.as_str().unwrap().parse().unwrap();
+        match self.accounts.get(&address) {
+            Some(data) => json!({
+                "lamports": 1_000_000,
+                "data": [STANDARD.encode(data), "base64"],
+                "owner": jito_tip_distribution::id().to_string(),
+                "executable": false,
+                "rentEpoch": 0,
+                "space": data.len(),
+            }),
+            None => Value::Null,
+        }
+    }
+}
+
+#[async_trait]
+impl RpcSender for MockSender {
+    async fn send(&self, request: RpcRequest, params: Value) -> RpcResult<Value> {
+        let value = match request {
+            RpcRequest::GetAccountInfo => self.account(&params[0]),
+            RpcRequest::GetMultipleAccounts => {
+                let addresses = params[0].as_array().unwrap();
+                self.chunks.lock().unwrap().push(addresses.len());
+                addresses
+                    .iter()
+                    .map(|address| self.account(address))
+                    .collect()
+            }
+            _ => unimplemented!("{request}"),
+        };
+        Ok(json!({ "context": { "slot": 1 }, "value": value }))
+    }
+
+    fn get_transport_stats(&self) -> RpcTransportStats {
+        RpcTransportStats::default()
+    }
+
+    fn url(&self) -> String {
+        "mock".to_string()
+    }
+}
+
+fn config_data(num_epochs_valid: u64) -> Vec<u8> {
+    let mut data = Vec::new();
+    Config {
+        num_epochs_valid,
+        ..Config::default()
+    }
+    .try_serialize(&mut data)
+    .unwrap();
+    data
+}
+
+fn blocking_client(sender: MockSender) -> blocking::Client {
+    blocking::Client::new(solana_client::rpc_client::RpcClient::new_sender(
+        sender,
+        RpcClientConfig::default(),
+    ))
+}
+
+#[test]
+fn test_get_account_decodes_by_type() {
+    let config = Pubkey::new_unique();
+    let claim_status = Pubkey::new_unique();
+    let truncated = Pubkey::new_unique();
+    let missing = Pubkey::new_unique();
+
+    let mut claim_status_data = Vec::new();
+    ClaimStatus::default()
+        .try_serialize(&mut claim_status_data)
+        .unwrap();
+    let config_data = config_data(3);
+    let client = blocking_client(MockSender::new(HashMap::from([
+        (config, config_data.clone()),
+        (claim_status, claim_status_data),
+        (truncated, config_data[..config_data.len() - 1].to_vec()),
+    ])));
+
+    assert_eq!(
+        client
+            .get_account::<Config>(&config)
+            .unwrap()
+            .num_epochs_valid,
+        3
+    );
+    assert!(matches!(
+        client.get_account::<Config>(&missing),
+        Err(ClientError::AccountNotFound(address)) if address == missing
+    ));
+    assert!(matches!(
+        client.get_account::<Config>(&claim_status),
+        Err(ClientError::AccountDiscriminatorMismatch(address)) if address == claim_status
+    ));
+    assert!(matches!(
+        client.get_account::<Config>(&truncated),
+        Err(ClientError::AccountDidNotDeserialize(address)) if address == truncated
+    ));
+}
+
+#[test]
+fn test_get_accounts_chunks_in_order() {
+    // every third account is missing
+    let addresses: Vec<Pubkey> = (0..MAX_MULTIPLE_ACCOUNTS * 2 + 3)
+        .map(|_| Pubkey::new_unique())
+        .collect();
+    let accounts = addresses
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| i % 3 != 0)
+        .map(|(i, address)| (*address, config_data(i as u64)))
+        .collect();
+    let sender = MockSender::new(accounts);
+    let client = blocking_client(sender.clone());
+
+    let configs = client.get_accounts::<Config>(&addresses).unwrap();
+    assert_eq!(
+        *sender.chunks.lock().unwrap(),
+        [MAX_MULTIPLE_ACCOUNTS, MAX_MULTIPLE_ACCOUNTS, 3]
+    );
+    assert_eq!(configs.len(), addresses.len());
+    for (i, (address, config)) in addresses.iter().zip(configs).enumerate() {
+        if i % 3 == 0 {
+            assert!(matches!(
+                config,
+                Err(ClientError::AccountNotFound(missing)) if missing == *address
+            ));
+        } else {
+            assert_eq!(config.unwrap().num_epochs_valid, i as u64);
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_nonblocking_get_accounts() {
+    let addresses = [Pubkey::new_unique(), Pubkey::new_unique()];
+    let sender = MockSender::new(HashMap::from([(addresses[1], config_data(7))]));
+    let client = nonblocking::Client::new(
+        solana_client::nonblocking::rpc_client::RpcClient::new_sender(
+            sender.clone(),
+            RpcClientConfig::default(),
+        ),
+    );
+
+    let configs = client.get_accounts::<Config>(&addresses).await.unwrap();
+    assert_eq!(*sender.chunks.lock().unwrap(), [2]);
+    assert!(matches!(configs[0], Err(ClientError::AccountNotFound(_))));
+    assert_eq!(configs[1].as_ref().unwrap().num_epochs_valid, 7);
+    assert_eq!(
+        client
+            .get_account::<Config>(&addresses[1])
+            .await
+            .unwrap()
+            .num_epochs_valid,
+        7
+    );
+}
@@ -0,0 +1,208 @@
+//! The file format uploaders and claimers exchange for a distribution account's merkle tree,
+//! encoded either as JSON or compactly with bincode.
+use std::fmt;
+
+use jito_priority_fee_distribution::state::PriorityFeeDistributionAccount;
+use jito_tip_distribution::state::{ClaimMode, TipDistributionAccount};
+use serde_derive::{Deserialize, Serialize};
+use solana_program::pubkey::Pubkey;
+
+use crate::{serde_base58, GeneratedMerkleTree, LeafFormat, MerkleTreeError, TreeNode};
+
+#[derive(Debug)]
+pub enum DistributionTreeError {
+    Json(serde_json::Error),
+    Binary(bincode::Error),
+    MerkleTree(MerkleTreeError),
+    /// The tree's nodes don't produce its root, or a node's proof doesn't verify.
+    InvalidTree,
+    /// The named field doesn't match the on-chain account.
+    Mismatch(&'static str),
+    /// No merkle root has been uploaded to the on-chain account.
+    RootNotUploaded,
+}
+
+impl fmt::Display for DistributionTreeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Json(e) => write!(f, "invalid JSON distribution tree: {e}"),
+            Self::Binary(e) => write!(f, "invalid binary distribution tree: {e}"),
+            Self::MerkleTree(e) => e.fmt(f),
+            Self::InvalidTree => write!(f, "tree nodes don't match the merkle root"),
+            Self::Mismatch(field) => write!(f, "{field} doesn't match the distribution account"),
+            Self::RootNotUploaded => write!(f, "distribution account has no merkle root"),
+        }
+    }
+}
+
+impl std::error::Error for DistributionTreeError {}
+
+impl From<MerkleTreeError> for DistributionTreeError {
+    fn from(e: MerkleTreeError) -> Self {
+        Self::MerkleTree(e)
+    }
+}
+
+/// Every version of the format, tagged so older files remain readable.
+#[derive(Serialize, Deserialize)]
+enum VersionedDistributionTree {
+    V1(DistributionTree),
+}
+
+/// The data behind a tip or priority fee distribution account's merkle root.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DistributionTree {
+    /// The `TipDistributionAccount` or `PriorityFeeDistributionAccount` the root is uploaded to.
+    #[serde(with = "serde_base58::pubkey")]
+    pub distribution_account: Pubkey,
+    /// The epoch the distribution account was created for.
+    pub epoch: u64,
+    #[serde(with = "serde_base58::hash")]
+    pub merkle_root: [u8; 32],
+    pub max_total_claim: u64,
+    pub max_num_nodes: u64,
+    pub tree_nodes: Vec<TreeNode>,
+    pub leaf_format: LeafFormat,
+}
+
+impl DistributionTree {
+    pub fn new(distribution_account: Pubkey, epoch: u64, tree: GeneratedMerkleTree) -> Self {
+        Self {
+            distribution_account,
+            epoch,
+            merkle_root: tree.merkle_root,
+            max_total_claim: tree.max_total_claim,
+            max_num_nodes: tree.max_num_nodes,
+            tree_nodes: tree.tree_nodes,
+            leaf_format: tree.leaf_format,
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String, DistributionTreeError> {
+        serde_json::to_string_pretty(&VersionedDistributionTree::V1(self.clone()))
+            .map_err(DistributionTreeError::Json)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, DistributionTreeError> {
+        match serde_json::from_str(json).map_err(DistributionTreeError::Json)? {
+            VersionedDistributionTree::V1(tree) => Ok(tree),
+        }
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, DistributionTreeError> {
+        bincode::serialize(&VersionedDistributionTree::V1(self.clone()))
+            .map_err(DistributionTreeError::Binary)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DistributionTreeError> {
+        match bincode::deserialize(bytes).map_err(DistributionTreeError::Binary)? {
+            VersionedDistributionTree::V1(tree) => Ok(tree),
+        }
+    }
+
+    /// Rebuilds the tree from its nodes, checking the root, totals and every proof.
+    pub fn verify(&self) -> Result<(), DistributionTreeError> {
+        let tree = GeneratedMerkleTree::with_leaf_format(
+            self.tree_nodes
+                .iter()
+                .map(|node| (node.claimant, node.amount)),
+            self.leaf_format,
+        )?;
+        if tree.merkle_root != self.merkle_root {
+            return Err(DistributionTreeError::InvalidTree);
+        }
+        if tree.max_total_claim != self.max_total_claim {
+            return Err(DistributionTreeError::Mismatch("max_total_claim"));
+        }
+        if tree.max_num_nodes != self.max_num_nodes {
+            return Err(DistributionTreeError::Mismatch("max_num_nodes"));
+        }
+
+        if tree
+            .tree_nodes
+            .iter()
+            .zip(&self.tree_nodes)
+            .any(|(expected, node)| expected.proof != node.proof)
+        {
+            return Err(DistributionTreeError::InvalidTree);
+        }
+
+        Ok(())
+    }
+
+    /// Checks the tree is consistent and is the one uploaded to `tip_distribution_account`.
+    pub fn validate_tip_distribution_account(
+        &self,
+        address: &Pubkey,
+        tip_distribution_account: &TipDistributionAccount,
+    ) -> Result<(), DistributionTreeError> {
+        let merkle_root = tip_distribution_account
+            .merkle_root
+            .as_ref()
+            .ok_or(DistributionTreeError::RootNotUploaded)?;
+        let leaf_format = match tip_distribution_account.claim_mode {
+            ClaimMode::ClaimStatus => LeafFormat::Unindexed,
+            ClaimMode::Bitmap => LeafFormat::Indexed,
+        };
+        if self.leaf_format != leaf_format {
+            return Err(DistributionTreeError::Mismatch("leaf_format"));
+        }
+        self.validate(
+            address,
+            tip_distribution_account.epoch_created_at,
+            &merkle_root.root,
+            merkle_root.max_total_claim,
+            merkle_root.max_num_nodes,
+        )
+    }
+
+    /// Checks the tree is consistent and is the one uploaded to `priority_fee_distribution_account`.
+    pub fn validate_priority_fee_distribution_account(
+        &self,
+        address: &Pubkey,
+        priority_fee_distribution_account: &PriorityFeeDistributionAccount,
+    ) -> Result<(), DistributionTreeError> {
+        let merkle_root = priority_fee_distribution_account
+            .merkle_root
+            .as_ref()
+            .ok_or(DistributionTreeError::RootNotUploaded)?;
+        // priority fee distribution accounts only support claim status accounts
+        if self.leaf_format != LeafFormat::Unindexed {
+            return Err(DistributionTreeError::Mismatch("leaf_format"));
+        }
+        self.validate(
+            address,
+            priority_fee_distribution_account.epoch_created_at,
+            &merkle_root.root,
+            merkle_root.max_total_claim,
+            merkle_root.max_num_nodes,
+        )
+    }
+
+    fn validate(
+        &self,
+        address: &Pubkey,
+        epoch: u64,
+        root: &[u8; 32],
+        max_total_claim: u64,
+        max_num_nodes: u64,
+    ) -> Result<(), DistributionTreeError> {
+        if self.distribution_account != *address {
+            return Err(DistributionTreeError::Mismatch("distribution_account"));
+        }
+        if self.epoch != epoch {
+            return Err(DistributionTreeError::Mismatch("epoch"));
+        }
+        if self.merkle_root != *root {
+            return Err(DistributionTreeError::Mismatch("merkle_root"));
+        }
+        if self.max_total_claim != max_total_claim {
+            return Err(DistributionTreeError::Mismatch("max_total_claim"));
+        }
+        if self.max_num_nodes != max_num_nodes {
+            return Err(DistributionTreeError::Mismatch("max_num_nodes"));
+        }
+
+        self.verify()
+    }
+}
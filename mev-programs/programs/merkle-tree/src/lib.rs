@@ -0,0 +1,286 @@
+//! Builds the merkle trees whose roots are uploaded to tip distribution and priority fee
+//! distribution accounts, using the same hashing scheme as their `merkle_proof::verify`:
+//! - leaves are `hashv([0u8, hashv([claimant, amount_le])])`
+//! - intermediate nodes are `hashv([1u8, min(a, b), max(a, b)])`
+//! - a node without a sibling is hashed with itself, as the existing Jito tree builders do
+pub mod distribution_tree;
+mod serde_base58;
+
+use std::{collections::HashSet, fmt};
+
+use serde_derive::{Deserialize, Serialize};
+use solana_program::{hash::hashv, pubkey::Pubkey};
+
+const LEAF_PREFIX: &[u8] = &[0];
+const INTERMEDIATE_PREFIX: &[u8] = &[1];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MerkleTreeError {
+    /// A tree needs at least one node.
+    Empty,
+    /// Each claimant can only claim once from a distribution account.
+    DuplicateClaimant(Pubkey),
+    /// The sum of all amounts overflows a u64.
+    ArithmeticOverflow,
+}
+
+impl fmt::Display for MerkleTreeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "merkle tree has no nodes"),
+            Self::DuplicateClaimant(claimant) => write!(f, "duplicate claimant {claimant}"),
+            Self::ArithmeticOverflow => write!(f, "total claim overflows a u64"),
+        }
+    }
+}
+
+impl std::error::Error for MerkleTreeError {}
+
+/// The leaf a claim is verified against.
+pub fn hash_leaf(claimant: &Pubkey, amount: u64) -> [u8; 32] {
+    let data = hashv(&[claimant.as_ref(), &amount.to_le_bytes()]);
+    hashv(&[LEAF_PREFIX, data.as_ref()]).to_bytes()
+}
+
+/// The leaf a claim is verified against for distribution accounts tracking claims in a bitmap,
+/// `index` being the leaf's position in the tree.
+pub fn hash_indexed_leaf(claimant: &Pubkey, amount: u64, index: u64) -> [u8; 32] {
+    let data = hashv(&[
+        claimant.as_ref(),
+        &amount.to_le_bytes(),
+        &index.to_le_bytes(),
+    ]);
+    hashv(&[LEAF_PREFIX, data.as_ref()]).to_bytes()
+}
+
+/// Pairs are sorted before hashing, so proofs don't need to encode a direction.
+pub fn hash_intermediate(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (first, second) = if a <= b { (a, b) } else { (b, a) };
+    hashv(&[INTERMEDIATE_PREFIX, first, second]).to_bytes()
+}
+
+/// How a tree's leaves are hashed, which depends on how the distribution account tracks claims.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LeafFormat {
+    /// [hash_leaf], for accounts creating a claim status account per claim.
+    #[default]
+    Unindexed,
+    /// [hash_indexed_leaf], for accounts tracking claims in a bitmap.
+    Indexed,
+}
+
+impl LeafFormat {
+    /// The leaf for the claim at position `index` in the tree.
+    pub fn hash(&self, claimant: &Pubkey, amount: u64, index: u64) -> [u8; 32] {
+        match self {
+            Self::Unindexed => hash_leaf(claimant, amount),
+            Self::Indexed => hash_indexed_leaf(claimant, amount, index),
+        }
+    }
+}
+
+/// A merkle tree over already hashed leaves.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MerkleTree {
+    /// The leaves first, the root last.
+    layers: Vec<Vec<[u8; 32]>>,
+}
+
+impl MerkleTree {
+    pub fn new(leaves: Vec<[u8; 32]>) -> Result<Self, MerkleTreeError> {
+        if leaves.is_empty() {
+            return Err(MerkleTreeError::Empty);
+        }
+
+        let mut layers = vec![leaves];
+        while let Some(layer) = layers.last().filter(|layer| layer.len() > 1) {
+            let next_layer = layer
+                .chunks(2)
+                .map(|pair| match pair {
+                    [a, b] => hash_intermediate(a, b),
+                    [a] => hash_intermediate(a, a),
+                    _ => unreachable!(),
+                })
+                .collect();
+            layers.push(next_layer);
+        }
+
+        Ok(Self { layers })
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        // `new` ensures there is at least the leaf layer
+        self.layers.last().unwrap()[0]
+    }
+
+    pub fn leaves(&self) -> &[[u8; 32]] {
+        &self.layers[0]
+    }
+
+    /// The sibling hashes from the leaf at `index` up to the root, `None` if it's out of range.
+    pub fn proof(&self, index: usize) -> Option<Vec<[u8; 32]>> {
+        if index >= self.leaves().len() {
+            return None;
+        }
+
+        let mut index = index;
+        let mut proof = Vec::with_capacity(self.layers.len());
+        for layer in self.layers.iter().take_while(|layer| layer.len() > 1) {
+            // a node without a sibling is its own
+            proof.push(*layer.get(index ^ 1).unwrap_or(&layer[index]));
+            index /= 2;
+        }
+
+        Some(proof)
+    }
+
+    /// A single proof for the leaves at `indices`, sharing the siblings their paths have in
+    /// common. `None` if the indices aren't strictly ascending or any is out of range.
+    pub fn multiproof(&self, indices: &[usize]) -> Option<MultiProof> {
+        let num_leaves = self.leaves().len();
+        if indices.is_empty()
+            || indices.windows(2).any(|pair| pair[0] >= pair[1])
+            || indices.iter().any(|index| *index >= num_leaves)
+        {
+            return None;
+        }
+
+        // walks the layers the same way the on-chain verifier does, recording each sibling it
+        // can't compute from the nodes it already knows
+        let mut known = indices.to_vec();
+        let mut proof = Vec::new();
+        for layer in self.layers.iter().take_while(|layer| layer.len() > 1) {
+            let mut parents = Vec::with_capacity(known.len());
+            let mut known_iter = known.into_iter().peekable();
+            while let Some(index) = known_iter.next() {
+                let sibling_index = index ^ 1;
+                // a node without a sibling is hashed with itself, so needs no proof element
+                if sibling_index < layer.len() && known_iter.next_if_eq(&sibling_index).is_none() {
+                    proof.push(layer[sibling_index]);
+                }
+                parents.push(index >> 1);
+            }
+            known = parents;
+        }
+
+        Some(MultiProof {
+            indices: indices.iter().map(|index| *index as u64).collect(),
+            num_leaves: num_leaves as u64,
+            proof,
+        })
+    }
+}
+
+/// Proves several leaves of a [MerkleTree] at once, see `merkle_proof::verify_multi`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MultiProof {
+    /// Positions of the proved leaves, ascending.
+    pub indices: Vec<u64>,
+    pub num_leaves: u64,
+    pub proof: Vec<[u8; 32]>,
+}
+
+/// A claimant's entitlement and the proof it's claimed with.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TreeNode {
+    #[serde(with = "serde_base58::pubkey")]
+    pub claimant: Pubkey,
+    pub amount: u64,
+    #[serde(with = "serde_base58::hashes")]
+    pub proof: Vec<[u8; 32]>,
+}
+
+/// Everything needed to upload a merkle root and for claimants to claim against it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GeneratedMerkleTree {
+    pub merkle_root: [u8; 32],
+    /// Sum of all amounts, uploaded as the root's `max_total_claim`.
+    pub max_total_claim: u64,
+    /// Number of nodes, uploaded as the root's `max_num_nodes`.
+    pub max_num_nodes: u64,
+    /// In the order the claims were given.
+    pub tree_nodes: Vec<TreeNode>,
+    pub leaf_format: LeafFormat,
+}
+
+impl GeneratedMerkleTree {
+    pub fn new(claims: impl IntoIterator<Item = (Pubkey, u64)>) -> Result<Self, MerkleTreeError> {
+        Self::with_leaf_format(claims, LeafFormat::Unindexed)
+    }
+
+    /// Builds the tree from [hash_indexed_leaf]s, for distribution accounts tracking claims in a
+    /// bitmap. Each node's index is its position in `tree_nodes`.
+    pub fn new_indexed(
+        claims: impl IntoIterator<Item = (Pubkey, u64)>,
+    ) -> Result<Self, MerkleTreeError> {
+        Self::with_leaf_format(claims, LeafFormat::Indexed)
+    }
+
+    pub fn with_leaf_format(
+        claims: impl IntoIterator<Item = (Pubkey, u64)>,
+        leaf_format: LeafFormat,
+    ) -> Result<Self, MerkleTreeError> {
+        let claims: Vec<(Pubkey, u64)> = claims.into_iter().collect();
+
+        let mut claimants = HashSet::with_capacity(claims.len());
+        let mut max_total_claim: u64 = 0;
+        for (claimant, amount) in &claims {
+            if !claimants.insert(*claimant) {
+                return Err(MerkleTreeError::DuplicateClaimant(*claimant));
+            }
+            max_total_claim = max_total_claim
+                .checked_add(*amount)
+                .ok_or(MerkleTreeError::ArithmeticOverflow)?;
+        }
+
+        let tree = MerkleTree::new(
+            claims
+                .iter()
+                .enumerate()
+                .map(|(index, (claimant, amount))| {
+                    leaf_format.hash(claimant, *amount, index as u64)
+                })
+                .collect(),
+        )?;
+
+        let tree_nodes = claims
+            .into_iter()
+            .enumerate()
+            .map(|(index, (claimant, amount))| TreeNode {
+                claimant,
+                amount,
+                proof: tree.proof(index).unwrap(),
+            })
+            .collect::<Vec<_>>();
+
+        Ok(Self {
+            merkle_root: tree.root(),
+            max_total_claim,
+            max_num_nodes: tree_nodes.len() as u64,
+            tree_nodes,
+            leaf_format,
+        })
+    }
+
+    /// A multiproof for the nodes at `indices` of `tree_nodes`, see [MerkleTree::multiproof].
+    pub fn multiproof(&self, indices: &[usize]) -> Option<MultiProof> {
+        MerkleTree::new(
+            self.tree_nodes
+                .iter()
+                .enumerate()
+                .map(|(index, node)| {
+                    self.leaf_format
+                        .hash(&node.claimant, node.amount, index as u64)
+                })
+                .collect(),
+        )
+        .ok()?
+        .multiproof(indices)
+    }
+
+    pub fn find_node(&self, claimant: &Pubkey) -> Option<&TreeNode> {
+        self.tree_nodes
+            .iter()
+            .find(|node| node.claimant == *claimant)
+    }
+}
@@ -0,0 +1,72 @@
+//! Hashes and pubkeys are base58 strings in human readable formats, and raw bytes otherwise.
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+use solana_program::pubkey::Pubkey;
+
+pub(crate) mod hash {
+    use super::*;
+
+    pub(crate) fn serialize<S: Serializer>(
+        bytes: &[u8; 32],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&bs58::encode(bytes).into_string())
+        } else {
+            bytes.serialize(serializer)
+        }
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<[u8; 32], D::Error> {
+        if deserializer.is_human_readable() {
+            let encoded = String::deserialize(deserializer)?;
+            bs58::decode(&encoded)
+                .into_vec()
+                .map_err(D::Error::custom)?
+                .try_into()
+                .map_err(|_| D::Error::custom(format!("{encoded} is not 32 bytes")))
+        } else {
+            <[u8; 32]>::deserialize(deserializer)
+        }
+    }
+}
+
+pub(crate) mod pubkey {
+    use super::*;
+
+    pub(crate) fn serialize<S: Serializer>(
+        pubkey: &Pubkey,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        hash::serialize(&pubkey.to_bytes(), serializer)
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Pubkey, D::Error> {
+        hash::deserialize(deserializer).map(Pubkey::new_from_array)
+    }
+}
+
+pub(crate) mod hashes {
+    use super::*;
+
+    #[derive(serde_derive::Serialize, serde_derive::Deserialize)]
+    #[serde(transparent)]
+    struct Hash(#[serde(with = "hash")] [u8; 32]);
+
+    pub(crate) fn serialize<S: Serializer>(
+        hashes: &[[u8; 32]],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(hashes.iter().map(|hash| Hash(*hash)))
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<[u8; 32]>, D::Error> {
+        Vec::<Hash>::deserialize(deserializer)
+            .map(|hashes| hashes.into_iter().map(|Hash(hash)| hash).collect())
+    }
+}
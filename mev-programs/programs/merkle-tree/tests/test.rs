@@ -0,0 +1,361 @@
+use jito_merkle_tree::{
+    distribution_tree::{DistributionTree, DistributionTreeError},
+    hash_indexed_leaf, hash_intermediate, hash_leaf, GeneratedMerkleTree, LeafFormat, MerkleTree,
+    MerkleTreeError,
+};
+use jito_priority_fee_distribution::state::PriorityFeeDistributionAccount;
+use jito_tip_distribution::state::{ClaimMode, TipDistributionAccount};
+use solana_program::pubkey::Pubkey;
+
+fn claims(num_claims: u64) -> Vec<(Pubkey, u64)> {
+    (1..=num_claims)
+        .map(|amount| (Pubkey::new_unique(), amount * 1_000))
+        .collect()
+}
+
+#[test]
+fn test_proofs_verify_on_chain() {
+    for num_claims in 1..=17 {
+        let tree = GeneratedMerkleTree::new(claims(num_claims)).unwrap();
+
+        for node in &tree.tree_nodes {
+            let leaf = hash_leaf(&node.claimant, node.amount);
+            assert!(jito_tip_distribution::merkle_proof::verify(
+                node.proof.clone(),
+                tree.merkle_root,
+                leaf
+            ));
+            assert!(jito_priority_fee_distribution::merkle_proof::verify(
+                node.proof.clone(),
+                tree.merkle_root,
+                leaf
+            ));
+        }
+    }
+}
+
+#[test]
+fn test_indexed_proofs_verify_on_chain() {
+    let tree = GeneratedMerkleTree::new_indexed(claims(9)).unwrap();
+    assert_eq!(tree.leaf_format, LeafFormat::Indexed);
+
+    for (index, node) in tree.tree_nodes.iter().enumerate() {
+        let leaf = hash_indexed_leaf(&node.claimant, node.amount, index as u64);
+        assert!(jito_tip_distribution::merkle_proof::verify(
+            node.proof.clone(),
+            tree.merkle_root,
+            leaf
+        ));
+        // the same claim at another position doesn't verify
+        assert!(!jito_tip_distribution::merkle_proof::verify(
+            node.proof.clone(),
+            tree.merkle_root,
+            hash_indexed_leaf(&node.claimant, node.amount, index as u64 + 1)
+        ));
+    }
+}
+
+#[test]
+fn test_wrong_amount_does_not_verify() {
+    let tree = GeneratedMerkleTree::new(claims(5)).unwrap();
+
+    let node = &tree.tree_nodes[2];
+    assert!(!jito_tip_distribution::merkle_proof::verify(
+        node.proof.clone(),
+        tree.merkle_root,
+        hash_leaf(&node.claimant, node.amount + 1)
+    ));
+}
+
+#[test]
+fn test_max_total_claim_and_max_num_nodes() {
+    let tree = GeneratedMerkleTree::new(claims(4)).unwrap();
+
+    assert_eq!(tree.max_total_claim, 10_000);
+    assert_eq!(tree.max_num_nodes, 4);
+}
+
+#[test]
+fn test_single_node_root_is_the_leaf() {
+    let claimant = Pubkey::new_unique();
+    let tree = GeneratedMerkleTree::new([(claimant, 42)]).unwrap();
+
+    assert_eq!(tree.merkle_root, hash_leaf(&claimant, 42));
+    assert!(tree.find_node(&claimant).unwrap().proof.is_empty());
+}
+
+#[test]
+fn test_proof_out_of_range() {
+    let tree = MerkleTree::new(vec![[0; 32], [1; 32], [2; 32]]).unwrap();
+
+    // the unpaired third leaf is its own sibling, then pairs with the hash of the first two
+    assert_eq!(
+        tree.proof(2).unwrap(),
+        vec![[2; 32], hash_intermediate(&[0; 32], &[1; 32])]
+    );
+    assert_eq!(
+        tree.root(),
+        hash_intermediate(
+            &hash_intermediate(&[0; 32], &[1; 32]),
+            &hash_intermediate(&[2; 32], &[2; 32])
+        )
+    );
+    assert!(tree.proof(3).is_none());
+}
+
+#[test]
+fn test_root_matches_existing_tree_builder() {
+    // root of the same claims built with the tip distributor's merkle tree, which hashes an
+    // unpaired node with itself
+    let claims = (1..=5u8).map(|i| (Pubkey::new_from_array([i; 32]), i as u64 * 1_000));
+    let tree = GeneratedMerkleTree::new(claims).unwrap();
+
+    assert_eq!(
+        bs58::encode(tree.merkle_root).into_string(),
+        "BWAFgHTkzhUnNRj6ri8UMadaYaJ8JS5fTypn1yMwDTBC"
+    );
+}
+
+#[test]
+fn test_invalid_claims() {
+    assert_eq!(
+        GeneratedMerkleTree::new([]).unwrap_err(),
+        MerkleTreeError::Empty
+    );
+
+    let claimant = Pubkey::new_unique();
+    assert_eq!(
+        GeneratedMerkleTree::new([(claimant, 1), (claimant, 2)]).unwrap_err(),
+        MerkleTreeError::DuplicateClaimant(claimant)
+    );
+
+    assert_eq!(
+        GeneratedMerkleTree::new([(Pubkey::new_unique(), u64::MAX), (Pubkey::new_unique(), 1)])
+            .unwrap_err(),
+        MerkleTreeError::ArithmeticOverflow
+    );
+}
+
+fn distribution_tree() -> DistributionTree {
+    DistributionTree::new(
+        Pubkey::new_unique(),
+        42,
+        GeneratedMerkleTree::new(claims(5)).unwrap(),
+    )
+}
+
+#[test]
+fn test_distribution_tree_round_trip() {
+    let tree = distribution_tree();
+
+    let json = tree.to_json().unwrap();
+    assert!(json.contains(&tree.tree_nodes[0].claimant.to_string()));
+    assert_eq!(DistributionTree::from_json(&json).unwrap(), tree);
+
+    let bytes = tree.to_bytes().unwrap();
+    assert!(bytes.len() < json.len());
+    assert_eq!(DistributionTree::from_bytes(&bytes).unwrap(), tree);
+}
+
+#[test]
+fn test_distribution_tree_reads_v1() {
+    let tree = distribution_tree();
+    let mut node = tree.tree_nodes[0].clone();
+    // a single leaf is its own root
+    node.proof = vec![];
+    let v1 = DistributionTree {
+        max_total_claim: node.amount,
+        max_num_nodes: 1,
+        merkle_root: hash_leaf(&node.claimant, node.amount),
+        tree_nodes: vec![node.clone()],
+        ..tree
+    };
+    let json = format!(
+        r#"{{"V1": {{
+            "distribution_account": "{}",
+            "epoch": {},
+            "merkle_root": "{}",
+            "max_total_claim": {},
+            "max_num_nodes": 1,
+            "tree_nodes": [{{"claimant": "{}", "amount": {}, "proof": []}}],
+            "leaf_format": "Unindexed"
+        }}}}"#,
+        v1.distribution_account,
+        v1.epoch,
+        bs58::encode(v1.merkle_root).into_string(),
+        node.amount,
+        node.claimant,
+        node.amount,
+    );
+
+    let read = DistributionTree::from_json(&json).unwrap();
+    assert_eq!(read, v1);
+    read.verify().unwrap();
+}
+
+#[test]
+fn test_distribution_tree_verify() {
+    let mut tree = distribution_tree();
+    tree.verify().unwrap();
+
+    tree.tree_nodes[1].amount += 1;
+    assert!(matches!(
+        tree.verify().unwrap_err(),
+        DistributionTreeError::InvalidTree
+    ));
+}
+
+#[test]
+fn test_validate_tip_distribution_account() {
+    let tree = distribution_tree();
+    let mut tip_distribution_account = TipDistributionAccount {
+        epoch_created_at: tree.epoch,
+        ..TipDistributionAccount::default()
+    };
+    assert!(matches!(
+        tree.validate_tip_distribution_account(
+            &tree.distribution_account,
+            &tip_distribution_account
+        )
+        .unwrap_err(),
+        DistributionTreeError::RootNotUploaded
+    ));
+
+    tip_distribution_account.merkle_root = Some(jito_tip_distribution::state::MerkleRoot {
+        root: tree.merkle_root,
+        max_total_claim: tree.max_total_claim,
+        max_num_nodes: tree.max_num_nodes,
+        ..Default::default()
+    });
+    tree.validate_tip_distribution_account(&tree.distribution_account, &tip_distribution_account)
+        .unwrap();
+    assert!(matches!(
+        tree.validate_tip_distribution_account(&Pubkey::new_unique(), &tip_distribution_account)
+            .unwrap_err(),
+        DistributionTreeError::Mismatch("distribution_account")
+    ));
+
+    tip_distribution_account.claim_mode = ClaimMode::Bitmap;
+    assert!(matches!(
+        tree.validate_tip_distribution_account(
+            &tree.distribution_account,
+            &tip_distribution_account
+        )
+        .unwrap_err(),
+        DistributionTreeError::Mismatch("leaf_format")
+    ));
+}
+
+#[test]
+fn test_validate_priority_fee_distribution_account() {
+    let tree = distribution_tree();
+    let priority_fee_distribution_account = PriorityFeeDistributionAccount {
+        epoch_created_at: tree.epoch,
+        merkle_root: Some(jito_priority_fee_distribution::state::MerkleRoot {
+            root: tree.merkle_root,
+            max_total_claim: tree.max_total_claim + 1,
+            max_num_nodes: tree.max_num_nodes,
+            ..Default::default()
+        }),
+        ..PriorityFeeDistributionAccount::default()
+    };
+
+    assert!(matches!(
+        tree.validate_priority_fee_distribution_account(
+            &tree.distribution_account,
+            &priority_fee_distribution_account
+        )
+        .unwrap_err(),
+        DistributionTreeError::Mismatch("max_total_claim")
+    ));
+}
+
+/// Every subset of leaves of a small tree, or a spread of subsets for larger ones.
+fn index_sets(num_leaves: usize) -> Vec<Vec<usize>> {
+    if num_leaves <= 8 {
+        (1..1usize << num_leaves)
+            .map(|mask| (0..num_leaves).filter(|i| mask & (1 << i) != 0).collect())
+            .collect()
+    } else {
+        (1..num_leaves)
+            .map(|step| (0..num_leaves).step_by(step).collect())
+            .chain([vec![num_leaves - 1], vec![0, num_leaves - 1]])
+            .collect()
+    }
+}
+
+#[test]
+fn test_multiproofs_verify_on_chain() {
+    for num_claims in 1..=17 {
+        let tree = GeneratedMerkleTree::new(claims(num_claims)).unwrap();
+
+        for indices in index_sets(num_claims as usize) {
+            let multiproof = tree.multiproof(&indices).unwrap();
+            let leaves = indices
+                .iter()
+                .map(|i| {
+                    let node = &tree.tree_nodes[*i];
+                    (*i as u64, hash_leaf(&node.claimant, node.amount))
+                })
+                .collect::<Vec<_>>();
+
+            // shared siblings are only included once
+            let total_proof_len: usize = indices
+                .iter()
+                .map(|i| tree.tree_nodes[*i].proof.len())
+                .sum();
+            assert!(multiproof.proof.len() <= total_proof_len);
+
+            assert!(jito_tip_distribution::merkle_proof::verify_multi(
+                multiproof.proof,
+                tree.merkle_root,
+                multiproof.num_leaves,
+                leaves
+            ));
+        }
+    }
+}
+
+#[test]
+fn test_invalid_multiproofs() {
+    let tree = GeneratedMerkleTree::new(claims(6)).unwrap();
+    let leaf = |i: usize| {
+        let node = &tree.tree_nodes[i];
+        (i as u64, hash_leaf(&node.claimant, node.amount))
+    };
+    let multiproof = tree.multiproof(&[1, 4]).unwrap();
+    let verify = |proof: Vec<[u8; 32]>, num_leaves, leaves| {
+        jito_tip_distribution::merkle_proof::verify_multi(
+            proof,
+            tree.merkle_root,
+            num_leaves,
+            leaves,
+        )
+    };
+
+    assert!(verify(multiproof.proof.clone(), 6, vec![leaf(1), leaf(4)]));
+    // wrong amount
+    let mut wrong_amount = leaf(4);
+    wrong_amount.1 = hash_leaf(&tree.tree_nodes[4].claimant, 1);
+    assert!(!verify(
+        multiproof.proof.clone(),
+        6,
+        vec![leaf(1), wrong_amount]
+    ));
+    // out of order, duplicated and out of range leaves
+    assert!(!verify(multiproof.proof.clone(), 6, vec![leaf(4), leaf(1)]));
+    assert!(!verify(multiproof.proof.clone(), 6, vec![leaf(1), leaf(1)]));
+    assert!(!verify(multiproof.proof.clone(), 4, vec![leaf(1), leaf(4)]));
+    assert!(!verify(multiproof.proof.clone(), 6, vec![]));
+    // missing and surplus proof elements
+    let mut short = multiproof.proof.clone();
+    short.pop();
+    assert!(!verify(short, 6, vec![leaf(1), leaf(4)]));
+    let mut long = multiproof.proof;
+    long.push([0; 32]);
+    assert!(!verify(long, 6, vec![leaf(1), leaf(4)]));
+
+    assert!(tree.multiproof(&[]).is_none());
+    assert!(tree.multiproof(&[4, 1]).is_none());
+    assert!(tree.multiproof(&[6]).is_none());
+}
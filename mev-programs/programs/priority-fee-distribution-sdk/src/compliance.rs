@@ -0,0 +1,32 @@
+//! Checks a validator's priority fee transfers against the revenue it attested to, so stake
+//! pools can flag under-delivering validators before the merkle root is uploaded.
+use anchor_lang::Result;
+use jito_priority_fee_distribution::state::PriorityFeeDistributionAccount;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ComplianceStatus {
+    /// The validator hasn't attested to its priority fee revenue.
+    NotAttested,
+    /// The validator transferred at least the share of its revenue it committed to.
+    OnTrack,
+    /// The validator transferred less than it committed to.
+    UnderDelivering { shortfall_lamports: u64 },
+}
+
+/// Compares [PriorityFeeDistributionAccount::total_lamports_transferred] with the validator's
+/// [jito_priority_fee_distribution::state::PriorityFeeRevenueAttestation].
+pub fn compliance_status(
+    priority_fee_distribution_account: &PriorityFeeDistributionAccount,
+) -> Result<ComplianceStatus> {
+    if priority_fee_distribution_account
+        .priority_fee_revenue_attestation
+        .is_none()
+    {
+        return Ok(ComplianceStatus::NotAttested);
+    }
+
+    match priority_fee_distribution_account.attested_shortfall_lamports()? {
+        0 => Ok(ComplianceStatus::OnTrack),
+        shortfall_lamports => Ok(ComplianceStatus::UnderDelivering { shortfall_lamports }),
+    }
+}
@@ -0,0 +1,70 @@
+//! Decodes the events the priority-fee-distribution program emits to transaction logs.
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use jito_priority_fee_distribution::{
+    ClaimStatusClosedEvent, ClaimedEvent, ConfigUpdatedEvent,
+    MerkleRootUploadAuthorityUpdatedEvent, MerkleRootUploadedEvent,
+    PriorityFeeDistributionAccountClosedEvent, PriorityFeeDistributionAccountInitializedEvent,
+    PriorityFeeRevenueAttestedEvent, ValidatorCommissionBpsUpdatedEvent,
+};
+pub use jito_programs_sdk_common::event::{decode_events, Event};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PriorityFeeDistributionEvent {
+    PriorityFeeDistributionAccountInitialized(PriorityFeeDistributionAccountInitializedEvent),
+    ValidatorCommissionBpsUpdated(ValidatorCommissionBpsUpdatedEvent),
+    MerkleRootUploadAuthorityUpdated(MerkleRootUploadAuthorityUpdatedEvent),
+    ConfigUpdated(ConfigUpdatedEvent),
+    Claimed(ClaimedEvent),
+    MerkleRootUploaded(MerkleRootUploadedEvent),
+    PriorityFeeDistributionAccountClosed(PriorityFeeDistributionAccountClosedEvent),
+    ClaimStatusClosed(ClaimStatusClosedEvent),
+    PriorityFeeRevenueAttested(PriorityFeeRevenueAttestedEvent),
+}
+
+impl Event for PriorityFeeDistributionEvent {
+    fn decode(data: &[u8]) -> Option<Self> {
+        let (discriminator, mut data) = data.split_at_checked(8)?;
+        match discriminator {
+            PriorityFeeDistributionAccountInitializedEvent::DISCRIMINATOR => {
+                PriorityFeeDistributionAccountInitializedEvent::deserialize(&mut data)
+                    .ok()
+                    .map(Self::PriorityFeeDistributionAccountInitialized)
+            }
+            ValidatorCommissionBpsUpdatedEvent::DISCRIMINATOR => {
+                ValidatorCommissionBpsUpdatedEvent::deserialize(&mut data)
+                    .ok()
+                    .map(Self::ValidatorCommissionBpsUpdated)
+            }
+            MerkleRootUploadAuthorityUpdatedEvent::DISCRIMINATOR => {
+                MerkleRootUploadAuthorityUpdatedEvent::deserialize(&mut data)
+                    .ok()
+                    .map(Self::MerkleRootUploadAuthorityUpdated)
+            }
+            ConfigUpdatedEvent::DISCRIMINATOR => ConfigUpdatedEvent::deserialize(&mut data)
+                .ok()
+                .map(Self::ConfigUpdated),
+            ClaimedEvent::DISCRIMINATOR => {
+                ClaimedEvent::deserialize(&mut data).ok().map(Self::Claimed)
+            }
+            MerkleRootUploadedEvent::DISCRIMINATOR => {
+                MerkleRootUploadedEvent::deserialize(&mut data)
+                    .ok()
+                    .map(Self::MerkleRootUploaded)
+            }
+            PriorityFeeDistributionAccountClosedEvent::DISCRIMINATOR => {
+                PriorityFeeDistributionAccountClosedEvent::deserialize(&mut data)
+                    .ok()
+                    .map(Self::PriorityFeeDistributionAccountClosed)
+            }
+            ClaimStatusClosedEvent::DISCRIMINATOR => ClaimStatusClosedEvent::deserialize(&mut data)
+                .ok()
+                .map(Self::ClaimStatusClosed),
+            PriorityFeeRevenueAttestedEvent::DISCRIMINATOR => {
+                PriorityFeeRevenueAttestedEvent::deserialize(&mut data)
+                    .ok()
+                    .map(Self::PriorityFeeRevenueAttested)
+            }
+            _ => None,
+        }
+    }
+}
@@ -1,10 +1,12 @@
 use anchor_lang::{prelude::*, solana_program::clock::Clock};
+use jito_programs_vote_state::VoteState;
 #[cfg(not(feature = "no-entrypoint"))]
 use solana_security_txt::security_txt;
 
 use crate::{
     state::{
         ClaimStatus, Config, MerkleRoot, MerkleRootUploadConfig, PriorityFeeDistributionAccount,
+        PriorityFeeRevenueAttestation,
     },
     ErrorCode::Unauthorized,
 };
@@ -24,13 +26,14 @@ security_txt! {
 }
 
 pub mod merkle_proof;
+#[cfg(feature = "sdk")]
+pub mod sdk;
 pub mod state;
 
 declare_id!("Priority6weCZ5HwDn29NxLFpb7TDp2iLZ6XKc5e8d3");
 
 #[program]
 pub mod jito_priority_fee_distribution {
-    use jito_programs_vote_state::VoteState;
     use solana_program::native_token::lamports_to_sol;
 
     use super::*;
@@ -65,16 +68,20 @@ pub mod jito_priority_fee_distribution {
         validator_commission_bps: u16,
         bump: u8,
     ) -> Result<()> {
-        if validator_commission_bps > ctx.accounts.config.max_validator_commission_bps {
-            return Err(MaxValidatorCommissionFeeBpsExceeded.into());
-        }
-
-        let validator_vote_account_node_pubkey =
-            VoteState::deserialize_node_pubkey(&ctx.accounts.validator_vote_account)?;
-        if validator_vote_account_node_pubkey != *ctx.accounts.signer.key {
+        // either the hot node identity or the cold withdraw authority can create the account
+        let validator_vote_account = &ctx.accounts.validator_vote_account;
+        let signer = ctx.accounts.signer.key;
+        if VoteState::deserialize_node_pubkey(validator_vote_account)? != *signer
+            && VoteState::deserialize_authorized_withdrawer(validator_vote_account)?
+                != Some(*signer)
+        {
             return Err(Unauthorized.into());
         }
 
+        let validator_commission_bps = ctx
+            .accounts
+            .config
+            .validator_commission_bps(validator_commission_bps, validator_vote_account)?;
         let current_epoch = Clock::get()?.epoch;
 
         let distribution_acc = &mut ctx.accounts.priority_fee_distribution_account;
@@ -83,6 +90,7 @@ pub mod jito_priority_fee_distribution {
         distribution_acc.validator_commission_bps = validator_commission_bps;
         distribution_acc.merkle_root_upload_authority = merkle_root_upload_authority;
         distribution_acc.merkle_root = None;
+        distribution_acc.priority_fee_revenue_attestation = None;
         distribution_acc.expires_at = current_epoch
             .checked_add(ctx.accounts.config.num_epochs_valid)
             .ok_or(ArithmeticError)?;
@@ -96,6 +104,39 @@ pub mod jito_priority_fee_distribution {
         Ok(())
     }
 
+    /// Records the priority fee revenue the validator earned in the epoch and the share of it the
+    /// validator committed to transfer, so stake pools can check `total_lamports_transferred`
+    /// against it. May be invoked many times until the merkle root is uploaded. Only the
+    /// validator's node identity can invoke this.
+    pub fn attest_priority_fee_revenue(
+        ctx: Context<AttestPriorityFeeRevenue>,
+        priority_fee_revenue_lamports: u64,
+        committed_share_bps: u16,
+    ) -> Result<()> {
+        AttestPriorityFeeRevenue::auth(&ctx)?;
+
+        let distribution_account = &mut ctx.accounts.priority_fee_distribution_account;
+        if distribution_account.merkle_root.is_some() {
+            return Err(MerkleRootAlreadyUploaded.into());
+        }
+
+        let attestation = PriorityFeeRevenueAttestation {
+            priority_fee_revenue_lamports,
+            committed_share_bps,
+        };
+        attestation.validate()?;
+        distribution_account.priority_fee_revenue_attestation = Some(attestation);
+
+        emit!(PriorityFeeRevenueAttestedEvent {
+            priority_fee_distribution_account: distribution_account.key(),
+            priority_fee_revenue_lamports,
+            committed_share_bps,
+            total_lamports_transferred: distribution_account.total_lamports_transferred,
+        });
+
+        Ok(())
+    }
+
     /// Update config fields. Only the [Config] authority can invoke this.
     pub fn update_config(ctx: Context<UpdateConfig>, new_config: Config) -> Result<()> {
         UpdateConfig::auth(&ctx)?;
@@ -106,6 +147,8 @@ pub mod jito_priority_fee_distribution {
         config.num_epochs_valid = new_config.num_epochs_valid;
         config.max_validator_commission_bps = new_config.max_validator_commission_bps;
         config.go_live_epoch = new_config.go_live_epoch;
+        config.strict_transfer_source = new_config.strict_transfer_source;
+        config.vote_commission_policy = new_config.vote_commission_policy;
         config.validate()?;
 
         emit!(ConfigUpdatedEvent {
@@ -313,6 +356,32 @@ pub mod jito_priority_fee_distribution {
         Ok(())
     }
 
+    /// Sets a new `merkle_root_upload_authority` on the validator's [PriorityFeeDistributionAccount], e.g. to
+    /// switch distribution providers. Only the validator's node identity has the authority to
+    /// invoke, before a merkle root is uploaded.
+    pub fn update_merkle_root_upload_authority(
+        ctx: Context<UpdateMerkleRootUploadAuthority>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        UpdateMerkleRootUploadAuthority::auth(&ctx)?;
+
+        let distribution_account = &mut ctx.accounts.priority_fee_distribution_account;
+        if distribution_account.merkle_root.is_some() {
+            return Err(MerkleRootAlreadyUploaded.into());
+        }
+
+        let old_authority = distribution_account.merkle_root_upload_authority;
+        distribution_account.merkle_root_upload_authority = new_authority;
+        distribution_account.validate()?;
+
+        emit!(MerkleRootUploadAuthorityUpdatedEvent {
+            old_authority,
+            new_authority,
+        });
+
+        Ok(())
+    }
+
     pub fn migrate_tda_merkle_root_upload_authority(
         ctx: Context<MigrateTdaMerkleRootUploadAuthority>,
     ) -> Result<()> {
@@ -338,10 +407,15 @@ pub mod jito_priority_fee_distribution {
         Ok(())
     }
 
+    /// Transfers `lamports` from `from` to the current epoch's [PriorityFeeDistributionAccount].
+    /// When [Config::strict_transfer_source] is set, `from` must be the node identity of the
+    /// account's validator vote account, which must then be supplied.
     pub fn transfer_priority_fee_tips(
         ctx: Context<TransferPriorityFeeTips>,
         lamports: u64,
     ) -> Result<()> {
+        TransferPriorityFeeTips::check_transfer_source(&ctx)?;
+
         let epoch = Clock::get()?.epoch;
         // Valdiate the PFDA is in the current epoch
         require!(
@@ -436,6 +510,14 @@ pub enum ErrorCode {
 
     #[msg("TDA not valid for migration.")]
     InvalidTdaForMigration,
+
+    #[msg(
+        "The merkle root has already been uploaded to the given PriorityFeeDistributionAccount."
+    )]
+    MerkleRootAlreadyUploaded,
+
+    #[msg("Validator's commission basis points must be less than or equal to the commission in their vote account.")]
+    VoteAccountCommissionExceeded,
 }
 
 #[derive(Accounts)]
@@ -497,16 +579,45 @@ pub struct InitializePriorityFeeDistributionAccount<'info> {
     pub priority_fee_distribution_account: Account<'info, PriorityFeeDistributionAccount>,
 
     /// CHECK: Safe because we check the vote program is the owner before deserialization.
-    /// The validator's vote account is used to check this transaction's signer is also the authorized withdrawer.
+    /// The validator's vote account is used to check this transaction's signer is the node identity
+    /// or the authorized withdrawer.
     pub validator_vote_account: AccountInfo<'info>,
 
-    /// Must be equal to the supplied validator vote account's authorized withdrawer.
+    /// Must be equal to the supplied validator vote account's node identity or authorized withdrawer.
     #[account(mut)]
     pub signer: Signer<'info>,
 
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct AttestPriorityFeeRevenue<'info> {
+    #[account(
+        mut,
+        has_one = validator_vote_account @ Unauthorized,
+        rent_exempt = enforce
+    )]
+    pub priority_fee_distribution_account: Account<'info, PriorityFeeDistributionAccount>,
+
+    /// CHECK: Safe because we check the vote program is the owner before deserialization.
+    /// The validator's vote account is used to check this transaction's signer is the node identity.
+    pub validator_vote_account: AccountInfo<'info>,
+
+    pub signer: Signer<'info>,
+}
+
+impl AttestPriorityFeeRevenue<'_> {
+    fn auth(ctx: &Context<AttestPriorityFeeRevenue>) -> Result<()> {
+        let validator_vote_account_node_pubkey =
+            VoteState::deserialize_node_pubkey(&ctx.accounts.validator_vote_account)?;
+        if validator_vote_account_node_pubkey != ctx.accounts.signer.key() {
+            Err(Unauthorized.into())
+        } else {
+            Ok(())
+        }
+    }
+}
+
 #[derive(Accounts)]
 pub struct UpdateConfig<'info> {
     #[account(mut, rent_exempt = enforce)]
@@ -706,6 +817,34 @@ impl UpdateMerkleRootUploadConfig<'_> {
     }
 }
 
+#[derive(Accounts)]
+pub struct UpdateMerkleRootUploadAuthority<'info> {
+    #[account(
+        mut,
+        has_one = validator_vote_account @ Unauthorized,
+        rent_exempt = enforce
+    )]
+    pub priority_fee_distribution_account: Account<'info, PriorityFeeDistributionAccount>,
+
+    /// CHECK: Safe because we check the vote program is the owner before deserialization.
+    /// The validator's vote account is used to check this transaction's signer is the node identity.
+    pub validator_vote_account: AccountInfo<'info>,
+
+    pub signer: Signer<'info>,
+}
+
+impl UpdateMerkleRootUploadAuthority<'_> {
+    fn auth(ctx: &Context<UpdateMerkleRootUploadAuthority>) -> Result<()> {
+        let validator_vote_account_node_pubkey =
+            VoteState::deserialize_node_pubkey(&ctx.accounts.validator_vote_account)?;
+        if validator_vote_account_node_pubkey != ctx.accounts.signer.key() {
+            Err(Unauthorized.into())
+        } else {
+            Ok(())
+        }
+    }
+}
+
 #[derive(Accounts)]
 pub struct MigrateTdaMerkleRootUploadAuthority<'info> {
     #[account(mut, rent_exempt = enforce)]
@@ -733,16 +872,46 @@ pub struct TransferPriorityFeeTips<'info> {
     #[account(mut)]
     pub from: Signer<'info>,
     pub system_program: Program<'info, System>,
+
+    /// CHECK: Safe because we check the vote program is the owner before deserialization.
+    /// Only required when [Config::strict_transfer_source] is set, so existing callers may omit it.
+    #[account(
+        address = priority_fee_distribution_account.validator_vote_account @ ErrorCode::AccountValidationFailure
+    )]
+    pub validator_vote_account: Option<AccountInfo<'info>>,
+}
+
+impl TransferPriorityFeeTips<'_> {
+    fn check_transfer_source(ctx: &Context<TransferPriorityFeeTips>) -> Result<()> {
+        if !ctx.accounts.config.strict_transfer_source {
+            return Ok(());
+        }
+
+        let validator_vote_account = ctx
+            .accounts
+            .validator_vote_account
+            .as_ref()
+            .ok_or(ErrorCode::AccountValidationFailure)?;
+        let validator_vote_account_node_pubkey =
+            VoteState::deserialize_node_pubkey(validator_vote_account)?;
+        if validator_vote_account_node_pubkey != *ctx.accounts.from.key {
+            return Err(Unauthorized.into());
+        }
+
+        Ok(())
+    }
 }
 
 // Events
 
 #[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct PriorityFeeDistributionAccountInitializedEvent {
     pub priority_fee_distribution_account: Pubkey,
 }
 
 #[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ValidatorCommissionBpsUpdatedEvent {
     pub priority_fee_distribution_account: Pubkey,
     pub old_commission_bps: u16,
@@ -750,18 +919,31 @@ pub struct ValidatorCommissionBpsUpdatedEvent {
 }
 
 #[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct MerkleRootUploadAuthorityUpdatedEvent {
     pub old_authority: Pubkey,
     pub new_authority: Pubkey,
 }
 
 #[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PriorityFeeRevenueAttestedEvent {
+    pub priority_fee_distribution_account: Pubkey,
+    pub priority_fee_revenue_lamports: u64,
+    pub committed_share_bps: u16,
+    /// Lamports transferred to the account at the time of the attestation.
+    pub total_lamports_transferred: u64,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ConfigUpdatedEvent {
     /// Who updated it.
-    authority: Pubkey,
+    pub authority: Pubkey,
 }
 
 #[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ClaimedEvent {
     /// [PriorityFeeDistributionAccount] claimed from.
     pub priority_fee_distribution_account: Pubkey,
@@ -777,6 +959,7 @@ pub struct ClaimedEvent {
 }
 
 #[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct MerkleRootUploadedEvent {
     /// Who uploaded the root.
     pub merkle_root_upload_authority: Pubkey,
@@ -786,6 +969,7 @@ pub struct MerkleRootUploadedEvent {
 }
 
 #[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct PriorityFeeDistributionAccountClosedEvent {
     /// Account where unclaimed funds were transferred to.
     pub expired_funds_account: Pubkey,
@@ -798,6 +982,7 @@ pub struct PriorityFeeDistributionAccountClosedEvent {
 }
 
 #[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ClaimStatusClosedEvent {
     /// Account where funds were transferred to.
     pub claim_status_payer: Pubkey,
@@ -0,0 +1,542 @@
+//! This module contains functions that build instructions to interact with the
+//! priority-fee-distribution program.
+//!
+//! Every PDA, and its bump, is derived by the builders. Instructions signed by the validator
+//! locate the [crate::state::PriorityFeeDistributionAccount] by vote account and epoch, the rest
+//! take its address as found in the distribution tree.
+use anchor_lang::{
+    prelude::Pubkey, solana_program::instruction::Instruction, system_program, InstructionData,
+    ToAccountMetas,
+};
+
+use super::{
+    derive_claim_status_account_address, derive_config_account_address,
+    derive_merkle_root_upload_config_account_address,
+    derive_priority_fee_distribution_account_address,
+};
+use crate::state::Config;
+
+pub struct InitializeArgs {
+    pub authority: Pubkey,
+    pub expired_funds_account: Pubkey,
+    pub num_epochs_valid: u64,
+    pub max_validator_commission_bps: u16,
+}
+pub struct InitializeAccounts {
+    pub initializer: Pubkey,
+}
+pub fn initialize_ix(
+    program_id: Pubkey,
+    args: InitializeArgs,
+    accounts: InitializeAccounts,
+) -> Instruction {
+    let InitializeArgs {
+        authority,
+        expired_funds_account,
+        num_epochs_valid,
+        max_validator_commission_bps,
+    } = args;
+
+    let InitializeAccounts { initializer } = accounts;
+
+    let (config, bump) = derive_config_account_address(&program_id);
+
+    Instruction {
+        program_id,
+        data: crate::instruction::Initialize {
+            authority,
+            expired_funds_account,
+            num_epochs_valid,
+            max_validator_commission_bps,
+            bump,
+        }
+        .data(),
+        accounts: crate::accounts::Initialize {
+            config,
+            system_program: system_program::ID,
+            initializer,
+        }
+        .to_account_metas(None),
+    }
+}
+
+pub struct InitializePriorityFeeDistributionAccountArgs {
+    pub merkle_root_upload_authority: Pubkey,
+    pub validator_commission_bps: u16,
+}
+pub struct InitializePriorityFeeDistributionAccountAccounts {
+    pub validator_vote_account: Pubkey,
+    /// The current epoch, which the program creates the account for.
+    pub epoch: u64,
+    pub signer: Pubkey,
+}
+pub fn initialize_priority_fee_distribution_account_ix(
+    program_id: Pubkey,
+    args: InitializePriorityFeeDistributionAccountArgs,
+    accounts: InitializePriorityFeeDistributionAccountAccounts,
+) -> Instruction {
+    let InitializePriorityFeeDistributionAccountArgs {
+        merkle_root_upload_authority,
+        validator_commission_bps,
+    } = args;
+
+    let InitializePriorityFeeDistributionAccountAccounts {
+        validator_vote_account,
+        epoch,
+        signer,
+    } = accounts;
+
+    let (priority_fee_distribution_account, bump) =
+        derive_priority_fee_distribution_account_address(
+            &program_id,
+            &validator_vote_account,
+            epoch,
+        );
+
+    Instruction {
+        program_id,
+        data: crate::instruction::InitializePriorityFeeDistributionAccount {
+            merkle_root_upload_authority,
+            validator_commission_bps,
+            bump,
+        }
+        .data(),
+        accounts: crate::accounts::InitializePriorityFeeDistributionAccount {
+            config: derive_config_account_address(&program_id).0,
+            priority_fee_distribution_account,
+            validator_vote_account,
+            signer,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+    }
+}
+
+pub struct AttestPriorityFeeRevenueArgs {
+    pub priority_fee_revenue_lamports: u64,
+    pub committed_share_bps: u16,
+}
+pub struct AttestPriorityFeeRevenueAccounts {
+    pub validator_vote_account: Pubkey,
+    pub epoch: u64,
+    pub signer: Pubkey,
+}
+pub fn attest_priority_fee_revenue_ix(
+    program_id: Pubkey,
+    args: AttestPriorityFeeRevenueArgs,
+    accounts: AttestPriorityFeeRevenueAccounts,
+) -> Instruction {
+    let AttestPriorityFeeRevenueArgs {
+        priority_fee_revenue_lamports,
+        committed_share_bps,
+    } = args;
+
+    let AttestPriorityFeeRevenueAccounts {
+        validator_vote_account,
+        epoch,
+        signer,
+    } = accounts;
+
+    Instruction {
+        program_id,
+        data: crate::instruction::AttestPriorityFeeRevenue {
+            priority_fee_revenue_lamports,
+            committed_share_bps,
+        }
+        .data(),
+        accounts: crate::accounts::AttestPriorityFeeRevenue {
+            priority_fee_distribution_account: derive_priority_fee_distribution_account_address(
+                &program_id,
+                &validator_vote_account,
+                epoch,
+            )
+            .0,
+            validator_vote_account,
+            signer,
+        }
+        .to_account_metas(None),
+    }
+}
+
+pub struct UpdateConfigArgs {
+    pub new_config: Config,
+}
+pub struct UpdateConfigAccounts {
+    pub authority: Pubkey,
+}
+pub fn update_config_ix(
+    program_id: Pubkey,
+    args: UpdateConfigArgs,
+    accounts: UpdateConfigAccounts,
+) -> Instruction {
+    let UpdateConfigArgs { new_config } = args;
+
+    let UpdateConfigAccounts { authority } = accounts;
+
+    Instruction {
+        program_id,
+        data: crate::instruction::UpdateConfig { new_config }.data(),
+        accounts: crate::accounts::UpdateConfig {
+            config: derive_config_account_address(&program_id).0,
+            authority,
+        }
+        .to_account_metas(None),
+    }
+}
+
+pub struct UploadMerkleRootArgs {
+    pub root: [u8; 32],
+    pub max_total_claim: u64,
+    pub max_num_nodes: u64,
+}
+pub struct UploadMerkleRootAccounts {
+    pub priority_fee_distribution_account: Pubkey,
+    pub merkle_root_upload_authority: Pubkey,
+}
+pub fn upload_merkle_root_ix(
+    program_id: Pubkey,
+    args: UploadMerkleRootArgs,
+    accounts: UploadMerkleRootAccounts,
+) -> Instruction {
+    let UploadMerkleRootArgs {
+        root,
+        max_total_claim,
+        max_num_nodes,
+    } = args;
+
+    let UploadMerkleRootAccounts {
+        priority_fee_distribution_account,
+        merkle_root_upload_authority,
+    } = accounts;
+
+    Instruction {
+        program_id,
+        data: crate::instruction::UploadMerkleRoot {
+            root,
+            max_total_claim,
+            max_num_nodes,
+        }
+        .data(),
+        accounts: crate::accounts::UploadMerkleRoot {
+            config: derive_config_account_address(&program_id).0,
+            priority_fee_distribution_account,
+            merkle_root_upload_authority,
+        }
+        .to_account_metas(None),
+    }
+}
+
+pub struct CloseClaimStatusArgs;
+pub struct CloseClaimStatusAccounts {
+    pub priority_fee_distribution_account: Pubkey,
+    pub claimant: Pubkey,
+    pub claim_status_payer: Pubkey,
+}
+pub fn close_claim_status_ix(
+    program_id: Pubkey,
+    _args: CloseClaimStatusArgs,
+    accounts: CloseClaimStatusAccounts,
+) -> Instruction {
+    let CloseClaimStatusAccounts {
+        priority_fee_distribution_account,
+        claimant,
+        claim_status_payer,
+    } = accounts;
+
+    Instruction {
+        program_id,
+        data: crate::instruction::CloseClaimStatus {}.data(),
+        accounts: crate::accounts::CloseClaimStatus {
+            claim_status: derive_claim_status_account_address(
+                &program_id,
+                &claimant,
+                &priority_fee_distribution_account,
+            )
+            .0,
+            claim_status_payer,
+        }
+        .to_account_metas(None),
+    }
+}
+
+pub struct ClosePriorityFeeDistributionAccountArgs {
+    pub _epoch: u64,
+}
+pub struct ClosePriorityFeeDistributionAccountAccounts {
+    pub validator_vote_account: Pubkey,
+    /// The [Config] account's `expired_funds_account`.
+    pub expired_funds_account: Pubkey,
+    pub signer: Pubkey,
+}
+pub fn close_priority_fee_distribution_account_ix(
+    program_id: Pubkey,
+    args: ClosePriorityFeeDistributionAccountArgs,
+    accounts: ClosePriorityFeeDistributionAccountAccounts,
+) -> Instruction {
+    let ClosePriorityFeeDistributionAccountArgs { _epoch } = args;
+
+    let ClosePriorityFeeDistributionAccountAccounts {
+        validator_vote_account,
+        expired_funds_account,
+        signer,
+    } = accounts;
+
+    let (priority_fee_distribution_account, _) = derive_priority_fee_distribution_account_address(
+        &program_id,
+        &validator_vote_account,
+        _epoch,
+    );
+
+    Instruction {
+        program_id,
+        data: crate::instruction::ClosePriorityFeeDistributionAccount { _epoch }.data(),
+        accounts: crate::accounts::ClosePriorityFeeDistributionAccount {
+            config: derive_config_account_address(&program_id).0,
+            expired_funds_account,
+            priority_fee_distribution_account,
+            validator_vote_account,
+            signer,
+        }
+        .to_account_metas(None),
+    }
+}
+
+pub struct ClaimArgs {
+    pub proof: Vec<[u8; 32]>,
+    pub amount: u64,
+}
+pub struct ClaimAccounts {
+    pub priority_fee_distribution_account: Pubkey,
+    pub merkle_root_upload_authority: Pubkey,
+    pub claimant: Pubkey,
+    pub payer: Pubkey,
+}
+pub fn claim_ix(program_id: Pubkey, args: ClaimArgs, accounts: ClaimAccounts) -> Instruction {
+    let ClaimArgs { proof, amount } = args;
+
+    let ClaimAccounts {
+        priority_fee_distribution_account,
+        merkle_root_upload_authority,
+        claimant,
+        payer,
+    } = accounts;
+
+    let (claim_status, bump) = derive_claim_status_account_address(
+        &program_id,
+        &claimant,
+        &priority_fee_distribution_account,
+    );
+
+    Instruction {
+        program_id,
+        data: crate::instruction::Claim {
+            _bump: bump,
+            amount,
+            proof,
+        }
+        .data(),
+        accounts: crate::accounts::Claim {
+            config: derive_config_account_address(&program_id).0,
+            priority_fee_distribution_account,
+            merkle_root_upload_authority,
+            claim_status,
+            claimant,
+            payer,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+    }
+}
+
+pub struct InitializeMerkleRootUploadConfigArgs {
+    /// The authority migrated
+    /// [crate::state::PriorityFeeDistributionAccount]s are given.
+    pub authority: Pubkey,
+    /// The upload authority of the accounts that may be migrated.
+    pub original_authority: Pubkey,
+}
+pub struct InitializeMerkleRootUploadConfigAccounts {
+    /// The [Config] account's authority.
+    pub authority: Pubkey,
+    pub payer: Pubkey,
+}
+pub fn initialize_merkle_root_upload_config_ix(
+    program_id: Pubkey,
+    args: InitializeMerkleRootUploadConfigArgs,
+    accounts: InitializeMerkleRootUploadConfigAccounts,
+) -> Instruction {
+    let InitializeMerkleRootUploadConfigArgs {
+        authority: override_authority,
+        original_authority,
+    } = args;
+
+    let InitializeMerkleRootUploadConfigAccounts { authority, payer } = accounts;
+
+    Instruction {
+        program_id,
+        data: crate::instruction::InitializeMerkleRootUploadConfig {
+            authority: override_authority,
+            original_authority,
+        }
+        .data(),
+        accounts: crate::accounts::InitializeMerkleRootUploadConfig {
+            config: derive_config_account_address(&program_id).0,
+            merkle_root_upload_config: derive_merkle_root_upload_config_account_address(
+                &program_id,
+            )
+            .0,
+            authority,
+            payer,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+    }
+}
+
+/// See [InitializeMerkleRootUploadConfigArgs].
+pub struct UpdateMerkleRootUploadConfigArgs {
+    pub authority: Pubkey,
+    pub original_authority: Pubkey,
+}
+pub struct UpdateMerkleRootUploadConfigAccounts {
+    /// The [Config] account's authority.
+    pub authority: Pubkey,
+}
+pub fn update_merkle_root_upload_config_ix(
+    program_id: Pubkey,
+    args: UpdateMerkleRootUploadConfigArgs,
+    accounts: UpdateMerkleRootUploadConfigAccounts,
+) -> Instruction {
+    let UpdateMerkleRootUploadConfigArgs {
+        authority: override_authority,
+        original_authority,
+    } = args;
+
+    let UpdateMerkleRootUploadConfigAccounts { authority } = accounts;
+
+    Instruction {
+        program_id,
+        data: crate::instruction::UpdateMerkleRootUploadConfig {
+            authority: override_authority,
+            original_authority,
+        }
+        .data(),
+        accounts: crate::accounts::UpdateMerkleRootUploadConfig {
+            config: derive_config_account_address(&program_id).0,
+            merkle_root_upload_config: derive_merkle_root_upload_config_account_address(
+                &program_id,
+            )
+            .0,
+            authority,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+    }
+}
+
+pub struct UpdateMerkleRootUploadAuthorityArgs {
+    pub new_authority: Pubkey,
+}
+pub struct UpdateMerkleRootUploadAuthorityAccounts {
+    pub validator_vote_account: Pubkey,
+    pub epoch: u64,
+    pub signer: Pubkey,
+}
+pub fn update_merkle_root_upload_authority_ix(
+    program_id: Pubkey,
+    args: UpdateMerkleRootUploadAuthorityArgs,
+    accounts: UpdateMerkleRootUploadAuthorityAccounts,
+) -> Instruction {
+    let UpdateMerkleRootUploadAuthorityArgs { new_authority } = args;
+
+    let UpdateMerkleRootUploadAuthorityAccounts {
+        validator_vote_account,
+        epoch,
+        signer,
+    } = accounts;
+
+    Instruction {
+        program_id,
+        data: crate::instruction::UpdateMerkleRootUploadAuthority { new_authority }.data(),
+        accounts: crate::accounts::UpdateMerkleRootUploadAuthority {
+            priority_fee_distribution_account: derive_priority_fee_distribution_account_address(
+                &program_id,
+                &validator_vote_account,
+                epoch,
+            )
+            .0,
+            validator_vote_account,
+            signer,
+        }
+        .to_account_metas(None),
+    }
+}
+
+pub struct MigrateTdaMerkleRootUploadAuthorityArgs;
+pub struct MigrateTdaMerkleRootUploadAuthorityAccounts {
+    pub priority_fee_distribution_account: Pubkey,
+}
+pub fn migrate_tda_merkle_root_upload_authority_ix(
+    program_id: Pubkey,
+    _args: MigrateTdaMerkleRootUploadAuthorityArgs,
+    accounts: MigrateTdaMerkleRootUploadAuthorityAccounts,
+) -> Instruction {
+    let MigrateTdaMerkleRootUploadAuthorityAccounts {
+        priority_fee_distribution_account,
+    } = accounts;
+
+    Instruction {
+        program_id,
+        data: crate::instruction::MigrateTdaMerkleRootUploadAuthority {}.data(),
+        accounts: crate::accounts::MigrateTdaMerkleRootUploadAuthority {
+            priority_fee_distribution_account,
+            merkle_root_upload_config: derive_merkle_root_upload_config_account_address(
+                &program_id,
+            )
+            .0,
+        }
+        .to_account_metas(None),
+    }
+}
+
+pub struct TransferPriorityFeeTipsArgs {
+    pub lamports: u64,
+}
+pub struct TransferPriorityFeeTipsAccounts {
+    pub validator_vote_account: Pubkey,
+    pub epoch: u64,
+    pub from: Pubkey,
+}
+/// The vote account is always passed, so the instruction also works when the [Config] account
+/// has `strict_transfer_source` set.
+pub fn transfer_priority_fee_tips_ix(
+    program_id: Pubkey,
+    args: TransferPriorityFeeTipsArgs,
+    accounts: TransferPriorityFeeTipsAccounts,
+) -> Instruction {
+    let TransferPriorityFeeTipsArgs { lamports } = args;
+
+    let TransferPriorityFeeTipsAccounts {
+        validator_vote_account,
+        epoch,
+        from,
+    } = accounts;
+
+    Instruction {
+        program_id,
+        data: crate::instruction::TransferPriorityFeeTips { lamports }.data(),
+        accounts: crate::accounts::TransferPriorityFeeTips {
+            config: derive_config_account_address(&program_id).0,
+            priority_fee_distribution_account: derive_priority_fee_distribution_account_address(
+                &program_id,
+                &validator_vote_account,
+                epoch,
+            )
+            .0,
+            from,
+            system_program: system_program::ID,
+            validator_vote_account: Some(validator_vote_account),
+        }
+        .to_account_metas(None),
+    }
+}
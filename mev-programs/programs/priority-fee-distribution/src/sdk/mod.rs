@@ -1,13 +1,15 @@
-pub mod instructions;
+//! PDA derivation and instruction builders for off-chain clients and CPI callers, enabled by
+//! the `sdk` feature.
+pub mod instruction;
 
-use anchor_lang::{prelude::Pubkey, solana_program::clock::Epoch};
+use anchor_lang::prelude::Pubkey;
 
-use crate::{Config, PriorityFeeDistributionAccount};
+use crate::state::{ClaimStatus, Config, MerkleRootUploadConfig, PriorityFeeDistributionAccount};
 
 pub fn derive_priority_fee_distribution_account_address(
     priority_fee_distribution_program_id: &Pubkey,
     vote_pubkey: &Pubkey,
-    epoch: Epoch,
+    epoch: u64,
 ) -> (Pubkey, u8) {
     Pubkey::find_program_address(
         &[
@@ -19,6 +21,32 @@ pub fn derive_priority_fee_distribution_account_address(
     )
 }
 
-pub fn derive_config_account_address(priority_fee_distribution_program_id: &Pubkey) -> (Pubkey, u8) {
+pub fn derive_config_account_address(
+    priority_fee_distribution_program_id: &Pubkey,
+) -> (Pubkey, u8) {
     Pubkey::find_program_address(&[Config::SEED], priority_fee_distribution_program_id)
 }
+
+pub fn derive_claim_status_account_address(
+    priority_fee_distribution_program_id: &Pubkey,
+    claimant: &Pubkey,
+    priority_fee_distribution_account: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            ClaimStatus::SEED,
+            claimant.to_bytes().as_ref(),
+            priority_fee_distribution_account.to_bytes().as_ref(),
+        ],
+        priority_fee_distribution_program_id,
+    )
+}
+
+pub fn derive_merkle_root_upload_config_account_address(
+    priority_fee_distribution_program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[MerkleRootUploadConfig::SEED],
+        priority_fee_distribution_program_id,
+    )
+}
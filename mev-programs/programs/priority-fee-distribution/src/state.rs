@@ -3,8 +3,13 @@
 use std::mem::size_of;
 
 use anchor_lang::prelude::*;
+use jito_programs_vote_state::VoteCommissionError;
+pub use jito_programs_vote_state::VoteCommissionPolicy;
 
-use crate::ErrorCode::{AccountValidationFailure, ArithmeticError};
+use crate::ErrorCode::{
+    AccountValidationFailure, ArithmeticError, MaxValidatorCommissionFeeBpsExceeded,
+    VoteAccountCommissionExceeded,
+};
 
 #[account]
 #[derive(Default)]
@@ -27,6 +32,13 @@ pub struct Config {
 
     /// The bump used to generate this account
     pub bump: u8,
+
+    /// When set, only the validator's node identity may transfer priority fee tips to its
+    /// distribution account, so `total_lamports_transferred` is validator-sourced.
+    pub strict_transfer_source: bool,
+
+    /// How the commission validators charge relates to the commission in their vote account.
+    pub vote_commission_policy: VoteCommissionPolicy,
 }
 
 /// The account that validators send priority fees to
@@ -57,6 +69,20 @@ pub struct PriorityFeeDistributionAccount {
 
     /// The bump used to generate this account
     pub bump: u8,
+
+    /// The validator's self-reported priority fee revenue for the epoch, which can no longer
+    /// change once the merkle root is uploaded.
+    pub priority_fee_revenue_attestation: Option<PriorityFeeRevenueAttestation>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PriorityFeeRevenueAttestation {
+    /// Priority fees the validator earned in the epoch.
+    pub priority_fee_revenue_lamports: u64,
+
+    /// The share of [PriorityFeeRevenueAttestation::priority_fee_revenue_lamports], in basis
+    /// points, the validator committed to transfer to this account.
+    pub committed_share_bps: u16,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
@@ -79,6 +105,34 @@ pub struct MerkleRoot {
 
 const HEADER_SIZE: usize = 8;
 
+// The sizes come from `size_of`, so make sure the largest borsh encoding of each account fits,
+// including the distribution accounts created before the attestation was added.
+const _: () = assert!(
+    32 // authority
+        + 32 // expired_funds_account
+        + 8 // num_epochs_valid
+        + 2 // max_validator_commission_bps
+        + 8 // go_live_epoch
+        + 1 // bump
+        + 1 // strict_transfer_source
+        + 1 // vote_commission_policy
+        + HEADER_SIZE
+        <= Config::SIZE
+);
+const _: () = assert!(
+    32 // validator_vote_account
+        + 32 // merkle_root_upload_authority
+        + 1 + 32 + 8 * 4 // merkle_root
+        + 8 // epoch_created_at
+        + 2 // validator_commission_bps
+        + 8 // expires_at
+        + 8 // total_lamports_transferred
+        + 1 // bump
+        + 1 + 8 + 2 // priority_fee_revenue_attestation
+        + HEADER_SIZE
+        <= PriorityFeeDistributionAccount::LEGACY_SIZE
+);
+
 impl Config {
     pub const SEED: &'static [u8] = b"CONFIG_ACCOUNT";
     pub const SIZE: usize = HEADER_SIZE + size_of::<Self>();
@@ -102,6 +156,30 @@ impl Config {
 
         Ok(())
     }
+
+    /// The commission a validator charges on a new or updated distribution account, given the
+    /// one they asked for, according to the [VoteCommissionPolicy].
+    pub fn validator_commission_bps(
+        &self,
+        requested_commission_bps: u16,
+        validator_vote_account: &AccountInfo,
+    ) -> Result<u16> {
+        self.vote_commission_policy
+            .validator_commission_bps(
+                requested_commission_bps,
+                self.max_validator_commission_bps,
+                validator_vote_account,
+            )
+            .map_err(|e| match e {
+                VoteCommissionError::VoteAccount(e) => e,
+                VoteCommissionError::VoteAccountCommissionExceeded => {
+                    VoteAccountCommissionExceeded.into()
+                }
+                VoteCommissionError::MaxCommissionExceeded => {
+                    MaxValidatorCommissionFeeBpsExceeded.into()
+                }
+            })
+    }
 }
 
 impl PriorityFeeDistributionAccount {
@@ -109,6 +187,10 @@ impl PriorityFeeDistributionAccount {
 
     pub const SIZE: usize = HEADER_SIZE + size_of::<Self>();
 
+    /// [PriorityFeeDistributionAccount::SIZE] before the revenue attestation was added, which the
+    /// accounts already on chain were allocated with.
+    pub const LEGACY_SIZE: usize = HEADER_SIZE + 168;
+
     pub fn validate(&self) -> Result<()> {
         let default_pubkey = Pubkey::default();
         if self.validator_vote_account == default_pubkey
@@ -137,6 +219,17 @@ impl PriorityFeeDistributionAccount {
         Self::transfer_lamports(from, to, amount)
     }
 
+    /// Lamports the validator still owes this account according to its attestation, zero if it's
+    /// transferred at least its committed share or hasn't attested.
+    pub fn attested_shortfall_lamports(&self) -> Result<u64> {
+        match &self.priority_fee_revenue_attestation {
+            Some(attestation) => Ok(attestation
+                .committed_lamports()?
+                .saturating_sub(self.total_lamports_transferred)),
+            None => Ok(0),
+        }
+    }
+
     pub fn increment_total_lamports_transferred(&mut self, amount: u64) -> Result<()> {
         let old_balance = self.total_lamports_transferred;
         let new_balance = old_balance.checked_add(amount).ok_or(ArithmeticError)?;
@@ -158,6 +251,29 @@ impl PriorityFeeDistributionAccount {
     }
 }
 
+impl PriorityFeeRevenueAttestation {
+    const MAX_BPS: u16 = 10_000;
+
+    pub fn validate(&self) -> Result<()> {
+        if self.committed_share_bps > Self::MAX_BPS {
+            return Err(AccountValidationFailure.into());
+        }
+
+        Ok(())
+    }
+
+    /// The committed share of the revenue, rounded down.
+    pub fn committed_lamports(&self) -> Result<u64> {
+        let committed_lamports = (self.priority_fee_revenue_lamports as u128)
+            .checked_mul(self.committed_share_bps as u128)
+            .ok_or(ArithmeticError)?
+            .checked_div(Self::MAX_BPS as u128)
+            .ok_or(ArithmeticError)?;
+
+        u64::try_from(committed_lamports).map_err(|_| ArithmeticError.into())
+    }
+}
+
 /// A PDA uniquely derived by the PriorityFeeDistributionAccount and claimant, which enforces an only-
 /// once claim by each claimant.
 /// @dev **this is very different than TipDistributor's ClaimStatus structure**
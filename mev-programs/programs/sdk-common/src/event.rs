@@ -0,0 +1,42 @@
+//! Decodes the events a program emits to transaction logs.
+use anchor_lang::prelude::Pubkey;
+use base64::{prelude::BASE64_STANDARD, Engine};
+
+const PROGRAM_DATA: &str = "Program data: ";
+
+/// The events a program emits, see [decode_events].
+pub trait Event: Sized {
+    /// Decodes discriminator prefixed event data, returning `None` if it isn't a known event.
+    fn decode(data: &[u8]) -> Option<Self>;
+}
+
+/// Decodes the events `program_id` emitted in a transaction's log messages, in order. Data
+/// logged by other programs, including ones invoked through CPI, is ignored.
+pub fn decode_events<E: Event>(program_id: &Pubkey, log_messages: &[String]) -> Vec<E> {
+    let program_id = program_id.to_string();
+    let mut invocations = Vec::new();
+    let mut events = Vec::new();
+    for log in log_messages {
+        if let Some(data) = log.strip_prefix(PROGRAM_DATA) {
+            if invocations.last() == Some(&program_id.as_str()) {
+                events.extend(
+                    BASE64_STANDARD
+                        .decode(data)
+                        .ok()
+                        .and_then(|data| E::decode(&data)),
+                );
+            }
+        } else if let Some(log) = log.strip_prefix("Program ") {
+            let mut words = log.split_whitespace();
+            match (words.next(), words.next()) {
+                (Some(invoked_program_id), Some("invoke")) => invocations.push(invoked_program_id),
+                (Some(_), Some("success" | "failed:")) => {
+                    invocations.pop();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    events
+}
@@ -0,0 +1,118 @@
+use anchor_lang::prelude::Pubkey;
+use base64::{prelude::BASE64_STANDARD, Engine};
+use jito_programs_sdk_common::event::{decode_events, Event};
+
+/// Decodes any data starting with the `b"event"` discriminator, keeping the rest.
+#[derive(Debug, PartialEq, Eq)]
+struct TestEvent(Vec<u8>);
+
+impl Event for TestEvent {
+    fn decode(data: &[u8]) -> Option<Self> {
+        data.strip_prefix(b"event").map(|data| Self(data.to_vec()))
+    }
+}
+
+fn invoke(program_id: &Pubkey, depth: usize) -> String {
+    format!("Program {program_id} invoke [{depth}]")
+}
+
+fn success(program_id: &Pubkey) -> String {
+    format!("Program {program_id} success")
+}
+
+fn data(event: &[u8]) -> String {
+    format!(
+        "Program data: {}",
+        BASE64_STANDARD.encode([b"event".as_slice(), event].concat())
+    )
+}
+
+#[test]
+fn test_decode_events() {
+    let program_id = Pubkey::new_unique();
+    let log_messages = vec![
+        invoke(&program_id, 1),
+        "Program log: Instruction: Claim".to_string(),
+        data(&[1]),
+        "Program return: 11111111111111111111111111111111 AQ==".to_string(),
+        format!("Program {program_id} consumed 5000 of 200000 compute units"),
+        data(&[2]),
+        success(&program_id),
+    ];
+
+    assert_eq!(
+        decode_events::<TestEvent>(&program_id, &log_messages),
+        vec![TestEvent(vec![1]), TestEvent(vec![2])]
+    );
+}
+
+#[test]
+fn test_decode_events_skips_other_programs() {
+    let program_id = Pubkey::new_unique();
+    let other_program_id = Pubkey::new_unique();
+    let log_messages = vec![
+        invoke(&other_program_id, 1),
+        data(&[1]),
+        success(&other_program_id),
+        invoke(&program_id, 1),
+        data(&[2]),
+        success(&program_id),
+        invoke(&other_program_id, 1),
+        data(&[3]),
+        format!("Program {other_program_id} failed: custom program error: 0x1"),
+    ];
+
+    assert_eq!(
+        decode_events::<TestEvent>(&program_id, &log_messages),
+        vec![TestEvent(vec![2])]
+    );
+}
+
+#[test]
+fn test_decode_events_nested_cpi() {
+    let program_id = Pubkey::new_unique();
+    let other_program_id = Pubkey::new_unique();
+    let log_messages = vec![
+        // The program invoked by another program, which logs before and after the CPI.
+        invoke(&other_program_id, 1),
+        data(&[1]),
+        invoke(&program_id, 2),
+        data(&[2]),
+        // The program invoking a third program, whose data is not the program's.
+        invoke(&other_program_id, 3),
+        data(&[3]),
+        success(&other_program_id),
+        data(&[4]),
+        success(&program_id),
+        data(&[5]),
+        success(&other_program_id),
+        // A top level invocation after the nested one.
+        invoke(&program_id, 1),
+        data(&[6]),
+        success(&program_id),
+    ];
+
+    assert_eq!(
+        decode_events::<TestEvent>(&program_id, &log_messages),
+        vec![TestEvent(vec![2]), TestEvent(vec![4]), TestEvent(vec![6])]
+    );
+}
+
+#[test]
+fn test_decode_events_truncated_logs() {
+    let program_id = Pubkey::new_unique();
+    let log_messages = vec![
+        invoke(&program_id, 1),
+        data(&[1]),
+        // Data cut short is skipped instead of failing the events around it.
+        "Program data: ZXZl".to_string(),
+        "Program data: ZXZlbn".to_string(),
+        data(&[2]),
+        "Log truncated".to_string(),
+    ];
+
+    assert_eq!(
+        decode_events::<TestEvent>(&program_id, &log_messages),
+        vec![TestEvent(vec![1]), TestEvent(vec![2])]
+    );
+}
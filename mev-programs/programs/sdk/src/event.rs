@@ -0,0 +1,74 @@
+//! Decodes the events the tip-distribution program emits to transaction logs.
+use anchor_lang::{AnchorDeserialize, Discriminator};
+pub use jito_programs_sdk_common::event::{decode_events, Event};
+use jito_tip_distribution::{
+    ClaimBitmapClosedEvent, ClaimBitmapInitializedEvent, ClaimStatusClosedEvent, ClaimedEvent,
+    ConfigUpdatedEvent, MerkleRootUploadAuthorityUpdatedEvent, MerkleRootUploadedEvent,
+    TipDistributionAccountClosedEvent, TipDistributionAccountInitializedEvent,
+    ValidatorCommissionBpsUpdatedEvent,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TipDistributionEvent {
+    TipDistributionAccountInitialized(TipDistributionAccountInitializedEvent),
+    ValidatorCommissionBpsUpdated(ValidatorCommissionBpsUpdatedEvent),
+    MerkleRootUploadAuthorityUpdated(MerkleRootUploadAuthorityUpdatedEvent),
+    ConfigUpdated(ConfigUpdatedEvent),
+    Claimed(ClaimedEvent),
+    MerkleRootUploaded(MerkleRootUploadedEvent),
+    TipDistributionAccountClosed(TipDistributionAccountClosedEvent),
+    ClaimStatusClosed(ClaimStatusClosedEvent),
+    ClaimBitmapInitialized(ClaimBitmapInitializedEvent),
+    ClaimBitmapClosed(ClaimBitmapClosedEvent),
+}
+
+impl Event for TipDistributionEvent {
+    fn decode(data: &[u8]) -> Option<Self> {
+        let (discriminator, mut data) = data.split_at_checked(8)?;
+        match discriminator {
+            TipDistributionAccountInitializedEvent::DISCRIMINATOR => {
+                TipDistributionAccountInitializedEvent::deserialize(&mut data)
+                    .ok()
+                    .map(Self::TipDistributionAccountInitialized)
+            }
+            ValidatorCommissionBpsUpdatedEvent::DISCRIMINATOR => {
+                ValidatorCommissionBpsUpdatedEvent::deserialize(&mut data)
+                    .ok()
+                    .map(Self::ValidatorCommissionBpsUpdated)
+            }
+            MerkleRootUploadAuthorityUpdatedEvent::DISCRIMINATOR => {
+                MerkleRootUploadAuthorityUpdatedEvent::deserialize(&mut data)
+                    .ok()
+                    .map(Self::MerkleRootUploadAuthorityUpdated)
+            }
+            ConfigUpdatedEvent::DISCRIMINATOR => ConfigUpdatedEvent::deserialize(&mut data)
+                .ok()
+                .map(Self::ConfigUpdated),
+            ClaimedEvent::DISCRIMINATOR => {
+                ClaimedEvent::deserialize(&mut data).ok().map(Self::Claimed)
+            }
+            MerkleRootUploadedEvent::DISCRIMINATOR => {
+                MerkleRootUploadedEvent::deserialize(&mut data)
+                    .ok()
+                    .map(Self::MerkleRootUploaded)
+            }
+            TipDistributionAccountClosedEvent::DISCRIMINATOR => {
+                TipDistributionAccountClosedEvent::deserialize(&mut data)
+                    .ok()
+                    .map(Self::TipDistributionAccountClosed)
+            }
+            ClaimStatusClosedEvent::DISCRIMINATOR => ClaimStatusClosedEvent::deserialize(&mut data)
+                .ok()
+                .map(Self::ClaimStatusClosed),
+            ClaimBitmapInitializedEvent::DISCRIMINATOR => {
+                ClaimBitmapInitializedEvent::deserialize(&mut data)
+                    .ok()
+                    .map(Self::ClaimBitmapInitialized)
+            }
+            ClaimBitmapClosedEvent::DISCRIMINATOR => ClaimBitmapClosedEvent::deserialize(&mut data)
+                .ok()
+                .map(Self::ClaimBitmapClosed),
+            _ => None,
+        }
+    }
+}
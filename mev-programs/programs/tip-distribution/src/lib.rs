@@ -1,9 +1,13 @@
 use anchor_lang::{prelude::*, solana_program::clock::Clock};
+use jito_programs_vote_state::VoteState;
 #[cfg(not(feature = "no-entrypoint"))]
 use solana_security_txt::security_txt;
 
 use crate::{
-    state::{ClaimStatus, Config, MerkleRoot, MerkleRootUploadConfig, TipDistributionAccount},
+    state::{
+        ClaimBitmap, ClaimMode, ClaimStatus, Config, MerkleRoot, MerkleRootUploadConfig,
+        TipDistributionAccount,
+    },
     ErrorCode::Unauthorized,
 };
 
@@ -22,6 +26,8 @@ security_txt! {
 }
 
 pub mod merkle_proof;
+#[cfg(feature = "sdk")]
+pub mod sdk;
 pub mod state;
 
 #[cfg(feature = "mainnet")]
@@ -32,9 +38,6 @@ declare_id!("DzvGET57TAgEDxvm3ERUM4GNcsAJdqjDLCne9sdfY4wf");
 
 #[program]
 pub mod jito_tip_distribution {
-    use anchor_lang::solana_program;
-    use jito_programs_vote_state::VoteState;
-
     use super::*;
     use crate::ErrorCode::*;
 
@@ -66,16 +69,20 @@ pub mod jito_tip_distribution {
         validator_commission_bps: u16,
         bump: u8,
     ) -> Result<()> {
-        if validator_commission_bps > ctx.accounts.config.max_validator_commission_bps {
-            return Err(MaxValidatorCommissionFeeBpsExceeded.into());
-        }
-
-        let validator_vote_account_node_pubkey =
-            VoteState::deserialize_node_pubkey(&ctx.accounts.validator_vote_account)?;
-        if validator_vote_account_node_pubkey != *ctx.accounts.signer.key {
+        // either the hot node identity or the cold withdraw authority can create the account
+        let validator_vote_account = &ctx.accounts.validator_vote_account;
+        let signer = ctx.accounts.signer.key;
+        if VoteState::deserialize_node_pubkey(validator_vote_account)? != *signer
+            && VoteState::deserialize_authorized_withdrawer(validator_vote_account)?
+                != Some(*signer)
+        {
             return Err(Unauthorized.into());
         }
 
+        let validator_commission_bps = ctx
+            .accounts
+            .config
+            .validator_commission_bps(validator_commission_bps, validator_vote_account)?;
         let current_epoch = Clock::get()?.epoch;
 
         let distribution_acc = &mut ctx.accounts.tip_distribution_account;
@@ -106,6 +113,7 @@ pub mod jito_tip_distribution {
         config.expired_funds_account = new_config.expired_funds_account;
         config.num_epochs_valid = new_config.num_epochs_valid;
         config.max_validator_commission_bps = new_config.max_validator_commission_bps;
+        config.vote_commission_policy = new_config.vote_commission_policy;
         config.validate()?;
 
         emit!(ConfigUpdatedEvent {
@@ -117,9 +125,10 @@ pub mod jito_tip_distribution {
 
     /// Uploads a merkle root to the provided [TipDistributionAccount].
     ///
-    /// This instruction may be invoked many times as long as the account is at least one epoch old and not expired; and
-    /// no funds have already been claimed. Only the `merkle_root_upload_authority` has the
-    /// authority to invoke.
+    /// This instruction may be invoked many times as long as the account is at least one epoch old and not expired;
+    /// no funds have already been claimed; and no [ClaimBitmap], which is sized for the current
+    /// root, has been initialized. Only the `merkle_root_upload_authority` has the authority to
+    /// invoke.
     pub fn upload_merkle_root(
         ctx: Context<UploadMerkleRoot>,
         root: [u8; 32],
@@ -136,6 +145,10 @@ pub mod jito_tip_distribution {
                 return Err(Unauthorized.into());
             }
         }
+        // the claim bitmap is sized for the uploaded root, so it can't be replaced
+        if distribution_acc.claim_mode == ClaimMode::Bitmap {
+            return Err(InvalidClaimMode.into());
+        }
         if current_epoch <= distribution_acc.epoch_created_at {
             return Err(PrematureMerkleRootUpload.into());
         }
@@ -161,6 +174,39 @@ pub mod jito_tip_distribution {
         Ok(())
     }
 
+    /// Changes the commission the validator charges on their [TipDistributionAccount], up to the
+    /// [Config] account's `max_validator_commission_bps` and subject to its
+    /// `vote_commission_policy`. Only the validator's node identity has
+    /// the authority to invoke, before a merkle root is uploaded.
+    pub fn update_validator_commission_bps(
+        ctx: Context<UpdateValidatorCommissionBps>,
+        validator_commission_bps: u16,
+    ) -> Result<()> {
+        UpdateValidatorCommissionBps::auth(&ctx)?;
+
+        let validator_commission_bps = ctx.accounts.config.validator_commission_bps(
+            validator_commission_bps,
+            &ctx.accounts.validator_vote_account,
+        )?;
+
+        let distribution_acc = &mut ctx.accounts.tip_distribution_account;
+        if distribution_acc.merkle_root.is_some() {
+            return Err(MerkleRootAlreadyUploaded.into());
+        }
+
+        let old_commission_bps = distribution_acc.validator_commission_bps;
+        distribution_acc.validator_commission_bps = validator_commission_bps;
+        distribution_acc.validate()?;
+
+        emit!(ValidatorCommissionBpsUpdatedEvent {
+            tip_distribution_account: distribution_acc.key(),
+            old_commission_bps,
+            new_commission_bps: validator_commission_bps,
+        });
+
+        Ok(())
+    }
+
     /// Anyone can invoke this only after the [TipDistributionAccount] has expired.
     /// This instruction will return any rent back to `claimant` and close the account
     pub fn close_claim_status(ctx: Context<CloseClaimStatus>) -> Result<()> {
@@ -219,6 +265,10 @@ pub mod jito_tip_distribution {
         let claimant_account = &mut ctx.accounts.claimant;
         let tip_distribution_account = &mut ctx.accounts.tip_distribution_account;
 
+        if tip_distribution_account.claim_mode != ClaimMode::ClaimStatus {
+            return Err(InvalidClaimMode.into());
+        }
+
         let clock = Clock::get()?;
         if clock.epoch > tip_distribution_account.expires_at {
             return Err(ExpiredTipDistributionAccount.into());
@@ -236,19 +286,7 @@ pub mod jito_tip_distribution {
             .as_mut()
             .ok_or(RootNotUploaded)?;
 
-        // Verify the merkle proof.
-        let node = &solana_program::hash::hashv(&[
-            &[0u8],
-            &solana_program::hash::hashv(&[
-                &claimant_account.key().to_bytes(),
-                &amount.to_le_bytes(),
-            ])
-            .to_bytes(),
-        ]);
-
-        if !merkle_proof::verify(proof, merkle_root.root, node.to_bytes()) {
-            return Err(InvalidProof.into());
-        }
+        verify_claim(merkle_root.root, &claimant_account.key(), amount, proof)?;
 
         TipDistributionAccount::claim(
             tip_distribution_info,
@@ -264,25 +302,152 @@ pub mod jito_tip_distribution {
         claim_status.claim_status_payer = ctx.accounts.payer.key();
         claim_status.expires_at = tip_distribution_epoch_expires_at;
 
-        merkle_root.total_funds_claimed = merkle_root
-            .total_funds_claimed
-            .checked_add(amount)
-            .ok_or(ArithmeticError)?;
-        if merkle_root.total_funds_claimed > merkle_root.max_total_claim {
-            return Err(ExceedsMaxClaim.into());
+        merkle_root.record_claim(amount)?;
+
+        emit!(ClaimedEvent {
+            tip_distribution_account: tip_distribution_account.key(),
+            payer: ctx.accounts.payer.key(),
+            claimant: claimant_account.key(),
+            amount
+        });
+
+        tip_distribution_account.validate()?;
+
+        Ok(())
+    }
+
+    /// Claims tokens from the [TipDistributionAccount] for several claimants at once. Each claim's
+    /// [ClaimStatus] PDA and claimant must be passed, in that order, through the remaining accounts.
+    pub fn claim_batch<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimBatch<'info>>,
+        claims: Vec<BatchClaim>,
+    ) -> Result<()> {
+        ClaimBatch::auth(&ctx)?;
+        let root = ClaimBatch::validate(&ctx, claims.len())?;
+
+        let claims = claims
+            .into_iter()
+            .map(|claim| {
+                verify_claim(root, &claim.claimant, claim.amount, claim.proof)?;
+                Ok((claim.claimant, claim.amount))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        pay_batch_claims(ctx, claims)
+    }
+
+    /// Same as [jito_tip_distribution::claim_batch], except all claims are proved together with a
+    /// single multiproof, see [merkle_proof::verify_multi]. Claims must be in ascending leaf order.
+    pub fn claim_batch_multi<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimBatch<'info>>,
+        claims: Vec<MultiProofClaim>,
+        num_leaves: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        ClaimBatch::auth(&ctx)?;
+        let root = ClaimBatch::validate(&ctx, claims.len())?;
+
+        let leaves = claims
+            .iter()
+            .map(|claim| (claim.index, claim_leaf(&claim.claimant, claim.amount)))
+            .collect();
+        if !merkle_proof::verify_multi(proof, root, num_leaves, leaves) {
+            return Err(InvalidProof.into());
         }
 
-        merkle_root.num_nodes_claimed = merkle_root
-            .num_nodes_claimed
-            .checked_add(1)
-            .ok_or(ArithmeticError)?;
-        if merkle_root.num_nodes_claimed > merkle_root.max_num_nodes {
-            return Err(ExceedsMaxNumNodes.into());
+        pay_batch_claims(
+            ctx,
+            claims
+                .into_iter()
+                .map(|claim| (claim.claimant, claim.amount))
+                .collect(),
+        )
+    }
+
+    /// Switches the [TipDistributionAccount] to [ClaimMode::Bitmap], creating a [ClaimBitmap] with
+    /// a bit for each of the uploaded merkle root's `max_num_nodes`. Claims are then made with
+    /// [jito_tip_distribution::claim_with_bitmap] instead of creating a [ClaimStatus] each.
+    ///
+    /// The bitmap is created through a CPI, so it can track at most ~81k nodes. Only the
+    /// `merkle_root_upload_authority` has the authority to invoke, before any funds are claimed.
+    pub fn initialize_claim_bitmap(ctx: Context<InitializeClaimBitmap>) -> Result<()> {
+        InitializeClaimBitmap::auth(&ctx)?;
+
+        let tip_distribution_account = &mut ctx.accounts.tip_distribution_account;
+        if Clock::get()?.epoch > tip_distribution_account.expires_at {
+            return Err(ExpiredTipDistributionAccount.into());
+        }
+        let merkle_root = tip_distribution_account
+            .merkle_root
+            .as_ref()
+            .ok_or(RootNotUploaded)?;
+        if merkle_root.num_nodes_claimed > 0 {
+            return Err(Unauthorized.into());
+        }
+        let num_nodes = merkle_root.max_num_nodes;
+
+        let claim_bitmap = &mut ctx.accounts.claim_bitmap;
+        claim_bitmap.tip_distribution_account = tip_distribution_account.key();
+        claim_bitmap.claim_bitmap_payer = ctx.accounts.payer.key();
+        claim_bitmap.expires_at = tip_distribution_account.expires_at;
+        claim_bitmap.bump = ctx.bumps.claim_bitmap;
+        claim_bitmap.bitmap = vec![0; num_nodes.div_ceil(8) as usize];
+
+        tip_distribution_account.claim_mode = ClaimMode::Bitmap;
+        tip_distribution_account.validate()?;
+
+        emit!(ClaimBitmapInitializedEvent {
+            tip_distribution_account: tip_distribution_account.key(),
+            claim_bitmap: claim_bitmap.key(),
+            num_nodes,
+        });
+
+        Ok(())
+    }
+
+    /// Claims tokens from a [ClaimMode::Bitmap] [TipDistributionAccount], `index` being the
+    /// claimant's position in the merkle tree.
+    pub fn claim_with_bitmap(
+        ctx: Context<ClaimWithBitmap>,
+        index: u64,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        ClaimWithBitmap::auth(&ctx)?;
+
+        let claimant_account = &ctx.accounts.claimant;
+        let tip_distribution_account = &mut ctx.accounts.tip_distribution_account;
+        if tip_distribution_account.claim_mode != ClaimMode::Bitmap {
+            return Err(InvalidClaimMode.into());
+        }
+        if Clock::get()?.epoch > tip_distribution_account.expires_at {
+            return Err(ExpiredTipDistributionAccount.into());
+        }
+
+        let tip_distribution_info = tip_distribution_account.to_account_info();
+        let merkle_root = tip_distribution_account
+            .merkle_root
+            .as_mut()
+            .ok_or(RootNotUploaded)?;
+
+        let leaf = indexed_claim_leaf(&claimant_account.key(), amount, index);
+        if !merkle_proof::verify(proof, merkle_root.root, leaf) {
+            return Err(InvalidProof.into());
         }
 
+        ctx.accounts.claim_bitmap.set_claimed(index)?;
+
+        TipDistributionAccount::claim(
+            tip_distribution_info,
+            claimant_account.to_account_info(),
+            amount,
+        )?;
+
+        merkle_root.record_claim(amount)?;
+
         emit!(ClaimedEvent {
             tip_distribution_account: tip_distribution_account.key(),
-            payer: ctx.accounts.payer.key(),
+            payer: ctx.accounts.merkle_root_upload_authority.key(),
             claimant: claimant_account.key(),
             amount
         });
@@ -292,6 +457,24 @@ pub mod jito_tip_distribution {
         Ok(())
     }
 
+    /// Anyone can invoke this only after the [ClaimBitmap] has expired.
+    /// This instruction will return the rent back to the bitmap's payer and close the account.
+    pub fn close_claim_bitmap(ctx: Context<CloseClaimBitmap>) -> Result<()> {
+        let claim_bitmap = &ctx.accounts.claim_bitmap;
+
+        // can only close after the bitmap has expired so claims can't be replayed
+        if Clock::get()?.epoch <= claim_bitmap.expires_at {
+            return Err(PrematureCloseClaimBitmap.into());
+        }
+
+        emit!(ClaimBitmapClosedEvent {
+            claim_bitmap_payer: ctx.accounts.claim_bitmap_payer.key(),
+            claim_bitmap_account: claim_bitmap.key(),
+        });
+
+        Ok(())
+    }
+
     pub fn initialize_merkle_root_upload_config(
         ctx: Context<InitializeMerkleRootUploadConfig>,
         authority: Pubkey,
@@ -324,6 +507,32 @@ pub mod jito_tip_distribution {
         Ok(())
     }
 
+    /// Sets a new `merkle_root_upload_authority` on the validator's [TipDistributionAccount], e.g. to
+    /// switch distribution providers. Only the validator's node identity has the authority to
+    /// invoke, before a merkle root is uploaded.
+    pub fn update_merkle_root_upload_authority(
+        ctx: Context<UpdateMerkleRootUploadAuthority>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        UpdateMerkleRootUploadAuthority::auth(&ctx)?;
+
+        let distribution_account = &mut ctx.accounts.tip_distribution_account;
+        if distribution_account.merkle_root.is_some() {
+            return Err(MerkleRootAlreadyUploaded.into());
+        }
+
+        let old_authority = distribution_account.merkle_root_upload_authority;
+        distribution_account.merkle_root_upload_authority = new_authority;
+        distribution_account.validate()?;
+
+        emit!(MerkleRootUploadAuthorityUpdatedEvent {
+            old_authority,
+            new_authority,
+        });
+
+        Ok(())
+    }
+
     pub fn migrate_tda_merkle_root_upload_authority(
         ctx: Context<MigrateTdaMerkleRootUploadAuthority>,
     ) -> Result<()> {
@@ -350,6 +559,113 @@ pub mod jito_tip_distribution {
     }
 }
 
+/// The leaf a claimant's `amount` is committed to in a [MerkleRoot].
+fn claim_leaf(claimant: &Pubkey, amount: u64) -> [u8; 32] {
+    solana_program::hash::hashv(&[
+        &[0u8],
+        &solana_program::hash::hashv(&[&claimant.to_bytes(), &amount.to_le_bytes()]).to_bytes(),
+    ])
+    .to_bytes()
+}
+
+/// The leaf a claimant's `amount` is committed to in a [MerkleRoot] of a [ClaimMode::Bitmap]
+/// account, `index` being the leaf's position in the tree and its bit in the [ClaimBitmap].
+fn indexed_claim_leaf(claimant: &Pubkey, amount: u64, index: u64) -> [u8; 32] {
+    solana_program::hash::hashv(&[
+        &[0u8],
+        &solana_program::hash::hashv(&[
+            &claimant.to_bytes(),
+            &amount.to_le_bytes(),
+            &index.to_le_bytes(),
+        ])
+        .to_bytes(),
+    ])
+    .to_bytes()
+}
+
+/// Verifies the merkle proof of `claimant`'s `amount`.
+fn verify_claim(
+    root: [u8; 32],
+    claimant: &Pubkey,
+    amount: u64,
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    if !merkle_proof::verify(proof, root, claim_leaf(claimant, amount)) {
+        return Err(ErrorCode::InvalidProof.into());
+    }
+
+    Ok(())
+}
+
+/// Pays out already verified `(claimant, amount)` claims, creating each claimant's
+/// [ClaimStatus] from the remaining accounts.
+fn pay_batch_claims<'info>(
+    ctx: Context<'_, '_, '_, 'info, ClaimBatch<'info>>,
+    claims: Vec<(Pubkey, u64)>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let rent = Rent::get()?;
+    let tip_distribution_account = &mut ctx.accounts.tip_distribution_account;
+    let tip_distribution_key = tip_distribution_account.key();
+    let tip_distribution_info = tip_distribution_account.to_account_info();
+    let tip_distribution_epoch_expires_at = tip_distribution_account.expires_at;
+    let merkle_root = tip_distribution_account
+        .merkle_root
+        .as_mut()
+        .ok_or(ErrorCode::RootNotUploaded)?;
+
+    for ((claimant, amount), accounts) in claims.into_iter().zip(ctx.remaining_accounts.chunks(2)) {
+        let [claim_status_info, claimant_info] = accounts else {
+            return Err(ErrorCode::InvalidParameters.into());
+        };
+        if claimant_info.key() != claimant {
+            return Err(ErrorCode::InvalidParameters.into());
+        }
+
+        let bump = ClaimStatus::create(
+            claim_status_info,
+            &[
+                ClaimStatus::SEED,
+                claimant.as_ref(),
+                tip_distribution_key.as_ref(),
+            ],
+            &ctx.accounts.payer,
+            &ctx.accounts.system_program,
+            &rent,
+        )?;
+
+        TipDistributionAccount::claim(
+            tip_distribution_info.clone(),
+            claimant_info.clone(),
+            amount,
+        )?;
+
+        ClaimStatus {
+            is_claimed: true,
+            claimant,
+            claim_status_payer: ctx.accounts.payer.key(),
+            slot_claimed_at: clock.slot,
+            amount,
+            expires_at: tip_distribution_epoch_expires_at,
+            bump,
+        }
+        .try_serialize(&mut &mut claim_status_info.try_borrow_mut_data()?[..])?;
+
+        merkle_root.record_claim(amount)?;
+
+        emit!(ClaimedEvent {
+            tip_distribution_account: tip_distribution_key,
+            payer: ctx.accounts.payer.key(),
+            claimant,
+            amount
+        });
+    }
+
+    tip_distribution_account.validate()?;
+
+    Ok(())
+}
+
 #[error_code]
 pub enum ErrorCode {
     #[msg("Account failed validation.")]
@@ -399,6 +715,24 @@ pub enum ErrorCode {
 
     #[msg("TDA not valid for migration.")]
     InvalidTdaForMigration,
+
+    #[msg("The instruction doesn't support the TipDistributionAccount's claim mode.")]
+    InvalidClaimMode,
+
+    #[msg("The claim index is outside of the ClaimBitmap.")]
+    InvalidClaimIndex,
+
+    #[msg("The given ClaimBitmap account is not ready to be closed.")]
+    PrematureCloseClaimBitmap,
+
+    #[msg("A merkle root has already been uploaded to the given TipDistributionAccount.")]
+    MerkleRootAlreadyUploaded,
+
+    #[msg("Validator's commission basis points must be less than or equal to the commission in their vote account.")]
+    VoteAccountCommissionExceeded,
+
+    #[msg("The claim index is already set in the ClaimBitmap.")]
+    NodeAlreadyClaimed,
 }
 
 #[derive(Accounts)]
@@ -463,10 +797,11 @@ pub struct InitializeTipDistributionAccount<'info> {
     pub tip_distribution_account: Account<'info, TipDistributionAccount>,
 
     /// CHECK: Safe because we check the vote program is the owner before deserialization.
-    /// The validator's vote account is used to check this transaction's signer is also the authorized withdrawer.
+    /// The validator's vote account is used to check this transaction's signer is the node identity
+    /// or the authorized withdrawer.
     pub validator_vote_account: AccountInfo<'info>,
 
-    /// Must be equal to the supplied validator vote account's authorized withdrawer.
+    /// Must be equal to the supplied validator vote account's node identity or authorized withdrawer.
     #[account(mut)]
     pub signer: Signer<'info>,
 
@@ -492,6 +827,36 @@ impl UpdateConfig<'_> {
     }
 }
 
+#[derive(Accounts)]
+pub struct UpdateValidatorCommissionBps<'info> {
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        has_one = validator_vote_account @ Unauthorized,
+        rent_exempt = enforce
+    )]
+    pub tip_distribution_account: Account<'info, TipDistributionAccount>,
+
+    /// CHECK: Safe because we check the vote program is the owner before deserialization.
+    /// The validator's vote account is used to check this transaction's signer is the node identity.
+    pub validator_vote_account: AccountInfo<'info>,
+
+    pub signer: Signer<'info>,
+}
+
+impl UpdateValidatorCommissionBps<'_> {
+    fn auth(ctx: &Context<UpdateValidatorCommissionBps>) -> Result<()> {
+        let validator_vote_account_node_pubkey =
+            VoteState::deserialize_node_pubkey(&ctx.accounts.validator_vote_account)?;
+        if validator_vote_account_node_pubkey != ctx.accounts.signer.key() {
+            Err(Unauthorized.into())
+        } else {
+            Ok(())
+        }
+    }
+}
+
 #[derive(Accounts)]
 #[instruction(epoch: u64)]
 pub struct CloseTipDistributionAccount<'info> {
@@ -583,6 +948,183 @@ impl Claim<'_> {
     }
 }
 
+/// A single claim of [jito_tip_distribution::claim_batch], see [jito_tip_distribution::claim].
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct BatchClaim {
+    pub claimant: Pubkey,
+    pub amount: u64,
+    pub proof: Vec<[u8; 32]>,
+}
+
+/// A single claim of [jito_tip_distribution::claim_batch_multi].
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct MultiProofClaim {
+    pub claimant: Pubkey,
+    pub amount: u64,
+    /// Position of the claimant's leaf in the merkle tree.
+    pub index: u64,
+}
+
+#[derive(Accounts)]
+pub struct ClaimBatch<'info> {
+    pub config: Account<'info, Config>,
+
+    #[account(mut, rent_exempt = enforce)]
+    pub tip_distribution_account: Account<'info, TipDistributionAccount>,
+
+    pub merkle_root_upload_authority: Signer<'info>,
+
+    /// Who is paying for the claims.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl ClaimBatch<'_> {
+    fn auth(ctx: &Context<ClaimBatch>) -> Result<()> {
+        if ctx.accounts.merkle_root_upload_authority.key()
+            != ctx
+                .accounts
+                .tip_distribution_account
+                .merkle_root_upload_authority
+        {
+            Err(Unauthorized.into())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Checks a batch of `num_claims` claims can be paid out, returning the root they're
+    /// verified against.
+    fn validate(ctx: &Context<ClaimBatch>, num_claims: usize) -> Result<[u8; 32]> {
+        if num_claims == 0 || ctx.remaining_accounts.len() != num_claims.saturating_mul(2) {
+            return Err(ErrorCode::InvalidParameters.into());
+        }
+
+        let tip_distribution_account = &ctx.accounts.tip_distribution_account;
+        if tip_distribution_account.claim_mode != ClaimMode::ClaimStatus {
+            return Err(ErrorCode::InvalidClaimMode.into());
+        }
+        if Clock::get()?.epoch > tip_distribution_account.expires_at {
+            return Err(ErrorCode::ExpiredTipDistributionAccount.into());
+        }
+
+        Ok(tip_distribution_account
+            .merkle_root
+            .as_ref()
+            .ok_or(ErrorCode::RootNotUploaded)?
+            .root)
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitializeClaimBitmap<'info> {
+    pub config: Account<'info, Config>,
+
+    #[account(mut, rent_exempt = enforce)]
+    pub tip_distribution_account: Account<'info, TipDistributionAccount>,
+
+    #[account(
+        init,
+        rent_exempt = enforce,
+        seeds = [
+            ClaimBitmap::SEED,
+            tip_distribution_account.key().as_ref()
+        ],
+        bump,
+        space = ClaimBitmap::size(
+            tip_distribution_account
+                .merkle_root
+                .as_ref()
+                .map_or(0, |merkle_root| merkle_root.max_num_nodes)
+        ),
+        payer = payer
+    )]
+    pub claim_bitmap: Account<'info, ClaimBitmap>,
+
+    pub merkle_root_upload_authority: Signer<'info>,
+
+    /// Who is paying for the bitmap.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl InitializeClaimBitmap<'_> {
+    fn auth(ctx: &Context<InitializeClaimBitmap>) -> Result<()> {
+        if ctx.accounts.merkle_root_upload_authority.key()
+            != ctx
+                .accounts
+                .tip_distribution_account
+                .merkle_root_upload_authority
+        {
+            Err(Unauthorized.into())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[derive(Accounts)]
+pub struct ClaimWithBitmap<'info> {
+    pub config: Account<'info, Config>,
+
+    #[account(mut, rent_exempt = enforce)]
+    pub tip_distribution_account: Account<'info, TipDistributionAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            ClaimBitmap::SEED,
+            tip_distribution_account.key().as_ref()
+        ],
+        bump = claim_bitmap.bump
+    )]
+    pub claim_bitmap: Account<'info, ClaimBitmap>,
+
+    pub merkle_root_upload_authority: Signer<'info>,
+
+    /// CHECK: This is safe.
+    /// Receiver of the funds.
+    #[account(mut)]
+    pub claimant: AccountInfo<'info>,
+}
+
+impl ClaimWithBitmap<'_> {
+    fn auth(ctx: &Context<ClaimWithBitmap>) -> Result<()> {
+        if ctx.accounts.merkle_root_upload_authority.key()
+            != ctx
+                .accounts
+                .tip_distribution_account
+                .merkle_root_upload_authority
+        {
+            Err(Unauthorized.into())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[derive(Accounts)]
+pub struct CloseClaimBitmap<'info> {
+    #[account(seeds = [Config::SEED], bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        close = claim_bitmap_payer,
+        constraint = claim_bitmap_payer.key() == claim_bitmap.claim_bitmap_payer
+    )]
+    pub claim_bitmap: Account<'info, ClaimBitmap>,
+
+    /// CHECK: This is checked against claim_bitmap in the constraint
+    /// Receiver of the funds.
+    #[account(mut)]
+    pub claim_bitmap_payer: UncheckedAccount<'info>,
+}
+
 #[derive(Accounts)]
 pub struct UploadMerkleRoot<'info> {
     pub config: Account<'info, Config>,
@@ -672,6 +1214,34 @@ impl UpdateMerkleRootUploadConfig<'_> {
     }
 }
 
+#[derive(Accounts)]
+pub struct UpdateMerkleRootUploadAuthority<'info> {
+    #[account(
+        mut,
+        has_one = validator_vote_account @ Unauthorized,
+        rent_exempt = enforce
+    )]
+    pub tip_distribution_account: Account<'info, TipDistributionAccount>,
+
+    /// CHECK: Safe because we check the vote program is the owner before deserialization.
+    /// The validator's vote account is used to check this transaction's signer is the node identity.
+    pub validator_vote_account: AccountInfo<'info>,
+
+    pub signer: Signer<'info>,
+}
+
+impl UpdateMerkleRootUploadAuthority<'_> {
+    fn auth(ctx: &Context<UpdateMerkleRootUploadAuthority>) -> Result<()> {
+        let validator_vote_account_node_pubkey =
+            VoteState::deserialize_node_pubkey(&ctx.accounts.validator_vote_account)?;
+        if validator_vote_account_node_pubkey != ctx.accounts.signer.key() {
+            Err(Unauthorized.into())
+        } else {
+            Ok(())
+        }
+    }
+}
+
 #[derive(Accounts)]
 pub struct MigrateTdaMerkleRootUploadAuthority<'info> {
     #[account(mut, rent_exempt = enforce)]
@@ -688,11 +1258,13 @@ pub struct MigrateTdaMerkleRootUploadAuthority<'info> {
 // Events
 
 #[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct TipDistributionAccountInitializedEvent {
     pub tip_distribution_account: Pubkey,
 }
 
 #[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ValidatorCommissionBpsUpdatedEvent {
     pub tip_distribution_account: Pubkey,
     pub old_commission_bps: u16,
@@ -700,18 +1272,21 @@ pub struct ValidatorCommissionBpsUpdatedEvent {
 }
 
 #[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct MerkleRootUploadAuthorityUpdatedEvent {
     pub old_authority: Pubkey,
     pub new_authority: Pubkey,
 }
 
 #[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ConfigUpdatedEvent {
     /// Who updated it.
-    authority: Pubkey,
+    pub authority: Pubkey,
 }
 
 #[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ClaimedEvent {
     /// [TipDistributionAccount] claimed from.
     pub tip_distribution_account: Pubkey,
@@ -727,6 +1302,7 @@ pub struct ClaimedEvent {
 }
 
 #[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct MerkleRootUploadedEvent {
     /// Who uploaded the root.
     pub merkle_root_upload_authority: Pubkey,
@@ -736,6 +1312,7 @@ pub struct MerkleRootUploadedEvent {
 }
 
 #[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct TipDistributionAccountClosedEvent {
     /// Account where unclaimed funds were transferred to.
     pub expired_funds_account: Pubkey,
@@ -748,6 +1325,7 @@ pub struct TipDistributionAccountClosedEvent {
 }
 
 #[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ClaimStatusClosedEvent {
     /// Account where funds were transferred to.
     pub claim_status_payer: Pubkey,
@@ -755,3 +1333,26 @@ pub struct ClaimStatusClosedEvent {
     /// [ClaimStatus] account that was closed.
     pub claim_status_account: Pubkey,
 }
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClaimBitmapInitializedEvent {
+    /// [TipDistributionAccount] switched to [ClaimMode::Bitmap].
+    pub tip_distribution_account: Pubkey,
+
+    /// [ClaimBitmap] account that was created.
+    pub claim_bitmap: Pubkey,
+
+    /// Number of claims the bitmap tracks.
+    pub num_nodes: u64,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClaimBitmapClosedEvent {
+    /// Account where funds were transferred to.
+    pub claim_bitmap_payer: Pubkey,
+
+    /// [ClaimBitmap] account that was closed.
+    pub claim_bitmap_account: Pubkey,
+}
@@ -27,3 +27,61 @@ pub fn verify(proof: Vec<[u8; 32]>, root: [u8; 32], leaf: [u8; 32]) -> bool {
     // Check if the computed hash (root) is equal to the provided root
     computed_hash == root
 }
+
+fn hash_pair(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    if a <= b {
+        solana_program::hash::hashv(&[&[1u8], a, b]).to_bytes()
+    } else {
+        solana_program::hash::hashv(&[&[1u8], b, a]).to_bytes()
+    }
+}
+
+/// Returns true if every leaf in `leaves` can be proved to be a part of the Merkle tree defined
+/// by `root`, using one set of sibling hashes shared between them.
+///
+/// `leaves` are `(index, leaf)` pairs in strictly ascending index order, out of a tree of
+/// `num_leaves` leaves in which a node without a sibling is hashed with itself.
+/// `proof` holds only the siblings that can't be computed from the leaves themselves, in the
+/// order they're needed: layer by layer from the leaves up, left to right within a layer.
+pub fn verify_multi(
+    proof: Vec<[u8; 32]>,
+    root: [u8; 32],
+    num_leaves: u64,
+    leaves: Vec<(u64, [u8; 32])>,
+) -> bool {
+    match leaves.last() {
+        Some((last_index, _)) if *last_index < num_leaves => {}
+        _ => return false,
+    }
+    if leaves.windows(2).any(|pair| pair[0].0 >= pair[1].0) {
+        return false;
+    }
+
+    let mut proof = proof.into_iter();
+    let mut nodes = leaves;
+    let mut layer_len = num_leaves;
+    while layer_len > 1 {
+        let mut parents = Vec::with_capacity(nodes.len());
+        let mut nodes_iter = nodes.into_iter().peekable();
+        while let Some((index, hash)) = nodes_iter.next() {
+            let sibling_index = index ^ 1;
+            let parent = if sibling_index >= layer_len {
+                hash_pair(&hash, &hash)
+            } else if let Some((_, sibling)) =
+                nodes_iter.next_if(|(next_index, _)| *next_index == sibling_index)
+            {
+                hash_pair(&hash, &sibling)
+            } else if let Some(sibling) = proof.next() {
+                hash_pair(&hash, &sibling)
+            } else {
+                return false;
+            };
+            parents.push((index >> 1, parent));
+        }
+        nodes = parents;
+        layer_len = layer_len.div_ceil(2);
+    }
+
+    // every proof element must have been used, leaving the root as the only node
+    proof.next().is_none() && nodes.first().map(|(_, hash)| *hash) == Some(root)
+}
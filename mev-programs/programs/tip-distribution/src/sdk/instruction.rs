@@ -0,0 +1,696 @@
+//! This module contains functions that build instructions to interact with the tip-distribution program.
+//!
+//! Every PDA, and its bump, is derived by the builders. Instructions signed by the validator
+//! locate the [crate::state::TipDistributionAccount] by vote account and epoch, the rest take its
+//! address as found in the distribution tree.
+use anchor_lang::{
+    prelude::Pubkey,
+    solana_program::instruction::{AccountMeta, Instruction},
+    system_program, InstructionData, ToAccountMetas,
+};
+
+use super::{
+    derive_claim_bitmap_account_address, derive_claim_status_account_address,
+    derive_config_account_address, derive_merkle_root_upload_config_account_address,
+    derive_tip_distribution_account_address, Config,
+};
+use crate::{BatchClaim, MultiProofClaim};
+
+pub struct InitializeArgs {
+    pub authority: Pubkey,
+    pub expired_funds_account: Pubkey,
+    pub num_epochs_valid: u64,
+    pub max_validator_commission_bps: u16,
+}
+pub struct InitializeAccounts {
+    pub initializer: Pubkey,
+}
+pub fn initialize_ix(
+    program_id: Pubkey,
+    args: InitializeArgs,
+    accounts: InitializeAccounts,
+) -> Instruction {
+    let InitializeArgs {
+        authority,
+        expired_funds_account,
+        num_epochs_valid,
+        max_validator_commission_bps,
+    } = args;
+
+    let InitializeAccounts { initializer } = accounts;
+
+    let (config, bump) = derive_config_account_address(&program_id);
+
+    Instruction {
+        program_id,
+        data: crate::instruction::Initialize {
+            authority,
+            expired_funds_account,
+            num_epochs_valid,
+            max_validator_commission_bps,
+            bump,
+        }
+        .data(),
+        accounts: crate::accounts::Initialize {
+            config,
+            system_program: system_program::ID,
+            initializer,
+        }
+        .to_account_metas(None),
+    }
+}
+
+pub struct InitializeTipDistributionAccountArgs {
+    pub merkle_root_upload_authority: Pubkey,
+    pub validator_commission_bps: u16,
+}
+pub struct InitializeTipDistributionAccountAccounts {
+    pub validator_vote_account: Pubkey,
+    /// The current epoch, which the program creates the account for.
+    pub epoch: u64,
+    pub signer: Pubkey,
+}
+pub fn initialize_tip_distribution_account_ix(
+    program_id: Pubkey,
+    args: InitializeTipDistributionAccountArgs,
+    accounts: InitializeTipDistributionAccountAccounts,
+) -> Instruction {
+    let InitializeTipDistributionAccountArgs {
+        merkle_root_upload_authority,
+        validator_commission_bps,
+    } = args;
+
+    let InitializeTipDistributionAccountAccounts {
+        validator_vote_account,
+        epoch,
+        signer,
+    } = accounts;
+
+    let (tip_distribution_account, bump) =
+        derive_tip_distribution_account_address(&program_id, &validator_vote_account, epoch);
+
+    Instruction {
+        program_id,
+        data: crate::instruction::InitializeTipDistributionAccount {
+            merkle_root_upload_authority,
+            validator_commission_bps,
+            bump,
+        }
+        .data(),
+        accounts: crate::accounts::InitializeTipDistributionAccount {
+            config: derive_config_account_address(&program_id).0,
+            signer,
+            system_program: system_program::ID,
+            tip_distribution_account,
+            validator_vote_account,
+        }
+        .to_account_metas(None),
+    }
+}
+
+pub struct CloseClaimStatusArgs;
+pub struct CloseClaimStatusAccounts {
+    pub tip_distribution_account: Pubkey,
+    pub claimant: Pubkey,
+    pub claim_status_payer: Pubkey,
+}
+pub fn close_claim_status_ix(
+    program_id: Pubkey,
+    _args: CloseClaimStatusArgs,
+    accounts: CloseClaimStatusAccounts,
+) -> Instruction {
+    let CloseClaimStatusAccounts {
+        tip_distribution_account,
+        claimant,
+        claim_status_payer,
+    } = accounts;
+
+    let (claim_status, _) =
+        derive_claim_status_account_address(&program_id, &claimant, &tip_distribution_account);
+
+    Instruction {
+        program_id,
+        data: crate::instruction::CloseClaimStatus {}.data(),
+        accounts: crate::accounts::CloseClaimStatus {
+            config: derive_config_account_address(&program_id).0,
+            claim_status,
+            claim_status_payer,
+        }
+        .to_account_metas(None),
+    }
+}
+
+pub struct UpdateConfigArgs {
+    pub new_config: Config,
+}
+pub struct UpdateConfigAccounts {
+    pub authority: Pubkey,
+}
+pub fn update_config_ix(
+    program_id: Pubkey,
+    args: UpdateConfigArgs,
+    accounts: UpdateConfigAccounts,
+) -> Instruction {
+    let UpdateConfigArgs { new_config } = args;
+
+    let UpdateConfigAccounts { authority } = accounts;
+
+    Instruction {
+        program_id,
+        data: crate::instruction::UpdateConfig { new_config }.data(),
+        accounts: crate::accounts::UpdateConfig {
+            config: derive_config_account_address(&program_id).0,
+            authority,
+        }
+        .to_account_metas(None),
+    }
+}
+
+pub struct UpdateValidatorCommissionBpsArgs {
+    pub validator_commission_bps: u16,
+}
+pub struct UpdateValidatorCommissionBpsAccounts {
+    pub validator_vote_account: Pubkey,
+    pub epoch: u64,
+    pub signer: Pubkey,
+}
+pub fn update_validator_commission_bps_ix(
+    program_id: Pubkey,
+    args: UpdateValidatorCommissionBpsArgs,
+    accounts: UpdateValidatorCommissionBpsAccounts,
+) -> Instruction {
+    let UpdateValidatorCommissionBpsArgs {
+        validator_commission_bps,
+    } = args;
+
+    let UpdateValidatorCommissionBpsAccounts {
+        validator_vote_account,
+        epoch,
+        signer,
+    } = accounts;
+
+    Instruction {
+        program_id,
+        data: crate::instruction::UpdateValidatorCommissionBps {
+            validator_commission_bps,
+        }
+        .data(),
+        accounts: crate::accounts::UpdateValidatorCommissionBps {
+            config: derive_config_account_address(&program_id).0,
+            tip_distribution_account: derive_tip_distribution_account_address(
+                &program_id,
+                &validator_vote_account,
+                epoch,
+            )
+            .0,
+            validator_vote_account,
+            signer,
+        }
+        .to_account_metas(None),
+    }
+}
+
+pub struct UpdateMerkleRootUploadAuthorityArgs {
+    pub new_authority: Pubkey,
+}
+pub struct UpdateMerkleRootUploadAuthorityAccounts {
+    pub validator_vote_account: Pubkey,
+    pub epoch: u64,
+    pub signer: Pubkey,
+}
+pub fn update_merkle_root_upload_authority_ix(
+    program_id: Pubkey,
+    args: UpdateMerkleRootUploadAuthorityArgs,
+    accounts: UpdateMerkleRootUploadAuthorityAccounts,
+) -> Instruction {
+    let UpdateMerkleRootUploadAuthorityArgs { new_authority } = args;
+
+    let UpdateMerkleRootUploadAuthorityAccounts {
+        validator_vote_account,
+        epoch,
+        signer,
+    } = accounts;
+
+    Instruction {
+        program_id,
+        data: crate::instruction::UpdateMerkleRootUploadAuthority { new_authority }.data(),
+        accounts: crate::accounts::UpdateMerkleRootUploadAuthority {
+            tip_distribution_account: derive_tip_distribution_account_address(
+                &program_id,
+                &validator_vote_account,
+                epoch,
+            )
+            .0,
+            validator_vote_account,
+            signer,
+        }
+        .to_account_metas(None),
+    }
+}
+
+pub struct UploadMerkleRootArgs {
+    pub root: [u8; 32],
+    pub max_total_claim: u64,
+    pub max_num_nodes: u64,
+}
+pub struct UploadMerkleRootAccounts {
+    pub tip_distribution_account: Pubkey,
+    pub merkle_root_upload_authority: Pubkey,
+}
+pub fn upload_merkle_root_ix(
+    program_id: Pubkey,
+    args: UploadMerkleRootArgs,
+    accounts: UploadMerkleRootAccounts,
+) -> Instruction {
+    let UploadMerkleRootArgs {
+        root,
+        max_total_claim,
+        max_num_nodes,
+    } = args;
+
+    let UploadMerkleRootAccounts {
+        tip_distribution_account,
+        merkle_root_upload_authority,
+    } = accounts;
+
+    Instruction {
+        program_id,
+        data: crate::instruction::UploadMerkleRoot {
+            max_total_claim,
+            max_num_nodes,
+            root,
+        }
+        .data(),
+        accounts: crate::accounts::UploadMerkleRoot {
+            config: derive_config_account_address(&program_id).0,
+            merkle_root_upload_authority,
+            tip_distribution_account,
+        }
+        .to_account_metas(None),
+    }
+}
+
+pub struct CloseTipDistributionAccountArgs {
+    pub _epoch: u64,
+}
+pub struct CloseTipDistributionAccounts {
+    pub validator_vote_account: Pubkey,
+    /// The [Config] account's `expired_funds_account`.
+    pub expired_funds_account: Pubkey,
+    pub signer: Pubkey,
+}
+pub fn close_tip_distribution_account_ix(
+    program_id: Pubkey,
+    args: CloseTipDistributionAccountArgs,
+    accounts: CloseTipDistributionAccounts,
+) -> Instruction {
+    let CloseTipDistributionAccountArgs { _epoch } = args;
+
+    let CloseTipDistributionAccounts {
+        validator_vote_account,
+        expired_funds_account,
+        signer,
+    } = accounts;
+
+    let (tip_distribution_account, _) =
+        derive_tip_distribution_account_address(&program_id, &validator_vote_account, _epoch);
+
+    Instruction {
+        program_id,
+        data: crate::instruction::CloseTipDistributionAccount { _epoch }.data(),
+        accounts: crate::accounts::CloseTipDistributionAccount {
+            config: derive_config_account_address(&program_id).0,
+            validator_vote_account,
+            expired_funds_account,
+            tip_distribution_account,
+            signer,
+        }
+        .to_account_metas(None),
+    }
+}
+
+pub struct ClaimArgs {
+    pub proof: Vec<[u8; 32]>,
+    pub amount: u64,
+}
+pub struct ClaimAccounts {
+    pub tip_distribution_account: Pubkey,
+    pub merkle_root_upload_authority: Pubkey,
+    pub claimant: Pubkey,
+    pub payer: Pubkey,
+}
+pub fn claim_ix(program_id: Pubkey, args: ClaimArgs, accounts: ClaimAccounts) -> Instruction {
+    let ClaimArgs { proof, amount } = args;
+
+    let ClaimAccounts {
+        tip_distribution_account,
+        merkle_root_upload_authority,
+        claimant,
+        payer,
+    } = accounts;
+
+    let (claim_status, bump) =
+        derive_claim_status_account_address(&program_id, &claimant, &tip_distribution_account);
+
+    Instruction {
+        program_id,
+        data: crate::instruction::Claim {
+            proof,
+            amount,
+            bump,
+        }
+        .data(),
+        accounts: crate::accounts::Claim {
+            config: derive_config_account_address(&program_id).0,
+            tip_distribution_account,
+            merkle_root_upload_authority,
+            claimant,
+            claim_status,
+            payer,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+    }
+}
+
+pub struct ClaimBatchArgs {
+    pub claims: Vec<BatchClaim>,
+}
+pub struct ClaimBatchAccounts {
+    pub tip_distribution_account: Pubkey,
+    pub merkle_root_upload_authority: Pubkey,
+    pub payer: Pubkey,
+}
+/// Each claim's claim status and claimant accounts are appended in the order of `args.claims`.
+pub fn claim_batch_ix(
+    program_id: Pubkey,
+    args: ClaimBatchArgs,
+    accounts: ClaimBatchAccounts,
+) -> Instruction {
+    let ClaimBatchArgs { claims } = args;
+
+    let account_metas = claim_batch_account_metas(
+        &program_id,
+        accounts,
+        claims.iter().map(|claim| claim.claimant),
+    );
+
+    Instruction {
+        program_id,
+        data: crate::instruction::ClaimBatch { claims }.data(),
+        accounts: account_metas,
+    }
+}
+
+pub struct ClaimBatchMultiArgs {
+    pub claims: Vec<MultiProofClaim>,
+    pub num_leaves: u64,
+    pub proof: Vec<[u8; 32]>,
+}
+/// Accounts are laid out as in [claim_batch_ix].
+pub fn claim_batch_multi_ix(
+    program_id: Pubkey,
+    args: ClaimBatchMultiArgs,
+    accounts: ClaimBatchAccounts,
+) -> Instruction {
+    let ClaimBatchMultiArgs {
+        claims,
+        num_leaves,
+        proof,
+    } = args;
+
+    let account_metas = claim_batch_account_metas(
+        &program_id,
+        accounts,
+        claims.iter().map(|claim| claim.claimant),
+    );
+
+    Instruction {
+        program_id,
+        data: crate::instruction::ClaimBatchMulti {
+            claims,
+            num_leaves,
+            proof,
+        }
+        .data(),
+        accounts: account_metas,
+    }
+}
+
+fn claim_batch_account_metas(
+    program_id: &Pubkey,
+    accounts: ClaimBatchAccounts,
+    claimants: impl Iterator<Item = Pubkey>,
+) -> Vec<AccountMeta> {
+    let ClaimBatchAccounts {
+        tip_distribution_account,
+        merkle_root_upload_authority,
+        payer,
+    } = accounts;
+
+    let mut account_metas = crate::accounts::ClaimBatch {
+        config: derive_config_account_address(program_id).0,
+        tip_distribution_account,
+        merkle_root_upload_authority,
+        payer,
+        system_program: system_program::ID,
+    }
+    .to_account_metas(None);
+    for claimant in claimants {
+        let (claim_status, _) =
+            derive_claim_status_account_address(program_id, &claimant, &tip_distribution_account);
+        account_metas.push(AccountMeta::new(claim_status, false));
+        account_metas.push(AccountMeta::new(claimant, false));
+    }
+
+    account_metas
+}
+
+pub struct InitializeClaimBitmapArgs;
+pub struct InitializeClaimBitmapAccounts {
+    pub tip_distribution_account: Pubkey,
+    pub merkle_root_upload_authority: Pubkey,
+    pub payer: Pubkey,
+}
+pub fn initialize_claim_bitmap_ix(
+    program_id: Pubkey,
+    _args: InitializeClaimBitmapArgs,
+    accounts: InitializeClaimBitmapAccounts,
+) -> Instruction {
+    let InitializeClaimBitmapAccounts {
+        tip_distribution_account,
+        merkle_root_upload_authority,
+        payer,
+    } = accounts;
+
+    Instruction {
+        program_id,
+        data: crate::instruction::InitializeClaimBitmap {}.data(),
+        accounts: crate::accounts::InitializeClaimBitmap {
+            config: derive_config_account_address(&program_id).0,
+            tip_distribution_account,
+            claim_bitmap: derive_claim_bitmap_account_address(
+                &program_id,
+                &tip_distribution_account,
+            )
+            .0,
+            merkle_root_upload_authority,
+            payer,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+    }
+}
+
+pub struct ClaimWithBitmapArgs {
+    pub index: u64,
+    pub amount: u64,
+    pub proof: Vec<[u8; 32]>,
+}
+pub struct ClaimWithBitmapAccounts {
+    pub tip_distribution_account: Pubkey,
+    pub merkle_root_upload_authority: Pubkey,
+    pub claimant: Pubkey,
+}
+pub fn claim_with_bitmap_ix(
+    program_id: Pubkey,
+    args: ClaimWithBitmapArgs,
+    accounts: ClaimWithBitmapAccounts,
+) -> Instruction {
+    let ClaimWithBitmapArgs {
+        index,
+        amount,
+        proof,
+    } = args;
+
+    let ClaimWithBitmapAccounts {
+        tip_distribution_account,
+        merkle_root_upload_authority,
+        claimant,
+    } = accounts;
+
+    Instruction {
+        program_id,
+        data: crate::instruction::ClaimWithBitmap {
+            index,
+            amount,
+            proof,
+        }
+        .data(),
+        accounts: crate::accounts::ClaimWithBitmap {
+            config: derive_config_account_address(&program_id).0,
+            tip_distribution_account,
+            claim_bitmap: derive_claim_bitmap_account_address(
+                &program_id,
+                &tip_distribution_account,
+            )
+            .0,
+            merkle_root_upload_authority,
+            claimant,
+        }
+        .to_account_metas(None),
+    }
+}
+
+pub struct CloseClaimBitmapArgs;
+pub struct CloseClaimBitmapAccounts {
+    pub tip_distribution_account: Pubkey,
+    pub claim_bitmap_payer: Pubkey,
+}
+pub fn close_claim_bitmap_ix(
+    program_id: Pubkey,
+    _args: CloseClaimBitmapArgs,
+    accounts: CloseClaimBitmapAccounts,
+) -> Instruction {
+    let CloseClaimBitmapAccounts {
+        tip_distribution_account,
+        claim_bitmap_payer,
+    } = accounts;
+
+    Instruction {
+        program_id,
+        data: crate::instruction::CloseClaimBitmap {}.data(),
+        accounts: crate::accounts::CloseClaimBitmap {
+            config: derive_config_account_address(&program_id).0,
+            claim_bitmap: derive_claim_bitmap_account_address(
+                &program_id,
+                &tip_distribution_account,
+            )
+            .0,
+            claim_bitmap_payer,
+        }
+        .to_account_metas(None),
+    }
+}
+
+pub struct InitializeMerkleRootUploadConfigArgs {
+    /// The authority migrated [crate::state::TipDistributionAccount]s are given.
+    pub authority: Pubkey,
+    /// The upload authority of the accounts that may be migrated.
+    pub original_authority: Pubkey,
+}
+pub struct InitializeMerkleRootUploadConfigAccounts {
+    /// The [Config] account's authority.
+    pub authority: Pubkey,
+    pub payer: Pubkey,
+}
+pub fn initialize_merkle_root_upload_config_ix(
+    program_id: Pubkey,
+    args: InitializeMerkleRootUploadConfigArgs,
+    accounts: InitializeMerkleRootUploadConfigAccounts,
+) -> Instruction {
+    let InitializeMerkleRootUploadConfigArgs {
+        authority: override_authority,
+        original_authority,
+    } = args;
+
+    let InitializeMerkleRootUploadConfigAccounts { authority, payer } = accounts;
+
+    Instruction {
+        program_id,
+        data: crate::instruction::InitializeMerkleRootUploadConfig {
+            authority: override_authority,
+            original_authority,
+        }
+        .data(),
+        accounts: crate::accounts::InitializeMerkleRootUploadConfig {
+            config: derive_config_account_address(&program_id).0,
+            merkle_root_upload_config: derive_merkle_root_upload_config_account_address(
+                &program_id,
+            )
+            .0,
+            authority,
+            payer,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+    }
+}
+
+/// See [InitializeMerkleRootUploadConfigArgs].
+pub struct UpdateMerkleRootUploadConfigArgs {
+    pub authority: Pubkey,
+    pub original_authority: Pubkey,
+}
+pub struct UpdateMerkleRootUploadConfigAccounts {
+    /// The [Config] account's authority.
+    pub authority: Pubkey,
+}
+pub fn update_merkle_root_upload_config_ix(
+    program_id: Pubkey,
+    args: UpdateMerkleRootUploadConfigArgs,
+    accounts: UpdateMerkleRootUploadConfigAccounts,
+) -> Instruction {
+    let UpdateMerkleRootUploadConfigArgs {
+        authority: override_authority,
+        original_authority,
+    } = args;
+
+    let UpdateMerkleRootUploadConfigAccounts { authority } = accounts;
+
+    Instruction {
+        program_id,
+        data: crate::instruction::UpdateMerkleRootUploadConfig {
+            authority: override_authority,
+            original_authority,
+        }
+        .data(),
+        accounts: crate::accounts::UpdateMerkleRootUploadConfig {
+            config: derive_config_account_address(&program_id).0,
+            merkle_root_upload_config: derive_merkle_root_upload_config_account_address(
+                &program_id,
+            )
+            .0,
+            authority,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+    }
+}
+
+pub struct MigrateTdaMerkleRootUploadAuthorityArgs;
+pub struct MigrateTdaMerkleRootUploadAuthorityAccounts {
+    pub tip_distribution_account: Pubkey,
+}
+pub fn migrate_tda_merkle_root_upload_authority_ix(
+    program_id: Pubkey,
+    _args: MigrateTdaMerkleRootUploadAuthorityArgs,
+    accounts: MigrateTdaMerkleRootUploadAuthorityAccounts,
+) -> Instruction {
+    let MigrateTdaMerkleRootUploadAuthorityAccounts {
+        tip_distribution_account,
+    } = accounts;
+
+    Instruction {
+        program_id,
+        data: crate::instruction::MigrateTdaMerkleRootUploadAuthority {}.data(),
+        accounts: crate::accounts::MigrateTdaMerkleRootUploadAuthority {
+            tip_distribution_account,
+            merkle_root_upload_config: derive_merkle_root_upload_config_account_address(
+                &program_id,
+            )
+            .0,
+        }
+        .to_account_metas(None),
+    }
+}
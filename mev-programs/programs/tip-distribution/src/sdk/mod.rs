@@ -1,8 +1,12 @@
-pub mod instructions;
+//! PDA derivation and instruction builders for off-chain clients and CPI callers, enabled by
+//! the `sdk` feature.
+pub mod instruction;
 
 use anchor_lang::{prelude::Pubkey, solana_program::clock::Epoch};
 
-use crate::{Config, TipDistributionAccount};
+use crate::state::{
+    ClaimBitmap, ClaimStatus, Config, MerkleRootUploadConfig, TipDistributionAccount,
+};
 
 pub fn derive_tip_distribution_account_address(
     tip_distribution_program_id: &Pubkey,
@@ -22,3 +26,37 @@ pub fn derive_tip_distribution_account_address(
 pub fn derive_config_account_address(tip_distribution_program_id: &Pubkey) -> (Pubkey, u8) {
     Pubkey::find_program_address(&[Config::SEED], tip_distribution_program_id)
 }
+
+pub fn derive_claim_status_account_address(
+    tip_distribution_program_id: &Pubkey,
+    claimant: &Pubkey,
+    tip_distribution_account: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            ClaimStatus::SEED,
+            claimant.to_bytes().as_ref(),
+            tip_distribution_account.to_bytes().as_ref(),
+        ],
+        tip_distribution_program_id,
+    )
+}
+
+pub fn derive_claim_bitmap_account_address(
+    tip_distribution_program_id: &Pubkey,
+    tip_distribution_account: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            ClaimBitmap::SEED,
+            tip_distribution_account.to_bytes().as_ref(),
+        ],
+        tip_distribution_program_id,
+    )
+}
+
+pub fn derive_merkle_root_upload_config_account_address(
+    tip_distribution_program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[MerkleRootUploadConfig::SEED], tip_distribution_program_id)
+}
@@ -2,9 +2,15 @@
 
 use std::mem::size_of;
 
-use anchor_lang::prelude::*;
+use anchor_lang::{prelude::*, system_program};
+use jito_programs_vote_state::VoteCommissionError;
+pub use jito_programs_vote_state::VoteCommissionPolicy;
 
-use crate::ErrorCode::{AccountValidationFailure, ArithmeticError};
+use crate::ErrorCode::{
+    AccountValidationFailure, ArithmeticError, ExceedsMaxClaim, ExceedsMaxNumNodes,
+    FundsAlreadyClaimed, InvalidClaimIndex, MaxValidatorCommissionFeeBpsExceeded,
+    NodeAlreadyClaimed, VoteAccountCommissionExceeded,
+};
 
 #[account]
 #[derive(Default)]
@@ -24,6 +30,9 @@ pub struct Config {
 
     /// The bump used to generate this account
     pub bump: u8,
+
+    /// How the commission validators charge relates to the commission in their vote account.
+    pub vote_commission_policy: VoteCommissionPolicy,
 }
 
 /// The account that validators register as **tip_receiver** with the tip-payment program.
@@ -51,6 +60,20 @@ pub struct TipDistributionAccount {
 
     /// The bump used to generate this account
     pub bump: u8,
+
+    /// How claims against the merkle root are tracked.
+    pub claim_mode: ClaimMode,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ClaimMode {
+    /// Each claim creates a [ClaimStatus] PDA, leaves commit to `(claimant, amount)`.
+    #[default]
+    ClaimStatus,
+
+    /// Claims are tracked in the account's [ClaimBitmap], leaves commit to
+    /// `(claimant, amount, index)`.
+    Bitmap,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
@@ -96,6 +119,30 @@ impl Config {
 
         Ok(())
     }
+
+    /// The commission a validator charges on a new or updated distribution account, given the
+    /// one they asked for, according to the [VoteCommissionPolicy].
+    pub fn validator_commission_bps(
+        &self,
+        requested_commission_bps: u16,
+        validator_vote_account: &AccountInfo,
+    ) -> Result<u16> {
+        self.vote_commission_policy
+            .validator_commission_bps(
+                requested_commission_bps,
+                self.max_validator_commission_bps,
+                validator_vote_account,
+            )
+            .map_err(|e| match e {
+                VoteCommissionError::VoteAccount(e) => e,
+                VoteCommissionError::VoteAccountCommissionExceeded => {
+                    VoteAccountCommissionExceeded.into()
+                }
+                VoteCommissionError::MaxCommissionExceeded => {
+                    MaxValidatorCommissionFeeBpsExceeded.into()
+                }
+            })
+    }
 }
 
 impl TipDistributionAccount {
@@ -170,10 +217,144 @@ pub struct ClaimStatus {
     pub bump: u8,
 }
 
+impl MerkleRoot {
+    /// Counts a claim of `amount` against the root's limits.
+    pub fn record_claim(&mut self, amount: u64) -> Result<()> {
+        self.total_funds_claimed = self
+            .total_funds_claimed
+            .checked_add(amount)
+            .ok_or(ArithmeticError)?;
+        if self.total_funds_claimed > self.max_total_claim {
+            return Err(ExceedsMaxClaim.into());
+        }
+
+        self.num_nodes_claimed = self
+            .num_nodes_claimed
+            .checked_add(1)
+            .ok_or(ArithmeticError)?;
+        if self.num_nodes_claimed > self.max_num_nodes {
+            return Err(ExceedsMaxNumNodes.into());
+        }
+
+        Ok(())
+    }
+}
+
 impl ClaimStatus {
     pub const SEED: &'static [u8] = b"CLAIM_STATUS";
 
     pub const SIZE: usize = HEADER_SIZE + size_of::<Self>();
+
+    /// Creates the claim status PDA derived from `seeds`, the equivalent of an `init` constraint
+    /// for claim status accounts passed through the remaining accounts. Returns its bump.
+    pub fn create<'info>(
+        claim_status: &AccountInfo<'info>,
+        seeds: &[&[u8]],
+        payer: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+        rent: &Rent,
+    ) -> Result<u8> {
+        // only the canonical bump is accepted, otherwise every other bump would derive another
+        // claim status for the same claimant to claim again with. Proving a given bump canonical
+        // costs the same search, so it's derived here.
+        let (pda, bump) = Pubkey::find_program_address(seeds, &crate::id());
+        if pda != claim_status.key() {
+            return Err(anchor_lang::error::ErrorCode::ConstraintSeeds.into());
+        }
+        let bump_seed = [bump];
+        let signer_seeds = [seeds, &[&bump_seed]].concat();
+        // an initialized claim status means the claimant already claimed
+        if *claim_status.owner != system_program::ID || !claim_status.data_is_empty() {
+            return Err(FundsAlreadyClaimed.into());
+        }
+
+        // the account may have been funded in advance, in which case it can't be created outright
+        let required_lamports = rent
+            .minimum_balance(Self::SIZE)
+            .saturating_sub(claim_status.lamports());
+        if required_lamports > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    system_program.clone(),
+                    system_program::Transfer {
+                        from: payer.clone(),
+                        to: claim_status.clone(),
+                    },
+                ),
+                required_lamports,
+            )?;
+        }
+        system_program::allocate(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                system_program::Allocate {
+                    account_to_allocate: claim_status.clone(),
+                },
+                &[&signer_seeds],
+            ),
+            Self::SIZE as u64,
+        )?;
+        system_program::assign(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                system_program::Assign {
+                    account_to_assign: claim_status.clone(),
+                },
+                &[&signer_seeds],
+            ),
+            &crate::id(),
+        )?;
+
+        Ok(bump)
+    }
+}
+
+/// Tracks which leaves of a [TipDistributionAccount]'s merkle root have been claimed, one bit per
+/// leaf, for accounts in [ClaimMode::Bitmap].
+#[account]
+#[derive(Default)]
+pub struct ClaimBitmap {
+    /// The [TipDistributionAccount] whose claims are tracked.
+    pub tip_distribution_account: Pubkey,
+
+    /// The payer who created the bitmap, refunded its rent when it's closed.
+    pub claim_bitmap_payer: Pubkey,
+
+    /// The epoch (upto and including) that tip funds can be claimed.
+    /// Copied since TDA can be closed, need to track to avoid making multiple claims
+    pub expires_at: u64,
+
+    /// The bump used to generate this account
+    pub bump: u8,
+
+    /// Bit `index % 8` of byte `index / 8` is set once the leaf at `index` is claimed.
+    pub bitmap: Vec<u8>,
+}
+
+impl ClaimBitmap {
+    pub const SEED: &'static [u8] = b"CLAIM_BITMAP";
+
+    /// Size of a bitmap tracking `num_leaves` leaves.
+    pub fn size(num_leaves: u64) -> usize {
+        HEADER_SIZE
+            .saturating_add(32 + 32 + 8 + 1 + 4)
+            .saturating_add(num_leaves.div_ceil(8) as usize)
+    }
+
+    /// Marks the leaf at `index` as claimed, failing if it already was.
+    pub fn set_claimed(&mut self, index: u64) -> Result<()> {
+        let byte = self
+            .bitmap
+            .get_mut((index >> 3) as usize)
+            .ok_or(InvalidClaimIndex)?;
+        let mask = 1u8 << (index & 7);
+        if *byte & mask != 0 {
+            return Err(NodeAlreadyClaimed.into());
+        }
+        *byte |= mask;
+
+        Ok(())
+    }
 }
 
 /// Singleton account that allows overriding TDA's merkle upload authority
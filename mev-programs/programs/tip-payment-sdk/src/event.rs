@@ -0,0 +1,25 @@
+//! Decodes the events the tip-payment program emits to transaction logs.
+use anchor_lang::{AnchorDeserialize, Discriminator};
+pub use jito_programs_sdk_common::event::{decode_events, Event};
+use jito_tip_payment::{StrandedTipsClaimed, TipsClaimed};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TipPaymentEvent {
+    TipsClaimed(TipsClaimed),
+    StrandedTipsClaimed(StrandedTipsClaimed),
+}
+
+impl Event for TipPaymentEvent {
+    fn decode(data: &[u8]) -> Option<Self> {
+        let (discriminator, mut data) = data.split_at_checked(8)?;
+        match discriminator {
+            TipsClaimed::DISCRIMINATOR => TipsClaimed::deserialize(&mut data)
+                .ok()
+                .map(Self::TipsClaimed),
+            StrandedTipsClaimed::DISCRIMINATOR => StrandedTipsClaimed::deserialize(&mut data)
+                .ok()
+                .map(Self::StrandedTipsClaimed),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,174 @@
+//! This module contains functions that build instructions to interact with the tip-payment program.
+//!
+//! Instructions that pay out the tips need the current [Config], which determines the old
+//! receivers, the number of tip payment accounts and the block builder recipients to pass.
+use anchor_lang::{
+    prelude::Pubkey,
+    solana_program::instruction::{AccountMeta, Instruction},
+    system_program, InstructionData, ToAccountMetas,
+};
+use jito_tip_payment::Config;
+
+use crate::{
+    derive_config_account_address, derive_settlement_ledger_account_address,
+    derive_stranded_tips_account_address, derive_tip_payment_account_address,
+};
+
+pub struct InitializeArgs {
+    /// Number of tip payment accounts to create.
+    pub num_tip_accounts: usize,
+}
+pub struct InitializeAccounts {
+    pub payer: Pubkey,
+}
+pub fn initialize_ix(
+    program_id: Pubkey,
+    args: InitializeArgs,
+    accounts: InitializeAccounts,
+) -> Instruction {
+    let InitializeArgs { num_tip_accounts } = args;
+
+    let InitializeAccounts { payer } = accounts;
+
+    let mut account_metas = jito_tip_payment::accounts::Initialize {
+        config: derive_config_account_address(&program_id).0,
+        system_program: system_program::ID,
+        payer,
+    }
+    .to_account_metas(None);
+    account_metas.extend(tip_payment_account_metas(&program_id, num_tip_accounts));
+
+    Instruction {
+        program_id,
+        data: jito_tip_payment::instruction::Initialize {}.data(),
+        accounts: account_metas,
+    }
+}
+
+pub struct ChangeTipReceiverArgs;
+pub struct ChangeTipReceiverAccounts {
+    pub new_tip_receiver: Pubkey,
+    pub signer: Pubkey,
+}
+pub fn change_tip_receiver_ix(
+    program_id: Pubkey,
+    config: &Config,
+    _args: ChangeTipReceiverArgs,
+    accounts: ChangeTipReceiverAccounts,
+) -> Instruction {
+    let ChangeTipReceiverAccounts {
+        new_tip_receiver,
+        signer,
+    } = accounts;
+
+    let mut account_metas = jito_tip_payment::accounts::ChangeTipReceiver {
+        config: derive_config_account_address(&program_id).0,
+        old_tip_receiver: config.tip_receiver,
+        new_tip_receiver,
+        block_builder: config.block_builder,
+        settlement_ledger: derive_settlement_ledger_account_address(&program_id).0,
+        tip_receiver_stranded_tips: derive_stranded_tips_account_address(
+            &program_id,
+            &config.tip_receiver,
+        )
+        .0,
+        block_builder_stranded_tips: derive_stranded_tips_account_address(
+            &program_id,
+            &config.block_builder,
+        )
+        .0,
+        signer,
+    }
+    .to_account_metas(None);
+    account_metas.extend(payment_account_metas(&program_id, config));
+
+    Instruction {
+        program_id,
+        data: jito_tip_payment::instruction::ChangeTipReceiver {}.data(),
+        accounts: account_metas,
+    }
+}
+
+pub struct ChangeBlockBuilderBpsArgs {
+    pub block_builder_commission_bps: u64,
+}
+pub struct ChangeBlockBuilderAccounts {
+    pub new_block_builder: Pubkey,
+    pub signer: Pubkey,
+}
+pub fn change_block_builder_bps_ix(
+    program_id: Pubkey,
+    config: &Config,
+    args: ChangeBlockBuilderBpsArgs,
+    accounts: ChangeBlockBuilderAccounts,
+) -> Instruction {
+    let ChangeBlockBuilderBpsArgs {
+        block_builder_commission_bps,
+    } = args;
+
+    let ChangeBlockBuilderAccounts {
+        new_block_builder,
+        signer,
+    } = accounts;
+
+    let mut account_metas = jito_tip_payment::accounts::ChangeBlockBuilder {
+        config: derive_config_account_address(&program_id).0,
+        tip_receiver: config.tip_receiver,
+        old_block_builder: config.block_builder,
+        new_block_builder,
+        settlement_ledger: derive_settlement_ledger_account_address(&program_id).0,
+        tip_receiver_stranded_tips: derive_stranded_tips_account_address(
+            &program_id,
+            &config.tip_receiver,
+        )
+        .0,
+        block_builder_stranded_tips: derive_stranded_tips_account_address(
+            &program_id,
+            &config.block_builder,
+        )
+        .0,
+        signer,
+    }
+    .to_account_metas(None);
+    account_metas.extend(payment_account_metas(&program_id, config));
+
+    Instruction {
+        program_id,
+        data: jito_tip_payment::instruction::ChangeBlockBuilderBps {
+            block_builder_commission_bps,
+        }
+        .data(),
+        accounts: account_metas,
+    }
+}
+
+/// The tip payment accounts in index order, followed by the block builder recipients and then
+/// their stranded tips accounts.
+fn payment_account_metas(program_id: &Pubkey, config: &Config) -> Vec<AccountMeta> {
+    tip_payment_account_metas(program_id, config.num_tip_accounts as usize)
+        .chain(
+            config
+                .block_builder_recipients
+                .iter()
+                .map(|recipient| AccountMeta::new(recipient.recipient, false)),
+        )
+        .chain(config.block_builder_recipients.iter().map(|recipient| {
+            AccountMeta::new(
+                derive_stranded_tips_account_address(program_id, &recipient.recipient).0,
+                false,
+            )
+        }))
+        .collect()
+}
+
+fn tip_payment_account_metas(
+    program_id: &Pubkey,
+    num_tip_accounts: usize,
+) -> impl Iterator<Item = AccountMeta> + '_ {
+    (0..num_tip_accounts).map(|index| {
+        AccountMeta::new(
+            derive_tip_payment_account_address(program_id, index).0,
+            false,
+        )
+    })
+}
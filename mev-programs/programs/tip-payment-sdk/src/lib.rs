@@ -0,0 +1,57 @@
+pub mod event;
+pub mod instruction;
+pub mod settlement_ledger;
+pub mod tip;
+
+use anchor_lang::{prelude::Pubkey, solana_program::pubkey};
+use jito_tip_payment::{
+    tip_account_seeds, CONFIG_ACCOUNT_SEED, NUM_LEGACY_TIP_ACCOUNTS, SETTLEMENT_LEDGER_SEED,
+    STRANDED_TIPS_SEED,
+};
+
+/// The mainnet deployment's addresses, precomputed to save deriving them.
+pub mod mainnet {
+    use super::*;
+
+    pub const PROGRAM_ID: Pubkey = pubkey!("T1pyyaTNZsKv2WcRAB8oVnk93mLJw2XzjtVYqCsaHqt");
+
+    pub const CONFIG_ACCOUNT: Pubkey = pubkey!("HgzT81VF1xZ3FT9Eq1pHhea7Wcfq2bv4tWTP3VvJ8Y9D");
+
+    /// The tip payment accounts in index order.
+    pub const TIP_ACCOUNTS: [Pubkey; NUM_LEGACY_TIP_ACCOUNTS] = [
+        pubkey!("96gYZGLnJYVFmbjzopPSU6QiEV5fGqZNyN9nmNhvrZU5"),
+        pubkey!("HFqU5x63VTqvQss8hp11i4wVV8bD44PvwucfZ2bU7gRe"),
+        pubkey!("Cw8CFyM9FkoMi7K7Crf6HNQqf4uEMzpKw6QNghXLvLkY"),
+        pubkey!("ADaUMid9yfUytqMBgopwjb2DTLSokTSzL1zt6iGPaS49"),
+        pubkey!("DfXygSm4jCyNCybVYYK6DwvWqjKee8pbDmJGcLWNDXjh"),
+        pubkey!("ADuUkR4vqLUMWXxW9gh6D6L8pMSawimctcNZ5pGwDcEt"),
+        pubkey!("DttWaMuVvTiduZRnguLF7jNxTgiMBZ1hyAumKUiL2KRL"),
+        pubkey!("3AVi9Tg9Uo68tJfuvoKvqKNWKkC5wPdSSdeBnizKZ6jT"),
+    ];
+}
+
+pub fn derive_config_account_address(tip_payment_program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CONFIG_ACCOUNT_SEED], tip_payment_program_id)
+}
+
+pub fn derive_settlement_ledger_account_address(tip_payment_program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SETTLEMENT_LEDGER_SEED], tip_payment_program_id)
+}
+
+/// Derives the tip payment account at `index`, see [tip_account_seeds].
+pub fn derive_tip_payment_account_address(
+    tip_payment_program_id: &Pubkey,
+    index: usize,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&tip_account_seeds(index), tip_payment_program_id)
+}
+
+pub fn derive_stranded_tips_account_address(
+    tip_payment_program_id: &Pubkey,
+    recipient: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[STRANDED_TIPS_SEED, recipient.as_ref()],
+        tip_payment_program_id,
+    )
+}
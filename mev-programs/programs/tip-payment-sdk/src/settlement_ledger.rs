@@ -0,0 +1,36 @@
+//! Decodes the tip payment program's settlement ledger.
+use anchor_lang::{error::ErrorCode, Discriminator, Result};
+use jito_tip_payment::{Settlement, SettlementLedger, SETTLEMENT_LEDGER_CAPACITY};
+
+/// Decodes the data of the [SettlementLedger] account, returning the recorded settlements from
+/// oldest to newest.
+pub fn decode_settlement_ledger(data: &[u8]) -> Result<Vec<Settlement>> {
+    if !data.starts_with(SettlementLedger::DISCRIMINATOR) {
+        return Err(ErrorCode::AccountDiscriminatorMismatch.into());
+    }
+    let settlement_ledger: SettlementLedger = data
+        .get(SettlementLedger::DISCRIMINATOR.len()..SettlementLedger::SIZE)
+        .and_then(|data| bytemuck::try_pod_read_unaligned(data).ok())
+        .ok_or(ErrorCode::AccountDidNotDeserialize)?;
+
+    Ok(ordered_settlements(&settlement_ledger))
+}
+
+/// Unrolls the ring buffer, the oldest settlement is the next one to be overwritten once the
+/// ledger has wrapped around.
+pub fn ordered_settlements(settlement_ledger: &SettlementLedger) -> Vec<Settlement> {
+    let num_settlements = settlement_ledger.num_settlements;
+    let settlements = &settlement_ledger.settlements;
+    if num_settlements <= SETTLEMENT_LEDGER_CAPACITY as u64 {
+        return settlements[..num_settlements as usize].to_vec();
+    }
+
+    let oldest = num_settlements
+        .checked_rem(SETTLEMENT_LEDGER_CAPACITY as u64)
+        .unwrap_or_default() as usize;
+    settlements[oldest..]
+        .iter()
+        .chain(&settlements[..oldest])
+        .copied()
+        .collect()
+}
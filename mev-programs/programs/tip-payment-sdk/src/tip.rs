@@ -0,0 +1,69 @@
+//! Helpers for tipping, i.e. transferring lamports to one of the tip payment accounts.
+//!
+//! Every tip in a block is written to the tip account it targets, so spreading tips across all of
+//! them lets transactions that tip land without contending for the same account.
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use anchor_lang::{
+    prelude::Pubkey,
+    solana_program::{instruction::Instruction, system_instruction},
+};
+use rand::Rng;
+
+use crate::mainnet;
+
+/// How [TipAccountSelector] picks the next tip account.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TipAccountSelection {
+    /// Pick a tip account uniformly at random.
+    #[default]
+    Random,
+    /// Cycle through the tip accounts in order.
+    RoundRobin,
+}
+
+/// Picks which tip account each tip is sent to.
+#[derive(Debug)]
+pub struct TipAccountSelector {
+    tip_accounts: Vec<Pubkey>,
+    selection: TipAccountSelection,
+    next: AtomicUsize,
+}
+
+impl TipAccountSelector {
+    /// Panics if `tip_accounts` is empty.
+    pub fn new(tip_accounts: Vec<Pubkey>, selection: TipAccountSelection) -> Self {
+        assert!(!tip_accounts.is_empty(), "no tip accounts to select from");
+        Self {
+            tip_accounts,
+            selection,
+            next: AtomicUsize::new(0),
+        }
+    }
+
+    /// Selects from the mainnet tip accounts.
+    pub fn mainnet(selection: TipAccountSelection) -> Self {
+        Self::new(mainnet::TIP_ACCOUNTS.to_vec(), selection)
+    }
+
+    pub fn tip_accounts(&self) -> &[Pubkey] {
+        &self.tip_accounts
+    }
+
+    pub fn next_tip_account(&self) -> Pubkey {
+        let index = match self.selection {
+            TipAccountSelection::Random => rand::thread_rng().gen_range(0..self.tip_accounts.len()),
+            TipAccountSelection::RoundRobin => self
+                .next
+                .fetch_add(1, Ordering::Relaxed)
+                .checked_rem(self.tip_accounts.len())
+                .unwrap(),
+        };
+        self.tip_accounts[index]
+    }
+
+    /// Transfers `lamports` from `from` to the next tip account.
+    pub fn tip_ix(&self, from: &Pubkey, lamports: u64) -> Instruction {
+        system_instruction::transfer(from, &self.next_tip_account(), lamports)
+    }
+}
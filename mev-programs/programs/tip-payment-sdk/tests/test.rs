@@ -0,0 +1,284 @@
+use anchor_lang::{
+    prelude::Pubkey,
+    solana_program::{instruction::AccountMeta, system_instruction},
+    system_program, Discriminator, InstructionData,
+};
+use jito_tip_payment::{
+    BlockBuilderRecipient, Config, Settlement, SettlementLedger, SETTLEMENT_LEDGER_CAPACITY,
+};
+use jito_tip_payment_sdk::{
+    derive_config_account_address, derive_settlement_ledger_account_address,
+    derive_stranded_tips_account_address, derive_tip_payment_account_address,
+    instruction::{
+        change_block_builder_bps_ix, change_tip_receiver_ix, initialize_ix,
+        ChangeBlockBuilderAccounts, ChangeBlockBuilderBpsArgs, ChangeTipReceiverAccounts,
+        ChangeTipReceiverArgs, InitializeAccounts, InitializeArgs,
+    },
+    mainnet,
+    settlement_ledger::decode_settlement_ledger,
+    tip::{TipAccountSelection, TipAccountSelector},
+};
+
+fn settlement(slot: u64) -> Settlement {
+    Settlement {
+        slot,
+        tip_receiver: Pubkey::new_unique(),
+        block_builder: Pubkey::new_unique(),
+        total_tips: slot * 1_000,
+        tip_receiver_amount: slot * 900,
+        block_builder_amount: slot * 100,
+    }
+}
+
+/// Records a settlement per slot in `slots` the way the program does, returning the account data.
+fn settlement_ledger_data(slots: impl IntoIterator<Item = u64>) -> (Vec<u8>, Vec<Settlement>) {
+    let mut settlement_ledger: SettlementLedger = bytemuck::Zeroable::zeroed();
+    let mut recorded = Vec::new();
+    for slot in slots {
+        let settlement = settlement(slot);
+        settlement_ledger.settlements
+            [settlement_ledger.num_settlements as usize % SETTLEMENT_LEDGER_CAPACITY] = settlement;
+        settlement_ledger.num_settlements += 1;
+        recorded.push(settlement);
+    }
+
+    let mut data = SettlementLedger::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(bytemuck::bytes_of(&settlement_ledger));
+    (data, recorded)
+}
+
+#[test]
+fn test_decode_empty_settlement_ledger() {
+    let (data, _) = settlement_ledger_data([]);
+    assert!(decode_settlement_ledger(&data).unwrap().is_empty());
+}
+
+#[test]
+fn test_decode_partly_filled_settlement_ledger() {
+    let (data, recorded) = settlement_ledger_data(1..=3);
+    assert_eq!(decode_settlement_ledger(&data).unwrap(), recorded);
+}
+
+#[test]
+fn test_decode_full_settlement_ledger() {
+    let (data, recorded) = settlement_ledger_data(1..=SETTLEMENT_LEDGER_CAPACITY as u64);
+    assert_eq!(decode_settlement_ledger(&data).unwrap(), recorded);
+}
+
+#[test]
+fn test_decode_wrapped_settlement_ledger() {
+    let num_settlements = SETTLEMENT_LEDGER_CAPACITY as u64 * 2 + 5;
+    let (data, recorded) = settlement_ledger_data(1..=num_settlements);
+
+    let settlements = decode_settlement_ledger(&data).unwrap();
+    assert_eq!(
+        settlements,
+        recorded[recorded.len() - SETTLEMENT_LEDGER_CAPACITY..]
+    );
+    assert_eq!(settlements.last().unwrap().slot, num_settlements);
+}
+
+#[test]
+fn test_decode_invalid_settlement_ledger_fails() {
+    let (mut data, _) = settlement_ledger_data(1..=3);
+    assert!(decode_settlement_ledger(&data[..data.len() - 1]).is_err());
+
+    data[0] ^= 1;
+    assert!(decode_settlement_ledger(&data).is_err());
+}
+
+#[test]
+fn test_mainnet_addresses_match_derived() {
+    assert_eq!(
+        mainnet::CONFIG_ACCOUNT,
+        derive_config_account_address(&mainnet::PROGRAM_ID).0
+    );
+    for (index, tip_account) in mainnet::TIP_ACCOUNTS.iter().enumerate() {
+        assert_eq!(
+            *tip_account,
+            derive_tip_payment_account_address(&mainnet::PROGRAM_ID, index).0
+        );
+    }
+}
+
+#[test]
+fn test_round_robin_tip_account_selection() {
+    let tip_accounts: Vec<_> = (0..3).map(|_| Pubkey::new_unique()).collect();
+    let selector = TipAccountSelector::new(tip_accounts.clone(), TipAccountSelection::RoundRobin);
+
+    let selected: Vec<_> = (0..7).map(|_| selector.next_tip_account()).collect();
+    assert_eq!(
+        selected,
+        [0, 1, 2, 0, 1, 2, 0].map(|index| tip_accounts[index])
+    );
+
+    let from = Pubkey::new_unique();
+    assert_eq!(
+        selector.tip_ix(&from, 1_000),
+        system_instruction::transfer(&from, &tip_accounts[1], 1_000)
+    );
+}
+
+#[test]
+fn test_random_tip_account_selection() {
+    let selector = TipAccountSelector::mainnet(TipAccountSelection::Random);
+    assert_eq!(selector.tip_accounts(), mainnet::TIP_ACCOUNTS);
+    for _ in 0..100 {
+        assert!(mainnet::TIP_ACCOUNTS.contains(&selector.next_tip_account()));
+    }
+}
+
+#[test]
+#[should_panic(expected = "no tip accounts to select from")]
+fn test_tip_account_selector_without_tip_accounts_panics() {
+    TipAccountSelector::new(vec![], TipAccountSelection::RoundRobin);
+}
+
+fn config_with_recipients() -> Config {
+    Config {
+        tip_receiver: Pubkey::new_unique(),
+        block_builder: Pubkey::new_unique(),
+        num_tip_accounts: 3,
+        block_builder_recipients: (1..=2)
+            .map(|weight| BlockBuilderRecipient {
+                recipient: Pubkey::new_unique(),
+                weight,
+            })
+            .collect(),
+        ..Config::default()
+    }
+}
+
+/// The tip payment accounts, block builder recipients and their stranded tips accounts the
+/// payout instructions pass after their named accounts.
+fn payment_account_metas(program_id: &Pubkey, config: &Config) -> Vec<AccountMeta> {
+    let recipients = || config.block_builder_recipients.iter().map(|r| r.recipient);
+    (0..config.num_tip_accounts as usize)
+        .map(|index| derive_tip_payment_account_address(program_id, index).0)
+        .chain(recipients())
+        .chain(
+            recipients()
+                .map(|recipient| derive_stranded_tips_account_address(program_id, &recipient).0),
+        )
+        .map(|pubkey| AccountMeta::new(pubkey, false))
+        .collect()
+}
+
+#[test]
+fn test_initialize_ix() {
+    let program_id = Pubkey::new_unique();
+    let payer = Pubkey::new_unique();
+    let ix = initialize_ix(
+        program_id,
+        InitializeArgs {
+            num_tip_accounts: 2,
+        },
+        InitializeAccounts { payer },
+    );
+
+    assert_eq!(ix.program_id, program_id);
+    assert_eq!(ix.data, jito_tip_payment::instruction::Initialize {}.data());
+    assert_eq!(
+        ix.accounts,
+        vec![
+            AccountMeta::new(derive_config_account_address(&program_id).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(payer, true),
+            AccountMeta::new(derive_tip_payment_account_address(&program_id, 0).0, false),
+            AccountMeta::new(derive_tip_payment_account_address(&program_id, 1).0, false),
+        ]
+    );
+}
+
+#[test]
+fn test_change_tip_receiver_ix() {
+    let program_id = Pubkey::new_unique();
+    let config = config_with_recipients();
+    let new_tip_receiver = Pubkey::new_unique();
+    let signer = Pubkey::new_unique();
+    let ix = change_tip_receiver_ix(
+        program_id,
+        &config,
+        ChangeTipReceiverArgs,
+        ChangeTipReceiverAccounts {
+            new_tip_receiver,
+            signer,
+        },
+    );
+
+    assert_eq!(ix.program_id, program_id);
+    assert_eq!(
+        ix.data,
+        jito_tip_payment::instruction::ChangeTipReceiver {}.data()
+    );
+    let mut expected_accounts = vec![
+        AccountMeta::new(derive_config_account_address(&program_id).0, false),
+        AccountMeta::new(config.tip_receiver, false),
+        AccountMeta::new(new_tip_receiver, false),
+        AccountMeta::new(config.block_builder, false),
+        AccountMeta::new(
+            derive_settlement_ledger_account_address(&program_id).0,
+            false,
+        ),
+        AccountMeta::new(
+            derive_stranded_tips_account_address(&program_id, &config.tip_receiver).0,
+            false,
+        ),
+        AccountMeta::new(
+            derive_stranded_tips_account_address(&program_id, &config.block_builder).0,
+            false,
+        ),
+        AccountMeta::new(signer, true),
+    ];
+    expected_accounts.extend(payment_account_metas(&program_id, &config));
+    assert_eq!(ix.accounts, expected_accounts);
+}
+
+#[test]
+fn test_change_block_builder_bps_ix() {
+    let program_id = Pubkey::new_unique();
+    let config = config_with_recipients();
+    let new_block_builder = Pubkey::new_unique();
+    let signer = Pubkey::new_unique();
+    let ix = change_block_builder_bps_ix(
+        program_id,
+        &config,
+        ChangeBlockBuilderBpsArgs {
+            block_builder_commission_bps: 250,
+        },
+        ChangeBlockBuilderAccounts {
+            new_block_builder,
+            signer,
+        },
+    );
+
+    assert_eq!(ix.program_id, program_id);
+    assert_eq!(
+        ix.data,
+        jito_tip_payment::instruction::ChangeBlockBuilderBps {
+            block_builder_commission_bps: 250,
+        }
+        .data()
+    );
+    let mut expected_accounts = vec![
+        AccountMeta::new(derive_config_account_address(&program_id).0, false),
+        AccountMeta::new(config.tip_receiver, false),
+        AccountMeta::new(config.block_builder, false),
+        AccountMeta::new(new_block_builder, false),
+        AccountMeta::new(
+            derive_settlement_ledger_account_address(&program_id).0,
+            false,
+        ),
+        AccountMeta::new(
+            derive_stranded_tips_account_address(&program_id, &config.tip_receiver).0,
+            false,
+        ),
+        AccountMeta::new(
+            derive_stranded_tips_account_address(&program_id, &config.block_builder).0,
+            false,
+        ),
+        AccountMeta::new(signer, true),
+    ];
+    expected_accounts.extend(payment_account_metas(&program_id, &config));
+    assert_eq!(ix.accounts, expected_accounts);
+}
@@ -31,6 +31,9 @@ declare_id!("T1pyyaTNZsKv2WcRAB8oVnk93mLJw2XzjtVYqCsaHqt");
 /// otherwise the tx would fail since the accounts would have
 /// already been initialized on subsequent calls.
 pub const CONFIG_ACCOUNT_SEED: &[u8] = b"CONFIG_ACCOUNT";
+pub const SETTLEMENT_LEDGER_SEED: &[u8] = b"SETTLEMENT_LEDGER";
+/// Seed prefix for [StrandedTips] accounts, derived as `[STRANDED_TIPS_SEED, recipient]`.
+pub const STRANDED_TIPS_SEED: &[u8] = b"STRANDED_TIPS";
 pub const TIP_ACCOUNT_SEED_0: &[u8] = b"TIP_ACCOUNT_0";
 pub const TIP_ACCOUNT_SEED_1: &[u8] = b"TIP_ACCOUNT_1";
 pub const TIP_ACCOUNT_SEED_2: &[u8] = b"TIP_ACCOUNT_2";
@@ -40,24 +43,86 @@ pub const TIP_ACCOUNT_SEED_5: &[u8] = b"TIP_ACCOUNT_5";
 pub const TIP_ACCOUNT_SEED_6: &[u8] = b"TIP_ACCOUNT_6";
 pub const TIP_ACCOUNT_SEED_7: &[u8] = b"TIP_ACCOUNT_7";
 
+/// Seed prefix for tip accounts past the original eight, derived as `[TIP_ACCOUNT_SEED_V2, &[index]]`.
+pub const TIP_ACCOUNT_SEED_V2: &[u8] = b"TIP_ACCOUNT_V2";
+
+/// The original tip accounts keep their singleton seeds so existing integrations continue to work.
+pub const NUM_LEGACY_TIP_ACCOUNTS: usize = 8;
+pub const LEGACY_TIP_ACCOUNT_SEEDS: [&[u8]; NUM_LEGACY_TIP_ACCOUNTS] = [
+    TIP_ACCOUNT_SEED_0,
+    TIP_ACCOUNT_SEED_1,
+    TIP_ACCOUNT_SEED_2,
+    TIP_ACCOUNT_SEED_3,
+    TIP_ACCOUNT_SEED_4,
+    TIP_ACCOUNT_SEED_5,
+    TIP_ACCOUNT_SEED_6,
+    TIP_ACCOUNT_SEED_7,
+];
+
+/// Upper bound on the number of tip accounts, all of which must fit in a single
+/// `change_tip_receiver` or `change_block_builder` transaction.
+pub const MAX_TIP_ACCOUNTS: usize = 16;
+
+/// Upper bound on the number of entries in [Config::block_builder_allow_list].
+pub const MAX_ALLOWED_BLOCK_BUILDERS: usize = 16;
+
+/// Upper bound on the number of entries in [Config::block_builder_recipients].
+pub const MAX_BLOCK_BUILDER_RECIPIENTS: usize = 4;
+
+/// Number of settlements kept in the [SettlementLedger] before the oldest is overwritten.
+pub const SETTLEMENT_LEDGER_CAPACITY: usize = 100;
+
+/// Backing storage for the single byte index seed of each tip account.
+static TIP_ACCOUNT_INDICES: [u8; MAX_TIP_ACCOUNTS] = {
+    let mut indices = [0u8; MAX_TIP_ACCOUNTS];
+    let mut i = 0;
+    while i < MAX_TIP_ACCOUNTS {
+        indices[i] = i as u8;
+        i += 1;
+    }
+    indices
+};
+
+/// Returns the seeds (excluding the bump) of the tip account at `index`.
+///
+/// Legacy accounts pad their singleton seed with an empty seed, which doesn't change the derived address.
+pub fn tip_account_seeds(index: usize) -> [&'static [u8]; 2] {
+    match LEGACY_TIP_ACCOUNT_SEEDS.get(index) {
+        Some(seed) => [seed, &[]],
+        None => [
+            TIP_ACCOUNT_SEED_V2,
+            std::slice::from_ref(&TIP_ACCOUNT_INDICES[index]),
+        ],
+    }
+}
+
 pub const HEADER: usize = 8;
 
+/// Block builder commission is expressed in basis points of the tips.
+pub const MAX_BPS: u64 = 10_000;
+
+/// Current [Config::version], bumped whenever `migrate_config` needs to convert existing fields.
+pub const CONFIG_VERSION: u8 = 1;
+
 struct Fees {
     block_builder_fee_lamports: u64,
     tip_receiver_fee_lamports: u64,
 }
 
 impl Fees {
+    /// The block builder fee is rounded down, so any remainder goes to the tip receiver.
     #[inline(always)]
     fn calculate(
         total_tips: u64,
-        block_builder_commission_pct: u64,
+        block_builder_commission_bps: u64,
     ) -> result::Result<Self, TipPaymentError> {
-        let block_builder_fee_lamports = total_tips
-            .checked_mul(block_builder_commission_pct)
+        let block_builder_fee_lamports = (total_tips as u128)
+            .checked_mul(block_builder_commission_bps as u128)
             .ok_or(TipPaymentError::ArithmeticError)?
-            .checked_div(100)
+            .checked_div(MAX_BPS as u128)
             .ok_or(TipPaymentError::ArithmeticError)?;
+        let block_builder_fee_lamports = u64::try_from(block_builder_fee_lamports)
+            .map_err(|_| TipPaymentError::ArithmeticError)?;
 
         let tip_receiver_fee_lamports = total_tips
             .checked_sub(block_builder_fee_lamports)
@@ -74,87 +139,197 @@ impl Fees {
 pub mod jito_tip_payment {
     use super::*;
 
-    pub fn initialize(ctx: Context<Initialize>, _bumps: InitBumps) -> Result<()> {
+    /// Creates the [Config] and an initial set of tip payment accounts, passed in index order through
+    /// the remaining accounts.
+    pub fn initialize<'info>(ctx: Context<'_, '_, '_, 'info, Initialize<'info>>) -> Result<()> {
         let cfg = &mut ctx.accounts.config;
         cfg.tip_receiver = ctx.accounts.payer.key();
         cfg.block_builder = ctx.accounts.payer.key();
-        let mut bumps = InitBumps::default();
-        bumps.config = ctx.bumps.config;
+        cfg.bumps.config = ctx.bumps.config;
+        cfg.block_builder_commission_bps = 0;
+        cfg.version = CONFIG_VERSION;
 
-        let rent = Rent::get()?;
-        bumps.tip_payment_account_0 = TipPaymentAccount::initialize(
-            TIP_ACCOUNT_SEED_0,
-            ctx.program_id,
-            &ctx.accounts.tip_payment_account_0,
-            &ctx.accounts.payer,
-            &ctx.accounts.system_program,
-            &rent,
-        )?;
-        bumps.tip_payment_account_1 = TipPaymentAccount::initialize(
-            TIP_ACCOUNT_SEED_1,
-            ctx.program_id,
-            &ctx.accounts.tip_payment_account_1,
-            &ctx.accounts.payer,
-            &ctx.accounts.system_program,
-            &rent,
-        )?;
-        bumps.tip_payment_account_2 = TipPaymentAccount::initialize(
-            TIP_ACCOUNT_SEED_2,
-            ctx.program_id,
-            &ctx.accounts.tip_payment_account_2,
-            &ctx.accounts.payer,
-            &ctx.accounts.system_program,
-            &rent,
-        )?;
-        bumps.tip_payment_account_3 = TipPaymentAccount::initialize(
-            TIP_ACCOUNT_SEED_3,
-            ctx.program_id,
-            &ctx.accounts.tip_payment_account_3,
-            &ctx.accounts.payer,
-            &ctx.accounts.system_program,
-            &rent,
-        )?;
-        bumps.tip_payment_account_4 = TipPaymentAccount::initialize(
-            TIP_ACCOUNT_SEED_4,
-            ctx.program_id,
-            &ctx.accounts.tip_payment_account_4,
-            &ctx.accounts.payer,
-            &ctx.accounts.system_program,
-            &rent,
-        )?;
-        bumps.tip_payment_account_5 = TipPaymentAccount::initialize(
-            TIP_ACCOUNT_SEED_5,
-            ctx.program_id,
-            &ctx.accounts.tip_payment_account_5,
-            &ctx.accounts.payer,
-            &ctx.accounts.system_program,
-            &rent,
-        )?;
-        bumps.tip_payment_account_6 = TipPaymentAccount::initialize(
-            TIP_ACCOUNT_SEED_6,
+        require!(
+            !ctx.remaining_accounts.is_empty(),
+            TipPaymentError::InvalidTipAccounts
+        );
+        cfg.add_tip_accounts(
             ctx.program_id,
-            &ctx.accounts.tip_payment_account_6,
+            ctx.remaining_accounts,
             &ctx.accounts.payer,
             &ctx.accounts.system_program,
-            &rent,
-        )?;
-        bumps.tip_payment_account_7 = TipPaymentAccount::initialize(
-            TIP_ACCOUNT_SEED_7,
+        )
+    }
+
+    /// Creates the [SettlementLedger], which every instruction paying out tips records to, so it
+    /// must be created before tips are paid out.
+    pub fn initialize_settlement_ledger(ctx: Context<InitializeSettlementLedger>) -> Result<()> {
+        let mut settlement_ledger = ctx.accounts.settlement_ledger.load_init()?;
+        settlement_ledger.bump = ctx.bumps.settlement_ledger;
+        Ok(())
+    }
+
+    /// Grows the set of tip payment accounts, passed in index order through the remaining accounts.
+    /// Only the program's upgrade authority can invoke this.
+    pub fn add_tip_accounts<'info>(
+        ctx: Context<'_, '_, '_, 'info, AddTipAccounts<'info>>,
+    ) -> Result<()> {
+        require!(
+            !ctx.remaining_accounts.is_empty(),
+            TipPaymentError::InvalidTipAccounts
+        );
+        ctx.accounts.config.add_tip_accounts(
             ctx.program_id,
-            &ctx.accounts.tip_payment_account_7,
+            ctx.remaining_accounts,
             &ctx.accounts.payer,
             &ctx.accounts.system_program,
-            &rent,
-        )?;
+        )
+    }
+
+    /// Reallocates the [Config] to its current layout, initializing any new fields. Only the
+    /// program's upgrade authority can invoke this.
+    pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+        let config_info = ctx.accounts.config.to_account_info();
+        if !config_info
+            .try_borrow_data()?
+            .starts_with(Config::DISCRIMINATOR)
+        {
+            return Err(anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch.into());
+        }
+
+        if config_info.data_len() < Config::SIZE {
+            let rent = Rent::get()?;
+            let lamports_required = rent
+                .minimum_balance(Config::SIZE)
+                .saturating_sub(config_info.lamports());
+            if lamports_required > 0 {
+                anchor_lang::system_program::transfer(
+                    CpiContext::new(
+                        ctx.accounts.system_program.to_account_info(),
+                        anchor_lang::system_program::Transfer {
+                            from: ctx.accounts.payer.to_account_info(),
+                            to: config_info.clone(),
+                        },
+                    ),
+                    lamports_required,
+                )?;
+            }
+            config_info.realloc(Config::SIZE, true)?;
+        }
+
+        // fields added since the account was created are zero initialized, which for the admin
+        // settings means no admin, claiming enabled, no commission cap and no allow-list
+        let mut config = Config::try_deserialize(&mut &config_info.try_borrow_data()?[..])?;
+        // accounts created before the tip account count was tracked have the legacy set
+        if config.num_tip_accounts == 0 {
+            config.num_tip_accounts = NUM_LEGACY_TIP_ACCOUNTS as u8;
+        }
+        // prior to versioning the block builder commission was stored as a whole percentage
+        if config.version == 0 {
+            config.block_builder_commission_bps = pct_to_bps(config.block_builder_commission_bps)?;
+            config.max_block_builder_commission_bps = config
+                .max_block_builder_commission_bps
+                .map(pct_to_bps)
+                .transpose()?;
+        }
+        config.version = CONFIG_VERSION;
+        config.try_serialize(&mut &mut config_info.try_borrow_mut_data()?[..])?;
+
+        Ok(())
+    }
+
+    /// Sets the [Config] admin, or removes it when `None`. Only the program's upgrade authority
+    /// can invoke this.
+    pub fn set_admin(ctx: Context<SetAdmin>, admin: Option<Pubkey>) -> Result<()> {
+        ctx.accounts.config.admin = admin;
+        Ok(())
+    }
+
+    /// Pauses or resumes `claim_tips`. Changing the tip receiver or block builder still pays out
+    /// the tips while paused, so they always settle to whoever was receiving them when they were
+    /// tipped. Only the [Config] admin can invoke this.
+    pub fn set_claims_paused(ctx: Context<AdminUpdateConfig>, claims_paused: bool) -> Result<()> {
+        AdminUpdateConfig::auth(&ctx)?;
 
-        cfg.bumps = bumps;
-        cfg.block_builder_commission_pct = 0;
+        ctx.accounts.config.claims_paused = claims_paused;
+        Ok(())
+    }
+
+    /// Caps the block builder commission, applying to the current block builder as well as any
+    /// future ones. Only the [Config] admin can invoke this.
+    pub fn set_max_block_builder_commission_bps(
+        ctx: Context<AdminUpdateConfig>,
+        max_block_builder_commission_bps: Option<u64>,
+    ) -> Result<()> {
+        AdminUpdateConfig::auth(&ctx)?;
+
+        if let Some(max_block_builder_commission_bps) = max_block_builder_commission_bps {
+            require_gte!(
+                MAX_BPS,
+                max_block_builder_commission_bps,
+                TipPaymentError::InvalidFee
+            );
+        }
+        ctx.accounts.config.max_block_builder_commission_bps = max_block_builder_commission_bps;
+        Ok(())
+    }
+
+    /// Restricts `change_block_builder` to the given block builders, an empty list allows any.
+    /// Only the [Config] admin can invoke this.
+    pub fn set_block_builder_allow_list(
+        ctx: Context<AdminUpdateConfig>,
+        block_builder_allow_list: Vec<Pubkey>,
+    ) -> Result<()> {
+        AdminUpdateConfig::auth(&ctx)?;
 
+        require_gte!(
+            MAX_ALLOWED_BLOCK_BUILDERS,
+            block_builder_allow_list.len(),
+            TipPaymentError::MaxAllowedBlockBuildersExceeded
+        );
+        ctx.accounts.config.block_builder_allow_list = block_builder_allow_list;
         Ok(())
     }
 
+    /// Transfers the tips to the current tip receiver and block builder without changing either.
+    ///
+    /// All tip payment accounts must be passed in index order through the remaining accounts,
+    /// followed by the current [Config::block_builder_recipients] and then their [StrandedTips]
+    /// accounts.
+    pub fn claim_tips(ctx: Context<ClaimTips>) -> Result<()> {
+        require!(
+            !ctx.accounts.config.claims_paused,
+            TipPaymentError::ClaimsPaused
+        );
+
+        let rent = Rent::get()?;
+        let (tip_accounts, block_builder_recipients, block_builder_recipients_stranded_tips) = ctx
+            .accounts
+            .config
+            .check_remaining_accounts(ctx.program_id, ctx.remaining_accounts)?;
+
+        handle_payments(
+            &rent,
+            tip_accounts,
+            &ctx.accounts.tip_receiver,
+            &ctx.accounts.block_builder,
+            &ctx.accounts.config.block_builder_recipients,
+            block_builder_recipients,
+            ctx.accounts.config.effective_block_builder_commission_bps(),
+            &ctx.accounts.settlement_ledger,
+            &ctx.accounts.tip_receiver_stranded_tips,
+            &ctx.accounts.block_builder_stranded_tips,
+            block_builder_recipients_stranded_tips,
+            ctx.program_id,
+        )
+    }
+
     /// Validator should invoke this instruction before executing any transactions that contain tips.
     /// Validator should also ensure it calls it if there's a fork detected.
+    ///
+    /// All tip payment accounts must be passed in index order through the remaining accounts,
+    /// followed by the current [Config::block_builder_recipients] and then their [StrandedTips]
+    /// accounts.
     pub fn change_tip_receiver(ctx: Context<ChangeTipReceiver>) -> Result<()> {
         if is_program(&ctx.accounts.new_tip_receiver)
             || is_sysvar(&ctx.accounts.new_tip_receiver)
@@ -164,14 +339,24 @@ pub mod jito_tip_payment {
         }
 
         let rent = Rent::get()?;
-        let tip_accounts = ctx.accounts.get_tip_accounts();
+        let (tip_accounts, block_builder_recipients, block_builder_recipients_stranded_tips) = ctx
+            .accounts
+            .config
+            .check_remaining_accounts(ctx.program_id, ctx.remaining_accounts)?;
 
         handle_payments(
             &rent,
-            &tip_accounts,
+            tip_accounts,
             &ctx.accounts.old_tip_receiver,
             &ctx.accounts.block_builder,
-            ctx.accounts.config.block_builder_commission_pct,
+            &ctx.accounts.config.block_builder_recipients,
+            block_builder_recipients,
+            ctx.accounts.config.effective_block_builder_commission_bps(),
+            &ctx.accounts.settlement_ledger,
+            &ctx.accounts.tip_receiver_stranded_tips,
+            &ctx.accounts.block_builder_stranded_tips,
+            block_builder_recipients_stranded_tips,
+            ctx.program_id,
         )?;
 
         // set new funding account
@@ -181,33 +366,96 @@ pub mod jito_tip_payment {
 
     /// Changes the block builder. The block builder takes a cut on tips transferred out by
     /// this program. In order for the block builder to be changed, all previous tips must have been
-    /// drained.
+    /// drained. The commission is a whole percentage of the tips, see `change_block_builder_bps`
+    /// for finer precision.
+    ///
+    /// All tip payment accounts must be passed in index order through the remaining accounts,
+    /// followed by the current [Config::block_builder_recipients] and then their [StrandedTips]
+    /// accounts.
     pub fn change_block_builder(
         ctx: Context<ChangeBlockBuilder>,
         block_builder_commission: u64,
     ) -> Result<()> {
         require_gte!(100, block_builder_commission, TipPaymentError::InvalidFee);
-        if is_program(&ctx.accounts.new_block_builder)
-            || is_sysvar(&ctx.accounts.new_block_builder)
-            || is_config(&ctx.accounts.new_block_builder)
-        {
-            return Err(TipPaymentError::InvalidBlockBuilder.into());
-        }
+
+        ChangeBlockBuilder::change(ctx, pct_to_bps(block_builder_commission)?, vec![])
+    }
+
+    /// Same as `change_block_builder`, but the commission is in basis points of the tips.
+    pub fn change_block_builder_bps(
+        ctx: Context<ChangeBlockBuilder>,
+        block_builder_commission_bps: u64,
+    ) -> Result<()> {
+        ChangeBlockBuilder::change(ctx, block_builder_commission_bps, vec![])
+    }
+
+    /// Same as `change_block_builder_bps`, but splits the block builder commission across
+    /// `block_builder_recipients` by weight.
+    ///
+    /// Each recipient must be passed, in order, after the remaining accounts
+    /// `change_block_builder` takes, and is checked the same way as the new block builder.
+    pub fn change_block_builder_with_recipients(
+        ctx: Context<ChangeBlockBuilder>,
+        block_builder_commission_bps: u64,
+        block_builder_recipients: Vec<BlockBuilderRecipient>,
+    ) -> Result<()> {
+        require!(
+            !block_builder_recipients.is_empty()
+                && block_builder_recipients.len() <= MAX_BLOCK_BUILDER_RECIPIENTS
+                && block_builder_recipients
+                    .iter()
+                    .all(|recipient| recipient.weight > 0),
+            TipPaymentError::InvalidBlockBuilderRecipients
+        );
+
+        ChangeBlockBuilder::change(ctx, block_builder_commission_bps, block_builder_recipients)
+    }
+
+    /// Opts `recipient` into having tips it can't receive held for it, instead of being credited
+    /// back to the first tip payment account. Anyone can pay for the account. Programs can never
+    /// claim held tips, so they can't opt in.
+    pub fn initialize_stranded_tips(ctx: Context<InitializeStrandedTips>) -> Result<()> {
+        require!(
+            !is_program(&ctx.accounts.recipient),
+            TipPaymentError::InvalidStrandedTipsRecipient
+        );
+
+        let stranded_tips = &mut ctx.accounts.stranded_tips;
+        stranded_tips.recipient = ctx.accounts.recipient.key();
+        stranded_tips.bump = ctx.bumps.stranded_tips;
+        Ok(())
+    }
+
+    /// Pays out the tips held in the recipient's [StrandedTips] account, which requires the
+    /// recipient to be rent exempt once it receives them. Anyone can crank this.
+    pub fn claim_stranded_tips(ctx: Context<ClaimStrandedTips>) -> Result<()> {
+        let amount = ctx.accounts.stranded_tips.amount;
+        require_gt!(amount, 0, TipPaymentError::NoStrandedTips);
 
         let rent = Rent::get()?;
-        let tip_accounts = ctx.accounts.get_tip_accounts();
-        handle_payments(
-            &rent,
-            &tip_accounts,
-            &ctx.accounts.tip_receiver,
-            &ctx.accounts.old_block_builder,
-            // old block builder commission so new block builder can't rug the old one
-            ctx.accounts.config.block_builder_commission_pct,
-        )?;
+        let recipient = &ctx.accounts.recipient;
+        let recipient_balance = recipient
+            .lamports()
+            .checked_add(amount)
+            .ok_or(TipPaymentError::ArithmeticError)?;
+        require!(
+            rent.is_exempt(recipient_balance, recipient.data_len()) && !is_program(recipient),
+            TipPaymentError::RecipientNotRentExempt
+        );
 
-        // set new funding account
-        ctx.accounts.config.block_builder = ctx.accounts.new_block_builder.key();
-        ctx.accounts.config.block_builder_commission_pct = block_builder_commission;
+        let stranded_tips_info = ctx.accounts.stranded_tips.to_account_info();
+        let stranded_tips_balance = stranded_tips_info
+            .lamports()
+            .checked_sub(amount)
+            .ok_or(TipPaymentError::ArithmeticError)?;
+        **stranded_tips_info.try_borrow_mut_lamports()? = stranded_tips_balance;
+        **recipient.try_borrow_mut_lamports()? = recipient_balance;
+        ctx.accounts.stranded_tips.amount = 0;
+
+        emit!(StrandedTipsClaimed {
+            recipient: recipient.key(),
+            amount,
+        });
         Ok(())
     }
 }
@@ -241,12 +489,15 @@ fn is_config(account: &AccountInfo) -> bool {
 /// Assumptions:
 /// - The transfer_amount are "dangling" lamports and need to be transferred somewhere to have a balanced instruction.
 /// - The receiver needs to remain rent exempt
+/// - stranded_tips is the receiver's [StrandedTips] address, which holds the lamports in place of the tip PDA once initialized, unless the receiver is a program
 #[inline(always)]
 fn transfer_or_credit_tip_pda(
     rent: &Rent,
     receiver: &AccountInfo,
     transfer_amount: u64,
     tip_pda_fallback: &AccountInfo,
+    stranded_tips: &AccountInfo,
+    program_id: &Pubkey,
 ) -> Result<u64> {
     let balance_post_transfer = receiver
         .lamports()
@@ -263,6 +514,28 @@ fn transfer_or_credit_tip_pda(
     if can_transfer {
         **receiver.try_borrow_mut_lamports()? = balance_post_transfer;
         Ok(transfer_amount)
+    } else if transfer_amount > 0 && stranded_tips.owner == program_id && !is_program(receiver) {
+        // only initialize_stranded_tips can assign the address to this program, and programs can
+        // never claim the tips held for them
+        let mut held_tips =
+            StrandedTips::try_deserialize(&mut &stranded_tips.try_borrow_data()?[..])?;
+        held_tips.amount = held_tips
+            .amount
+            .checked_add(transfer_amount)
+            .ok_or(TipPaymentError::ArithmeticError)?;
+        held_tips.try_serialize(&mut &mut stranded_tips.try_borrow_mut_data()?[..])?;
+
+        let new_stranded_tips_balance = stranded_tips
+            .lamports()
+            .checked_add(transfer_amount)
+            .ok_or(TipPaymentError::ArithmeticError)?;
+        **stranded_tips.try_borrow_mut_lamports()? = new_stranded_tips_balance;
+        msg!(
+            "WARN: held {} lamports for {:?} to claim later",
+            transfer_amount,
+            receiver.key()
+        );
+        Ok(0)
     } else {
         // These lamports can't be left dangling
         let new_tip_pda_balance = tip_pda_fallback
@@ -276,21 +549,29 @@ fn transfer_or_credit_tip_pda(
 
 /// Handles payment of the tips to the block builder and tip receiver
 /// Assumptions:
-/// - block_builder_commission_percent is a valid number (<= 100)
+/// - block_builder_commission_bps is a valid number (<= MAX_BPS)
 #[inline(always)]
+#[allow(clippy::too_many_arguments)]
 fn handle_payments(
     rent: &Rent,
     tip_accounts: &[AccountInfo],
     tip_receiver: &AccountInfo,
     block_builder: &AccountInfo,
-    block_builder_commission_percent: u64,
+    block_builder_recipients: &[BlockBuilderRecipient],
+    block_builder_recipient_accounts: &[AccountInfo],
+    block_builder_commission_bps: u64,
+    settlement_ledger: &AccountLoader<SettlementLedger>,
+    tip_receiver_stranded_tips: &AccountInfo,
+    block_builder_stranded_tips: &AccountInfo,
+    block_builder_recipients_stranded_tips: &[AccountInfo],
+    program_id: &Pubkey,
 ) -> Result<()> {
     let total_tips = TipPaymentAccount::drain_accounts(rent, tip_accounts)?;
 
     let Fees {
         block_builder_fee_lamports,
         tip_receiver_fee_lamports,
-    } = Fees::calculate(total_tips, block_builder_commission_percent)?;
+    } = Fees::calculate(total_tips, block_builder_commission_bps)?;
 
     let amount_transferred_to_tip_receiver = if tip_receiver_fee_lamports > 0 {
         let amount_transferred_to_tip_receiver = transfer_or_credit_tip_pda(
@@ -298,6 +579,8 @@ fn handle_payments(
             tip_receiver,
             tip_receiver_fee_lamports,
             tip_accounts.first().unwrap(),
+            tip_receiver_stranded_tips,
+            program_id,
         )?;
         if amount_transferred_to_tip_receiver == 0 {
             msg!(
@@ -310,12 +593,17 @@ fn handle_payments(
         0
     };
 
-    let amount_transferred_to_block_builder = if block_builder_fee_lamports > 0 {
+    let mut recipient_amounts = Vec::with_capacity(block_builder_recipients.len());
+    let amount_transferred_to_block_builder = if block_builder_fee_lamports == 0 {
+        0
+    } else if block_builder_recipients.is_empty() {
         let amount_transferred_to_block_builder = transfer_or_credit_tip_pda(
             rent,
             block_builder,
             block_builder_fee_lamports,
             tip_accounts.first().unwrap(),
+            block_builder_stranded_tips,
+            program_id,
         )?;
         if amount_transferred_to_block_builder == 0 {
             msg!(
@@ -325,9 +613,54 @@ fn handle_payments(
         }
         amount_transferred_to_block_builder
     } else {
-        0
+        let shares =
+            BlockBuilderRecipient::split(block_builder_fee_lamports, block_builder_recipients)?;
+        let mut amount_transferred_to_block_builder: u64 = 0;
+        for ((recipient, stranded_tips), share) in block_builder_recipient_accounts
+            .iter()
+            .zip(block_builder_recipients_stranded_tips)
+            .zip(shares)
+        {
+            let amount_transferred_to_recipient = if share > 0 {
+                transfer_or_credit_tip_pda(
+                    rent,
+                    recipient,
+                    share,
+                    tip_accounts.first().unwrap(),
+                    stranded_tips,
+                    program_id,
+                )?
+            } else {
+                0
+            };
+            if share > 0 && amount_transferred_to_recipient == 0 {
+                msg!(
+                    "WARN: did not transfer block builder lamports to {:?}",
+                    recipient.key()
+                );
+            }
+            recipient_amounts.push(RecipientAmount {
+                recipient: recipient.key(),
+                amount: amount_transferred_to_recipient,
+            });
+            amount_transferred_to_block_builder = amount_transferred_to_block_builder
+                .checked_add(amount_transferred_to_recipient)
+                .ok_or(TipPaymentError::ArithmeticError)?;
+        }
+        amount_transferred_to_block_builder
     };
 
+    if total_tips > 0 {
+        settlement_ledger.load_mut()?.record(Settlement {
+            slot: Clock::get()?.slot,
+            tip_receiver: tip_receiver.key(),
+            block_builder: block_builder.key(),
+            total_tips,
+            tip_receiver_amount: amount_transferred_to_tip_receiver,
+            block_builder_amount: amount_transferred_to_block_builder,
+        })?;
+    }
+
     if amount_transferred_to_tip_receiver > 0 || amount_transferred_to_block_builder > 0 {
         let tip_receiver = if amount_transferred_to_tip_receiver > 0 {
             tip_receiver.key()
@@ -344,39 +677,96 @@ fn handle_payments(
             tip_receiver_amount: amount_transferred_to_tip_receiver,
             block_builder,
             block_builder_amount: amount_transferred_to_block_builder,
+            block_builder_recipients: recipient_amounts,
         });
     }
     Ok(())
 }
 
+#[inline(always)]
+fn pct_to_bps(pct: u64) -> Result<u64> {
+    Ok(pct
+        .checked_mul(100)
+        .ok_or(TipPaymentError::ArithmeticError)?
+        .min(MAX_BPS))
+}
+
 #[error_code]
 pub enum TipPaymentError {
     ArithmeticError,
     InvalidFee,
     InvalidTipReceiver,
     InvalidBlockBuilder,
+    InvalidTipAccounts,
+    MaxTipAccountsExceeded,
+    Unauthorized,
+    ClaimsPaused,
+    BlockBuilderNotAllowed,
+    MaxAllowedBlockBuildersExceeded,
+    InvalidBlockBuilderRecipients,
+    InvalidStrandedTipsAccount,
+    NoStrandedTips,
+    RecipientNotRentExempt,
+    InvalidStrandedTipsRecipient,
 }
 
-/// Bumps used during initialization
+/// A share of the block builder commission, see [Config::block_builder_recipients].
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BlockBuilderRecipient {
+    pub recipient: Pubkey,
+    /// Relative to the sum of all recipients' weights.
+    pub weight: u16,
+}
+
+impl BlockBuilderRecipient {
+    const SIZE: usize = 32 + 2;
+
+    /// Splits `lamports` by weight, each share is rounded down and the remainder goes to the first
+    /// recipient.
+    #[inline(always)]
+    fn split(lamports: u64, recipients: &[BlockBuilderRecipient]) -> Result<Vec<u64>> {
+        let total_weight = recipients
+            .iter()
+            .map(|recipient| recipient.weight as u128)
+            .sum::<u128>();
+
+        let mut shares = Vec::with_capacity(recipients.len());
+        for recipient in recipients {
+            let share = (lamports as u128)
+                .checked_mul(recipient.weight as u128)
+                .ok_or(TipPaymentError::ArithmeticError)?
+                .checked_div(total_weight)
+                .ok_or(TipPaymentError::ArithmeticError)?;
+            shares.push(u64::try_from(share).map_err(|_| TipPaymentError::ArithmeticError)?);
+        }
+
+        let remainder = shares
+            .iter()
+            .try_fold(lamports, |remainder, share| remainder.checked_sub(*share))
+            .ok_or(TipPaymentError::ArithmeticError)?;
+        if let Some(first_share) = shares.first_mut() {
+            *first_share = first_share
+                .checked_add(remainder)
+                .ok_or(TipPaymentError::ArithmeticError)?;
+        }
+
+        Ok(shares)
+    }
+}
+
+/// Bumps used to derive the program's PDAs
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
 pub struct InitBumps {
     pub config: u8,
-    pub tip_payment_account_0: u8,
-    pub tip_payment_account_1: u8,
-    pub tip_payment_account_2: u8,
-    pub tip_payment_account_3: u8,
-    pub tip_payment_account_4: u8,
-    pub tip_payment_account_5: u8,
-    pub tip_payment_account_6: u8,
-    pub tip_payment_account_7: u8,
+    /// Indexed by tip account index, only the first [Config::num_tip_accounts] are in use.
+    pub tip_payment_accounts: [u8; MAX_TIP_ACCOUNTS],
 }
 
 impl InitBumps {
-    const SIZE: usize = 9;
+    const SIZE: usize = 1 + MAX_TIP_ACCOUNTS;
 }
 
 #[derive(Accounts)]
-#[instruction(bumps: InitBumps)]
 pub struct Initialize<'info> {
     /// singleton account
     #[account(
@@ -388,30 +778,24 @@ pub struct Initialize<'info> {
         rent_exempt = enforce
     )]
     pub config: Account<'info, Config>,
+
+    pub system_program: Program<'info, System>,
     #[account(mut)]
-    /// CHECK: Handled in TipPaymentAccount::initialize
-    pub tip_payment_account_0: UncheckedAccount<'info>,
-    #[account(mut)]
-    /// CHECK: Handled in TipPaymentAccount::initialize
-    pub tip_payment_account_1: UncheckedAccount<'info>,
-    #[account(mut)]
-    /// CHECK: Handled in TipPaymentAccount::initialize
-    pub tip_payment_account_2: UncheckedAccount<'info>,
-    #[account(mut)]
-    /// CHECK: Handled in TipPaymentAccount::initialize
-    pub tip_payment_account_3: UncheckedAccount<'info>,
-    #[account(mut)]
-    /// CHECK: Handled in TipPaymentAccount::initialize
-    pub tip_payment_account_4: UncheckedAccount<'info>,
-    #[account(mut)]
-    /// CHECK: Handled in TipPaymentAccount::initialize
-    pub tip_payment_account_5: UncheckedAccount<'info>,
-    #[account(mut)]
-    /// CHECK: Handled in TipPaymentAccount::initialize
-    pub tip_payment_account_6: UncheckedAccount<'info>,
-    #[account(mut)]
-    /// CHECK: Handled in TipPaymentAccount::initialize
-    pub tip_payment_account_7: UncheckedAccount<'info>,
+    pub payer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeSettlementLedger<'info> {
+    /// singleton account
+    #[account(
+        init,
+        seeds = [SETTLEMENT_LEDGER_SEED],
+        bump,
+        payer = payer,
+        space = SettlementLedger::SIZE,
+        rent_exempt = enforce
+    )]
+    pub settlement_ledger: AccountLoader<'info, SettlementLedger>,
 
     pub system_program: Program<'info, System>,
     #[account(mut)]
@@ -419,7 +803,7 @@ pub struct Initialize<'info> {
 }
 
 #[derive(Accounts)]
-pub struct ClaimTips<'info> {
+pub struct AddTipAccounts<'info> {
     #[account(
         mut,
         seeds = [CONFIG_ACCOUNT_SEED],
@@ -427,62 +811,101 @@ pub struct ClaimTips<'info> {
         rent_exempt = enforce
     )]
     pub config: Account<'info, Config>,
+
     #[account(
-        mut,
-        seeds = [TIP_ACCOUNT_SEED_0],
-        bump = config.bumps.tip_payment_account_0,
-        rent_exempt = enforce
-    )]
-    pub tip_payment_account_0: Account<'info, TipPaymentAccount>,
-    #[account(
-        mut,
-        seeds = [TIP_ACCOUNT_SEED_1],
-        bump = config.bumps.tip_payment_account_1,
-        rent_exempt = enforce
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::id(),
+        constraint = program_data.upgrade_authority_address == Some(authority.key()) @ TipPaymentError::Unauthorized
     )]
-    pub tip_payment_account_1: Account<'info, TipPaymentAccount>,
+    pub program_data: Account<'info, ProgramData>,
+
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateConfig<'info> {
+    /// CHECK: deserialized in the instruction since the account may still have an older layout.
     #[account(
         mut,
-        seeds = [TIP_ACCOUNT_SEED_2],
-        bump = config.bumps.tip_payment_account_2,
-        rent_exempt = enforce
+        seeds = [CONFIG_ACCOUNT_SEED],
+        bump,
+        owner = crate::ID
     )]
-    pub tip_payment_account_2: Account<'info, TipPaymentAccount>,
+    pub config: UncheckedAccount<'info>,
+
     #[account(
-        mut,
-        seeds = [TIP_ACCOUNT_SEED_3],
-        bump = config.bumps.tip_payment_account_3,
-        rent_exempt = enforce
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::id(),
+        constraint = program_data.upgrade_authority_address == Some(authority.key()) @ TipPaymentError::Unauthorized
     )]
-    pub tip_payment_account_3: Account<'info, TipPaymentAccount>,
+    pub program_data: Account<'info, ProgramData>,
+
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetAdmin<'info> {
     #[account(
         mut,
-        seeds = [TIP_ACCOUNT_SEED_4],
-        bump = config.bumps.tip_payment_account_4,
+        seeds = [CONFIG_ACCOUNT_SEED],
+        bump = config.bumps.config,
         rent_exempt = enforce
     )]
-    pub tip_payment_account_4: Account<'info, TipPaymentAccount>,
+    pub config: Account<'info, Config>,
+
     #[account(
-        mut,
-        seeds = [TIP_ACCOUNT_SEED_5],
-        bump = config.bumps.tip_payment_account_5,
-        rent_exempt = enforce
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::id(),
+        constraint = program_data.upgrade_authority_address == Some(authority.key()) @ TipPaymentError::Unauthorized
     )]
-    pub tip_payment_account_5: Account<'info, TipPaymentAccount>,
+    pub program_data: Account<'info, ProgramData>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AdminUpdateConfig<'info> {
     #[account(
         mut,
-        seeds = [TIP_ACCOUNT_SEED_6],
-        bump = config.bumps.tip_payment_account_6,
+        seeds = [CONFIG_ACCOUNT_SEED],
+        bump = config.bumps.config,
         rent_exempt = enforce
     )]
-    pub tip_payment_account_6: Account<'info, TipPaymentAccount>,
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
+
+impl AdminUpdateConfig<'_> {
+    fn auth(ctx: &Context<AdminUpdateConfig>) -> Result<()> {
+        if ctx.accounts.config.admin != Some(ctx.accounts.admin.key()) {
+            Err(TipPaymentError::Unauthorized.into())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[derive(Accounts)]
+pub struct ClaimTips<'info> {
     #[account(
         mut,
-        seeds = [TIP_ACCOUNT_SEED_7],
-        bump = config.bumps.tip_payment_account_7,
+        seeds = [CONFIG_ACCOUNT_SEED],
+        bump = config.bumps.config,
         rent_exempt = enforce
     )]
-    pub tip_payment_account_7: Account<'info, TipPaymentAccount>,
+    pub config: Account<'info, Config>,
 
     /// CHECK: this is the account that is configured to receive tips, which is constantly rotating and
     /// can be an account with a private key to a PDA owned by some other program.
@@ -499,6 +922,34 @@ pub struct ClaimTips<'info> {
     )]
     pub block_builder: AccountInfo<'info>,
 
+    /// Records every payout, so the ledger can be reconciled against.
+    #[account(
+        mut,
+        seeds = [SETTLEMENT_LEDGER_SEED],
+        bump = settlement_ledger.load()?.bump,
+    )]
+    pub settlement_ledger: AccountLoader<'info, SettlementLedger>,
+
+    /// Holds the tip receiver's share when it can't receive it, once the tip receiver has
+    /// initialized its [StrandedTips] account.
+    /// CHECK: the seeds ensure this is the tip receiver's stranded tips account.
+    #[account(
+        mut,
+        seeds = [STRANDED_TIPS_SEED, tip_receiver.key().as_ref()],
+        bump,
+    )]
+    pub tip_receiver_stranded_tips: UncheckedAccount<'info>,
+
+    /// Holds the block builder's share when it can't receive it, once the block builder has
+    /// initialized its [StrandedTips] account.
+    /// CHECK: the seeds ensure this is the block builder's stranded tips account.
+    #[account(
+        mut,
+        seeds = [STRANDED_TIPS_SEED, block_builder.key().as_ref()],
+        bump,
+    )]
+    pub block_builder_stranded_tips: UncheckedAccount<'info>,
+
     #[account(mut)]
     pub signer: Signer<'info>,
 }
@@ -522,90 +973,38 @@ pub struct ChangeTipReceiver<'info> {
     #[account(mut, constraint = block_builder.key() == config.block_builder)]
     pub block_builder: AccountInfo<'info>,
 
+    /// Records every payout, so the ledger can be reconciled against.
     #[account(
         mut,
-        seeds = [TIP_ACCOUNT_SEED_0],
-        bump = config.bumps.tip_payment_account_0,
-        rent_exempt = enforce
-    )]
-    pub tip_payment_account_0: Account<'info, TipPaymentAccount>,
-
-    #[account(
-        mut,
-        seeds = [TIP_ACCOUNT_SEED_1],
-        bump = config.bumps.tip_payment_account_1,
-        rent_exempt = enforce
-    )]
-    pub tip_payment_account_1: Account<'info, TipPaymentAccount>,
-
-    #[account(
-        mut,
-        seeds = [TIP_ACCOUNT_SEED_2],
-        bump = config.bumps.tip_payment_account_2,
-        rent_exempt = enforce
+        seeds = [SETTLEMENT_LEDGER_SEED],
+        bump = settlement_ledger.load()?.bump,
     )]
-    pub tip_payment_account_2: Account<'info, TipPaymentAccount>,
+    pub settlement_ledger: AccountLoader<'info, SettlementLedger>,
 
+    /// Holds the tip receiver's share when it can't receive it, once the tip receiver has
+    /// initialized its [StrandedTips] account.
+    /// CHECK: the seeds ensure this is the tip receiver's stranded tips account.
     #[account(
         mut,
-        seeds = [TIP_ACCOUNT_SEED_3],
-        bump = config.bumps.tip_payment_account_3,
-        rent_exempt = enforce
-    )]
-    pub tip_payment_account_3: Account<'info, TipPaymentAccount>,
-
-    #[account(
-        mut,
-        seeds = [TIP_ACCOUNT_SEED_4],
-        bump = config.bumps.tip_payment_account_4,
-        rent_exempt = enforce
-    )]
-    pub tip_payment_account_4: Account<'info, TipPaymentAccount>,
-
-    #[account(
-        mut,
-        seeds = [TIP_ACCOUNT_SEED_5],
-        bump = config.bumps.tip_payment_account_5,
-        rent_exempt = enforce
-    )]
-    pub tip_payment_account_5: Account<'info, TipPaymentAccount>,
-
-    #[account(
-        mut,
-        seeds = [TIP_ACCOUNT_SEED_6],
-        bump = config.bumps.tip_payment_account_6,
-        rent_exempt = enforce
+        seeds = [STRANDED_TIPS_SEED, old_tip_receiver.key().as_ref()],
+        bump,
     )]
-    pub tip_payment_account_6: Account<'info, TipPaymentAccount>,
+    pub tip_receiver_stranded_tips: UncheckedAccount<'info>,
 
+    /// Holds the block builder's share when it can't receive it, once the block builder has
+    /// initialized its [StrandedTips] account.
+    /// CHECK: the seeds ensure this is the block builder's stranded tips account.
     #[account(
         mut,
-        seeds = [TIP_ACCOUNT_SEED_7],
-        bump = config.bumps.tip_payment_account_7,
-        rent_exempt = enforce
+        seeds = [STRANDED_TIPS_SEED, block_builder.key().as_ref()],
+        bump,
     )]
-    pub tip_payment_account_7: Account<'info, TipPaymentAccount>,
+    pub block_builder_stranded_tips: UncheckedAccount<'info>,
 
     #[account(mut)]
     pub signer: Signer<'info>,
 }
 
-impl<'info> ChangeTipReceiver<'info> {
-    #[inline(always)]
-    fn get_tip_accounts(&self) -> [AccountInfo<'info>; 8] {
-        [
-            self.tip_payment_account_0.to_account_info(),
-            self.tip_payment_account_1.to_account_info(),
-            self.tip_payment_account_2.to_account_info(),
-            self.tip_payment_account_3.to_account_info(),
-            self.tip_payment_account_4.to_account_info(),
-            self.tip_payment_account_5.to_account_info(),
-            self.tip_payment_account_6.to_account_info(),
-            self.tip_payment_account_7.to_account_info(),
-        ]
-    }
-}
-
 #[derive(Accounts)]
 pub struct ChangeBlockBuilder<'info> {
     #[account(mut)]
@@ -625,88 +1024,164 @@ pub struct ChangeBlockBuilder<'info> {
     #[account(mut)]
     pub new_block_builder: AccountInfo<'info>,
 
+    /// Records every payout, so the ledger can be reconciled against.
     #[account(
         mut,
-        seeds = [TIP_ACCOUNT_SEED_0],
-        bump = config.bumps.tip_payment_account_0,
-        rent_exempt = enforce
+        seeds = [SETTLEMENT_LEDGER_SEED],
+        bump = settlement_ledger.load()?.bump,
     )]
-    pub tip_payment_account_0: Account<'info, TipPaymentAccount>,
+    pub settlement_ledger: AccountLoader<'info, SettlementLedger>,
 
+    /// Holds the tip receiver's share when it can't receive it, once the tip receiver has
+    /// initialized its [StrandedTips] account.
+    /// CHECK: the seeds ensure this is the tip receiver's stranded tips account.
     #[account(
         mut,
-        seeds = [TIP_ACCOUNT_SEED_1],
-        bump = config.bumps.tip_payment_account_1,
-        rent_exempt = enforce
+        seeds = [STRANDED_TIPS_SEED, tip_receiver.key().as_ref()],
+        bump,
     )]
-    pub tip_payment_account_1: Account<'info, TipPaymentAccount>,
+    pub tip_receiver_stranded_tips: UncheckedAccount<'info>,
 
+    /// Holds the block builder's share when it can't receive it, once the block builder has
+    /// initialized its [StrandedTips] account.
+    /// CHECK: the seeds ensure this is the block builder's stranded tips account.
     #[account(
         mut,
-        seeds = [TIP_ACCOUNT_SEED_2],
-        bump = config.bumps.tip_payment_account_2,
-        rent_exempt = enforce
+        seeds = [STRANDED_TIPS_SEED, old_block_builder.key().as_ref()],
+        bump,
     )]
-    pub tip_payment_account_2: Account<'info, TipPaymentAccount>,
+    pub block_builder_stranded_tips: UncheckedAccount<'info>,
 
-    #[account(
-        mut,
-        seeds = [TIP_ACCOUNT_SEED_3],
-        bump = config.bumps.tip_payment_account_3,
-        rent_exempt = enforce
-    )]
-    pub tip_payment_account_3: Account<'info, TipPaymentAccount>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+}
 
-    #[account(
-        mut,
-        seeds = [TIP_ACCOUNT_SEED_4],
-        bump = config.bumps.tip_payment_account_4,
-        rent_exempt = enforce
-    )]
-    pub tip_payment_account_4: Account<'info, TipPaymentAccount>,
+impl ChangeBlockBuilder<'_> {
+    fn change(
+        ctx: Context<ChangeBlockBuilder>,
+        block_builder_commission_bps: u64,
+        block_builder_recipients: Vec<BlockBuilderRecipient>,
+    ) -> Result<()> {
+        require_gte!(
+            MAX_BPS,
+            block_builder_commission_bps,
+            TipPaymentError::InvalidFee
+        );
+        if let Some(max_block_builder_commission_bps) =
+            ctx.accounts.config.max_block_builder_commission_bps
+        {
+            require_gte!(
+                max_block_builder_commission_bps,
+                block_builder_commission_bps,
+                TipPaymentError::InvalidFee
+            );
+        }
+        // the new recipients follow the accounts needed to pay out the old ones
+        let (remaining_accounts, new_block_builder_recipients) = ctx.remaining_accounts.split_at(
+            ctx.remaining_accounts
+                .len()
+                .checked_sub(block_builder_recipients.len())
+                .ok_or(TipPaymentError::InvalidBlockBuilderRecipients)?,
+        );
+        for (recipient, account_info) in block_builder_recipients
+            .iter()
+            .zip(new_block_builder_recipients)
+        {
+            require_keys_eq!(
+                recipient.recipient,
+                *account_info.key,
+                TipPaymentError::InvalidBlockBuilderRecipients
+            );
+            // a demoted write lock would fail every payout to it
+            require!(
+                account_info.is_writable,
+                anchor_lang::error::ErrorCode::ConstraintMut
+            );
+        }
 
-    #[account(
-        mut,
-        seeds = [TIP_ACCOUNT_SEED_5],
-        bump = config.bumps.tip_payment_account_5,
-        rent_exempt = enforce
-    )]
-    pub tip_payment_account_5: Account<'info, TipPaymentAccount>,
+        Self::check_block_builder(&ctx.accounts.config, &ctx.accounts.new_block_builder)?;
+        for account_info in new_block_builder_recipients {
+            Self::check_block_builder(&ctx.accounts.config, account_info)?;
+        }
+
+        let rent = Rent::get()?;
+        let (
+            tip_accounts,
+            old_block_builder_recipients,
+            old_block_builder_recipients_stranded_tips,
+        ) = ctx
+            .accounts
+            .config
+            .check_remaining_accounts(ctx.program_id, remaining_accounts)?;
+
+        handle_payments(
+            &rent,
+            tip_accounts,
+            &ctx.accounts.tip_receiver,
+            &ctx.accounts.old_block_builder,
+            &ctx.accounts.config.block_builder_recipients,
+            old_block_builder_recipients,
+            // old block builder commission so new block builder can't rug the old one
+            ctx.accounts.config.effective_block_builder_commission_bps(),
+            &ctx.accounts.settlement_ledger,
+            &ctx.accounts.tip_receiver_stranded_tips,
+            &ctx.accounts.block_builder_stranded_tips,
+            old_block_builder_recipients_stranded_tips,
+            ctx.program_id,
+        )?;
+
+        // set new funding account
+        ctx.accounts.config.block_builder = ctx.accounts.new_block_builder.key();
+        ctx.accounts.config.block_builder_commission_bps = block_builder_commission_bps;
+        ctx.accounts.config.block_builder_recipients = block_builder_recipients;
+        Ok(())
+    }
+
+    /// Ensures `block_builder` is allowed to take the block builder commission.
+    fn check_block_builder(config: &Config, block_builder: &AccountInfo) -> Result<()> {
+        require!(
+            config.is_block_builder_allowed(block_builder.key),
+            TipPaymentError::BlockBuilderNotAllowed
+        );
+        if is_program(block_builder) || is_sysvar(block_builder) || is_config(block_builder) {
+            return Err(TipPaymentError::InvalidBlockBuilder.into());
+        }
+        Ok(())
+    }
+}
 
+#[derive(Accounts)]
+pub struct InitializeStrandedTips<'info> {
     #[account(
-        mut,
-        seeds = [TIP_ACCOUNT_SEED_6],
-        bump = config.bumps.tip_payment_account_6,
+        init,
+        seeds = [STRANDED_TIPS_SEED, recipient.key().as_ref()],
+        bump,
+        payer = payer,
+        space = StrandedTips::SIZE,
         rent_exempt = enforce
     )]
-    pub tip_payment_account_6: Account<'info, TipPaymentAccount>,
+    pub stranded_tips: Account<'info, StrandedTips>,
+
+    /// CHECK: any account but a program can have tips held for it
+    pub recipient: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+}
 
+#[derive(Accounts)]
+pub struct ClaimStrandedTips<'info> {
     #[account(
         mut,
-        seeds = [TIP_ACCOUNT_SEED_7],
-        bump = config.bumps.tip_payment_account_7,
-        rent_exempt = enforce
+        seeds = [STRANDED_TIPS_SEED, recipient.key().as_ref()],
+        bump = stranded_tips.bump,
     )]
-    pub tip_payment_account_7: Account<'info, TipPaymentAccount>,
+    pub stranded_tips: Account<'info, StrandedTips>,
 
+    /// CHECK: the seeds ensure this is the account the tips are held for
     #[account(mut)]
-    pub signer: Signer<'info>,
-}
-
-impl<'info> ChangeBlockBuilder<'info> {
-    #[inline(always)]
-    fn get_tip_accounts(&self) -> [AccountInfo<'info>; 8] {
-        [
-            self.tip_payment_account_0.to_account_info(),
-            self.tip_payment_account_1.to_account_info(),
-            self.tip_payment_account_2.to_account_info(),
-            self.tip_payment_account_3.to_account_info(),
-            self.tip_payment_account_4.to_account_info(),
-            self.tip_payment_account_5.to_account_info(),
-            self.tip_payment_account_6.to_account_info(),
-            self.tip_payment_account_7.to_account_info(),
-        ]
-    }
+    pub recipient: AccountInfo<'info>,
 }
 
 /// Stores program config metadata.
@@ -718,19 +1193,263 @@ pub struct Config {
 
     /// Block builder that receives a % of fees
     pub block_builder: Pubkey,
-    pub block_builder_commission_pct: u64,
+    /// Share of the tips paid to the block builder in basis points.
+    pub block_builder_commission_bps: u64,
 
     /// Bumps used to derive PDAs
     pub bumps: InitBumps,
+
+    /// Number of tip payment accounts searchers can tip, see [tip_account_seeds].
+    pub num_tip_accounts: u8,
+
+    /// Optional authority over the settings below, set by the program's upgrade authority.
+    pub admin: Option<Pubkey>,
+
+    /// When set, `claim_tips` is rejected, tips are only paid out when the tip receiver or block
+    /// builder changes.
+    pub claims_paused: bool,
+
+    /// Upper bound on [Config::block_builder_commission_bps], applied when tips are paid out.
+    pub max_block_builder_commission_bps: Option<u64>,
+
+    /// Block builders `change_block_builder` accepts, any block builder is accepted when empty.
+    pub block_builder_allow_list: Vec<Pubkey>,
+
+    /// Layout version, see [CONFIG_VERSION].
+    pub version: u8,
+
+    /// Splits the block builder commission by weight, [Config::block_builder] receives all of it
+    /// when empty.
+    pub block_builder_recipients: Vec<BlockBuilderRecipient>,
 }
 
 impl Config {
     // header, fields, and InitBumps
-    pub const SIZE: usize = 8 + 32 + 32 + 8 + InitBumps::SIZE;
+    pub const SIZE: usize = 8
+        + 32
+        + 32
+        + 8
+        + InitBumps::SIZE
+        + 1
+        + (1 + 32)
+        + 1
+        + (1 + 8)
+        + (4 + 32 * MAX_ALLOWED_BLOCK_BUILDERS)
+        + 1
+        + (4 + BlockBuilderRecipient::SIZE * MAX_BLOCK_BUILDER_RECIPIENTS);
+
+    /// The commission paid to the block builder, limited by the admin configured maximum.
+    #[inline(always)]
+    pub fn effective_block_builder_commission_bps(&self) -> u64 {
+        match self.max_block_builder_commission_bps {
+            Some(max_block_builder_commission_bps) => self
+                .block_builder_commission_bps
+                .min(max_block_builder_commission_bps),
+            None => self.block_builder_commission_bps,
+        }
+    }
+
+    pub fn is_block_builder_allowed(&self, block_builder: &Pubkey) -> bool {
+        self.block_builder_allow_list.is_empty()
+            || self.block_builder_allow_list.contains(block_builder)
+    }
+
+    /// Creates `new_tip_accounts` as the tip payment accounts following the ones already in use.
+    fn add_tip_accounts<'info>(
+        &mut self,
+        program_id: &Pubkey,
+        new_tip_accounts: &[AccountInfo<'info>],
+        payer: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+    ) -> Result<()> {
+        let first_index = self.num_tip_accounts as usize;
+        let num_tip_accounts = first_index
+            .checked_add(new_tip_accounts.len())
+            .ok_or(TipPaymentError::ArithmeticError)?;
+        require_gte!(
+            MAX_TIP_ACCOUNTS,
+            num_tip_accounts,
+            TipPaymentError::MaxTipAccountsExceeded
+        );
+
+        let rent = Rent::get()?;
+        for (index, account_info) in (first_index..).zip(new_tip_accounts) {
+            self.bumps.tip_payment_accounts[index] = TipPaymentAccount::initialize(
+                &tip_account_seeds(index),
+                program_id,
+                account_info,
+                payer,
+                system_program,
+                &rent,
+            )?;
+        }
+        self.num_tip_accounts = num_tip_accounts as u8;
+
+        Ok(())
+    }
+
+    /// Splits the remaining accounts into the tip payment accounts, block builder recipients and
+    /// the recipients' [StrandedTips] accounts.
+    ///
+    /// Ensures the tip payment accounts are all of them, in index order, so that every tip is
+    /// drained before the receivers change; that the recipients match
+    /// [Config::block_builder_recipients]; and that each is followed, in the same order, by its
+    /// stranded tips account.
+    #[inline(always)]
+    fn check_remaining_accounts<'a, 'info>(
+        &self,
+        program_id: &Pubkey,
+        remaining_accounts: &'a [AccountInfo<'info>],
+    ) -> Result<(
+        &'a [AccountInfo<'info>],
+        &'a [AccountInfo<'info>],
+        &'a [AccountInfo<'info>],
+    )> {
+        let num_remaining_accounts = self
+            .block_builder_recipients
+            .len()
+            .checked_mul(2)
+            .and_then(|num_accounts| num_accounts.checked_add(self.num_tip_accounts as usize))
+            .ok_or(TipPaymentError::ArithmeticError)?;
+        require_eq!(
+            remaining_accounts.len(),
+            num_remaining_accounts,
+            TipPaymentError::InvalidTipAccounts
+        );
+        let (tip_accounts, block_builder_recipient_accounts) =
+            remaining_accounts.split_at(self.num_tip_accounts as usize);
+        let (block_builder_recipients, block_builder_recipients_stranded_tips) =
+            block_builder_recipient_accounts.split_at(self.block_builder_recipients.len());
+
+        for (index, account_info) in tip_accounts.iter().enumerate() {
+            let [seed, index_seed] = tip_account_seeds(index);
+            let tip_pda = Pubkey::create_program_address(
+                &[seed, index_seed, &[self.bumps.tip_payment_accounts[index]]],
+                program_id,
+            )
+            .map_err(|_| TipPaymentError::InvalidTipAccounts)?;
+            require_keys_eq!(
+                tip_pda,
+                *account_info.key,
+                TipPaymentError::InvalidTipAccounts
+            );
+            require_keys_eq!(
+                *account_info.owner,
+                *program_id,
+                TipPaymentError::InvalidTipAccounts
+            );
+            require!(
+                account_info.is_writable,
+                anchor_lang::error::ErrorCode::ConstraintMut
+            );
+        }
+
+        for (recipient, account_info) in self
+            .block_builder_recipients
+            .iter()
+            .zip(block_builder_recipients)
+        {
+            require_keys_eq!(
+                recipient.recipient,
+                *account_info.key,
+                TipPaymentError::InvalidBlockBuilderRecipients
+            );
+            require!(
+                account_info.is_writable,
+                anchor_lang::error::ErrorCode::ConstraintMut
+            );
+        }
+
+        for (recipient, account_info) in self
+            .block_builder_recipients
+            .iter()
+            .zip(block_builder_recipients_stranded_tips)
+        {
+            let (stranded_tips, _) = Pubkey::find_program_address(
+                &[STRANDED_TIPS_SEED, recipient.recipient.as_ref()],
+                program_id,
+            );
+            require_keys_eq!(
+                stranded_tips,
+                *account_info.key,
+                TipPaymentError::InvalidStrandedTipsAccount
+            );
+            require!(
+                account_info.is_writable,
+                anchor_lang::error::ErrorCode::ConstraintMut
+            );
+        }
+
+        Ok((
+            tip_accounts,
+            block_builder_recipients,
+            block_builder_recipients_stranded_tips,
+        ))
+    }
+}
+
+/// Ring buffer of the most recent tip settlements, so payouts can be reconciled without
+/// scraping transaction logs.
+#[account(zero_copy)]
+pub struct SettlementLedger {
+    /// Total number of settlements ever recorded, the next one is written at this index modulo
+    /// [SETTLEMENT_LEDGER_CAPACITY].
+    pub num_settlements: u64,
+    pub bump: u8,
+    pub _padding: [u8; 7],
+    pub settlements: [Settlement; SETTLEMENT_LEDGER_CAPACITY],
+}
+
+impl SettlementLedger {
+    pub const SIZE: usize = 8 + std::mem::size_of::<SettlementLedger>();
+
+    #[inline(always)]
+    fn record(&mut self, settlement: Settlement) -> Result<()> {
+        let index = self
+            .num_settlements
+            .checked_rem(SETTLEMENT_LEDGER_CAPACITY as u64)
+            .ok_or(TipPaymentError::ArithmeticError)?;
+        self.settlements[index as usize] = settlement;
+        self.num_settlements = self
+            .num_settlements
+            .checked_add(1)
+            .ok_or(TipPaymentError::ArithmeticError)?;
+        Ok(())
+    }
+}
+
+/// A single payout of the tip payment accounts.
+#[zero_copy]
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Settlement {
+    pub slot: u64,
+    pub tip_receiver: Pubkey,
+    pub block_builder: Pubkey,
+    /// Tips drained from the tip payment accounts.
+    pub total_tips: u64,
+    /// Lamports paid to the tip receiver, zero if it couldn't receive them.
+    pub tip_receiver_amount: u64,
+    /// Lamports paid to the block builder, zero if it couldn't receive them.
+    pub block_builder_amount: u64,
+}
+
+/// Tips held for a recipient that couldn't receive them when they were paid out, typically
+/// because the recipient wouldn't have been rent exempt.
+#[account]
+#[derive(Default)]
+pub struct StrandedTips {
+    pub recipient: Pubkey,
+    /// Lamports held for the recipient, on top of this account's rent exempt reserve.
+    pub amount: u64,
+    pub bump: u8,
+}
+
+impl StrandedTips {
+    pub const SIZE: usize = 8 + 32 + 8 + 1;
 }
 
 /// Account that searchers will need to tip for their bundles to be accepted.
-/// There will be 8 accounts of this type initialized in order to parallelize bundles.
+/// There will be [Config::num_tip_accounts] accounts of this type initialized in order to parallelize bundles.
 #[account]
 #[derive(Default)]
 pub struct TipPaymentAccount {}
@@ -768,7 +1487,7 @@ impl TipPaymentAccount {
     }
 
     fn initialize<'info>(
-        seeds: &[u8],
+        seeds: &[&[u8]; 2],
         program_id: &Pubkey,
         account_info: &AccountInfo<'info>,
         payer: &AccountInfo<'info>,
@@ -778,7 +1497,7 @@ impl TipPaymentAccount {
         let space = TipPaymentAccount::SIZE;
 
         // Validate PDA
-        let (pubkey, bump) = Pubkey::find_program_address(&[seeds], program_id);
+        let (pubkey, bump) = Pubkey::find_program_address(seeds, program_id);
         require!(
             &pubkey == account_info.key,
             anchor_lang::error::ErrorCode::ConstraintSeeds
@@ -799,7 +1518,7 @@ impl TipPaymentAccount {
         };
         let cpi_context = CpiContext::new(system_program.to_account_info(), cpi_accounts);
         anchor_lang::system_program::create_account(
-            cpi_context.with_signer(&[&[seeds, &[bump]]]),
+            cpi_context.with_signer(&[&[seeds[0], seeds[1], &[bump]]]),
             required_lamports,
             space as u64,
             program_id,
@@ -817,9 +1536,26 @@ impl TipPaymentAccount {
 
 /// events
 #[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct TipsClaimed {
-    tip_receiver: Pubkey,
-    tip_receiver_amount: u64,
-    block_builder: Pubkey,
-    block_builder_amount: u64,
+    pub tip_receiver: Pubkey,
+    pub tip_receiver_amount: u64,
+    pub block_builder: Pubkey,
+    /// Total paid out of the block builder commission.
+    pub block_builder_amount: u64,
+    /// How the block builder commission was split, empty if [Config::block_builder_recipients] was.
+    pub block_builder_recipients: Vec<RecipientAmount>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RecipientAmount {
+    pub recipient: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StrandedTipsClaimed {
+    pub recipient: Pubkey,
+    pub amount: u64,
 }
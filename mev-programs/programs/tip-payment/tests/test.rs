@@ -3,14 +3,16 @@ mod tests {
     use anchor_lang::{
         error::{ErrorCode, ERROR_CODE_OFFSET},
         solana_program::instruction::InstructionError,
-        AccountDeserialize, AnchorSerialize, Discriminator, InstructionData, ToAccountMetas,
+        AccountDeserialize, AccountSerialize, AnchorSerialize, Discriminator, InstructionData,
+        ToAccountMetas,
     };
     use jito_tip_payment::{
-        Config, InitBumps, TipPaymentAccount, TipPaymentError, CONFIG_ACCOUNT_SEED,
-        TIP_ACCOUNT_SEED_0, TIP_ACCOUNT_SEED_1, TIP_ACCOUNT_SEED_2, TIP_ACCOUNT_SEED_3,
-        TIP_ACCOUNT_SEED_4, TIP_ACCOUNT_SEED_5, TIP_ACCOUNT_SEED_6, TIP_ACCOUNT_SEED_7,
+        tip_account_seeds, BlockBuilderRecipient, Config, InitBumps, Settlement, SettlementLedger,
+        StrandedTips, TipPaymentAccount, TipPaymentError, CONFIG_ACCOUNT_SEED, CONFIG_VERSION,
+        LEGACY_TIP_ACCOUNT_SEEDS, MAX_TIP_ACCOUNTS, NUM_LEGACY_TIP_ACCOUNTS,
+        SETTLEMENT_LEDGER_SEED, STRANDED_TIPS_SEED,
     };
-    use solana_program_test::{BanksClient, ProgramTest, ProgramTestContext};
+    use solana_program_test::{find_file, read_file, BanksClient, ProgramTest, ProgramTestContext};
     use solana_sdk::{
         account::{Account, ReadableAccount},
         bpf_loader_upgradeable,
@@ -43,60 +45,105 @@ mod tests {
     }
 
     fn get_tip_pdas() -> Vec<(Pubkey, u8)> {
-        let tip_pda_0 =
-            Pubkey::find_program_address(&[TIP_ACCOUNT_SEED_0], &jito_tip_payment::id());
-        let tip_pda_1 =
-            Pubkey::find_program_address(&[TIP_ACCOUNT_SEED_1], &jito_tip_payment::id());
-        let tip_pda_2 =
-            Pubkey::find_program_address(&[TIP_ACCOUNT_SEED_2], &jito_tip_payment::id());
-        let tip_pda_3 =
-            Pubkey::find_program_address(&[TIP_ACCOUNT_SEED_3], &jito_tip_payment::id());
-        let tip_pda_4 =
-            Pubkey::find_program_address(&[TIP_ACCOUNT_SEED_4], &jito_tip_payment::id());
-        let tip_pda_5 =
-            Pubkey::find_program_address(&[TIP_ACCOUNT_SEED_5], &jito_tip_payment::id());
-        let tip_pda_6 =
-            Pubkey::find_program_address(&[TIP_ACCOUNT_SEED_6], &jito_tip_payment::id());
-        let tip_pda_7 =
-            Pubkey::find_program_address(&[TIP_ACCOUNT_SEED_7], &jito_tip_payment::id());
-        vec![
-            tip_pda_0, tip_pda_1, tip_pda_2, tip_pda_3, tip_pda_4, tip_pda_5, tip_pda_6, tip_pda_7,
-        ]
+        (0..NUM_LEGACY_TIP_ACCOUNTS)
+            .map(|index| {
+                Pubkey::find_program_address(&tip_account_seeds(index), &jito_tip_payment::id())
+            })
+            .collect()
+    }
+
+    fn get_tip_bumps() -> [u8; MAX_TIP_ACCOUNTS] {
+        let mut bumps = [0; MAX_TIP_ACCOUNTS];
+        for (bump, (_, tip_pda_bump)) in bumps.iter_mut().zip(get_tip_pdas()) {
+            *bump = tip_pda_bump;
+        }
+        bumps
+    }
+
+    /// Appends the tip payment accounts, which are passed through the remaining accounts.
+    fn with_tip_accounts(mut account_metas: Vec<AccountMeta>) -> Vec<AccountMeta> {
+        account_metas.extend(
+            get_tip_pdas()
+                .into_iter()
+                .map(|(pubkey, _)| AccountMeta::new(pubkey, false)),
+        );
+        account_metas
+    }
+
+    /// Replaces the tip payment program's data account so `upgrade_authority` can sign admin
+    /// instructions, since the genesis program is owned by an unusable authority.
+    fn get_program_data_account(upgrade_authority: &Pubkey) -> (Pubkey, Account) {
+        let (program_data, _) = Pubkey::find_program_address(
+            &[jito_tip_payment::id().as_ref()],
+            &bpf_loader_upgradeable::id(),
+        );
+
+        // bincode serialized UpgradeableLoaderState::ProgramData followed by the program ELF
+        let mut data = Vec::new();
+        data.extend_from_slice(&3u32.to_le_bytes());
+        data.extend_from_slice(&0u64.to_le_bytes());
+        data.push(1);
+        data.extend_from_slice(upgrade_authority.as_ref());
+        data.extend_from_slice(&read_file(find_file("jito_tip_payment.so").unwrap()));
+
+        (
+            program_data,
+            Account {
+                lamports: Rent::default().minimum_balance(data.len()),
+                data,
+                owner: bpf_loader_upgradeable::id(),
+                executable: false,
+                rent_epoch: 0,
+            },
+        )
     }
 
     fn get_config_pda() -> (Pubkey, u8) {
-        Pubkey::find_program_address(&[CONFIG_ACCOUNT_SEED], &&jito_tip_payment::id())
+        Pubkey::find_program_address(&[CONFIG_ACCOUNT_SEED], &jito_tip_payment::id())
+    }
+
+    fn get_settlement_ledger_pda() -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[SETTLEMENT_LEDGER_SEED], &jito_tip_payment::id())
+    }
+
+    async fn get_config(banks_client: &mut BanksClient) -> Config {
+        let config = banks_client
+            .get_account_with_commitment(get_config_pda().0, CommitmentLevel::Processed)
+            .await
+            .unwrap()
+            .unwrap();
+        Config::try_deserialize(&mut config.data()).unwrap()
+    }
+
+    async fn get_settlement_ledger(banks_client: &mut BanksClient) -> SettlementLedger {
+        let settlement_ledger = banks_client
+            .get_account_with_commitment(get_settlement_ledger_pda().0, CommitmentLevel::Processed)
+            .await
+            .unwrap()
+            .unwrap();
+        bytemuck::pod_read_unaligned(&settlement_ledger.data[8..SettlementLedger::SIZE])
     }
 
     async fn initialize_program(banks_client: &mut BanksClient, payer: &Keypair, blockhash: Hash) {
         let config_pda_bump = get_config_pda();
-        let tip_pdas = get_tip_pdas();
         let init_ix = Instruction {
             program_id: jito_tip_payment::id(),
-            data: jito_tip_payment::instruction::Initialize {
-                _bumps: InitBumps {
-                    config: config_pda_bump.1,
-                    tip_payment_account_0: tip_pdas[0].1,
-                    tip_payment_account_1: tip_pdas[1].1,
-                    tip_payment_account_2: tip_pdas[2].1,
-                    tip_payment_account_3: tip_pdas[3].1,
-                    tip_payment_account_4: tip_pdas[4].1,
-                    tip_payment_account_5: tip_pdas[5].1,
-                    tip_payment_account_6: tip_pdas[6].1,
-                    tip_payment_account_7: tip_pdas[7].1,
-                },
-            }
-            .data(),
-            accounts: jito_tip_payment::accounts::Initialize {
-                config: config_pda_bump.0,
-                tip_payment_account_0: tip_pdas[0].0,
-                tip_payment_account_1: tip_pdas[1].0,
-                tip_payment_account_2: tip_pdas[2].0,
-                tip_payment_account_3: tip_pdas[3].0,
-                tip_payment_account_4: tip_pdas[4].0,
-                tip_payment_account_5: tip_pdas[5].0,
-                tip_payment_account_6: tip_pdas[6].0,
-                tip_payment_account_7: tip_pdas[7].0,
+            data: jito_tip_payment::instruction::Initialize {}.data(),
+            accounts: with_tip_accounts(
+                jito_tip_payment::accounts::Initialize {
+                    config: config_pda_bump.0,
+                    system_program: system_program::id(),
+                    payer: payer.pubkey(),
+                }
+                .to_account_metas(None),
+            ),
+        };
+
+        let init_settlement_ledger_ix = Instruction {
+            program_id: jito_tip_payment::id(),
+            data: jito_tip_payment::instruction::InitializeSettlementLedger {}.data(),
+            accounts: jito_tip_payment::accounts::InitializeSettlementLedger {
+                settlement_ledger: get_settlement_ledger_pda().0,
                 system_program: system_program::id(),
                 payer: payer.pubkey(),
             }
@@ -104,7 +151,7 @@ mod tests {
         };
 
         let tx = Transaction::new_signed_with_payer(
-            &[init_ix],
+            &[init_ix, init_settlement_ledger_ix],
             Some(&payer.pubkey()),
             &[&payer],
             blockhash,
@@ -115,6 +162,16 @@ mod tests {
             .unwrap();
     }
 
+    #[test]
+    fn test_legacy_tip_account_addresses() {
+        for (index, seed) in LEGACY_TIP_ACCOUNT_SEEDS.iter().enumerate() {
+            assert_eq!(
+                Pubkey::find_program_address(&tip_account_seeds(index), &jito_tip_payment::id()),
+                Pubkey::find_program_address(&[seed], &jito_tip_payment::id())
+            );
+        }
+    }
+
     #[tokio::test]
     async fn test_init_ok() {
         let ProgramTestContext {
@@ -148,27 +205,23 @@ mod tests {
         initialize_program(&mut banks_client, &payer, last_blockhash).await;
 
         let config_pda = get_config_pda();
-        let tip_pdas = get_tip_pdas();
 
         let change_tip_receiver_ix = Instruction {
             program_id: jito_tip_payment::id(),
             data: jito_tip_payment::instruction::ChangeTipReceiver {}.data(),
-            accounts: jito_tip_payment::accounts::ChangeTipReceiver {
-                config: config_pda.0,
-                old_tip_receiver: Pubkey::new_unique(), // bad tip receiver
-                new_tip_receiver: Pubkey::new_unique(),
-                block_builder: payer.pubkey(),
-                tip_payment_account_0: tip_pdas[0].0,
-                tip_payment_account_1: tip_pdas[1].0,
-                tip_payment_account_2: tip_pdas[2].0,
-                tip_payment_account_3: tip_pdas[3].0,
-                tip_payment_account_4: tip_pdas[4].0,
-                tip_payment_account_5: tip_pdas[5].0,
-                tip_payment_account_6: tip_pdas[6].0,
-                tip_payment_account_7: tip_pdas[7].0,
-                signer: payer.pubkey(),
-            }
-            .to_account_metas(None),
+            accounts: with_tip_accounts(
+                jito_tip_payment::accounts::ChangeTipReceiver {
+                    config: config_pda.0,
+                    old_tip_receiver: Pubkey::new_unique(), // bad tip receiver
+                    new_tip_receiver: Pubkey::new_unique(),
+                    block_builder: payer.pubkey(),
+                    settlement_ledger: get_settlement_ledger_pda().0,
+                    tip_receiver_stranded_tips: Pubkey::new_unique(),
+                    block_builder_stranded_tips: get_stranded_tips_pda(&payer.pubkey()).0,
+                    signer: payer.pubkey(),
+                }
+                .to_account_metas(None),
+            ),
         };
         let tx = Transaction::new_signed_with_payer(
             &[change_tip_receiver_ix],
@@ -198,27 +251,23 @@ mod tests {
         initialize_program(&mut banks_client, &payer, last_blockhash).await;
 
         let config_pda = get_config_pda();
-        let tip_pdas = get_tip_pdas();
 
         let change_tip_receiver_ix = Instruction {
             program_id: jito_tip_payment::id(),
             data: jito_tip_payment::instruction::ChangeTipReceiver {}.data(),
-            accounts: jito_tip_payment::accounts::ChangeTipReceiver {
-                config: config_pda.0,
-                old_tip_receiver: payer.pubkey(),
-                new_tip_receiver: Pubkey::new_unique(),
-                block_builder: Pubkey::new_unique(), // bad block builder
-                tip_payment_account_0: tip_pdas[0].0,
-                tip_payment_account_1: tip_pdas[1].0,
-                tip_payment_account_2: tip_pdas[2].0,
-                tip_payment_account_3: tip_pdas[3].0,
-                tip_payment_account_4: tip_pdas[4].0,
-                tip_payment_account_5: tip_pdas[5].0,
-                tip_payment_account_6: tip_pdas[6].0,
-                tip_payment_account_7: tip_pdas[7].0,
-                signer: payer.pubkey(),
-            }
-            .to_account_metas(None),
+            accounts: with_tip_accounts(
+                jito_tip_payment::accounts::ChangeTipReceiver {
+                    config: config_pda.0,
+                    old_tip_receiver: payer.pubkey(),
+                    new_tip_receiver: Pubkey::new_unique(),
+                    block_builder: Pubkey::new_unique(), // bad block builder
+                    settlement_ledger: get_settlement_ledger_pda().0,
+                    tip_receiver_stranded_tips: get_stranded_tips_pda(&payer.pubkey()).0,
+                    block_builder_stranded_tips: Pubkey::new_unique(),
+                    signer: payer.pubkey(),
+                }
+                .to_account_metas(None),
+            ),
         };
         let tx = Transaction::new_signed_with_payer(
             &[change_tip_receiver_ix],
@@ -248,7 +297,6 @@ mod tests {
         initialize_program(&mut banks_client, &payer, last_blockhash).await;
 
         let config_pda = get_config_pda();
-        let tip_pdas = get_tip_pdas();
 
         let change_block_builder_ix = Instruction {
             program_id: jito_tip_payment::id(),
@@ -256,22 +304,19 @@ mod tests {
                 block_builder_commission: 0,
             }
             .data(),
-            accounts: jito_tip_payment::accounts::ChangeBlockBuilder {
-                config: config_pda.0,
-                tip_receiver: payer.pubkey(),
-                old_block_builder: Pubkey::new_unique(), // bad block builder
-                new_block_builder: Pubkey::new_unique(),
-                tip_payment_account_0: tip_pdas[0].0,
-                tip_payment_account_1: tip_pdas[1].0,
-                tip_payment_account_2: tip_pdas[2].0,
-                tip_payment_account_3: tip_pdas[3].0,
-                tip_payment_account_4: tip_pdas[4].0,
-                tip_payment_account_5: tip_pdas[5].0,
-                tip_payment_account_6: tip_pdas[6].0,
-                tip_payment_account_7: tip_pdas[7].0,
-                signer: payer.pubkey(),
-            }
-            .to_account_metas(None),
+            accounts: with_tip_accounts(
+                jito_tip_payment::accounts::ChangeBlockBuilder {
+                    config: config_pda.0,
+                    tip_receiver: payer.pubkey(),
+                    old_block_builder: Pubkey::new_unique(), // bad block builder
+                    new_block_builder: Pubkey::new_unique(),
+                    settlement_ledger: get_settlement_ledger_pda().0,
+                    tip_receiver_stranded_tips: get_stranded_tips_pda(&payer.pubkey()).0,
+                    block_builder_stranded_tips: Pubkey::new_unique(),
+                    signer: payer.pubkey(),
+                }
+                .to_account_metas(None),
+            ),
         };
         let tx = Transaction::new_signed_with_payer(
             &[change_block_builder_ix],
@@ -301,7 +346,6 @@ mod tests {
         initialize_program(&mut banks_client, &payer, last_blockhash).await;
 
         let config_pda = get_config_pda();
-        let tip_pdas = get_tip_pdas();
 
         let change_block_builder_ix = Instruction {
             program_id: jito_tip_payment::id(),
@@ -309,22 +353,19 @@ mod tests {
                 block_builder_commission: 0,
             }
             .data(),
-            accounts: jito_tip_payment::accounts::ChangeBlockBuilder {
-                config: config_pda.0,
-                tip_receiver: Pubkey::new_unique(), // bad tip receiver
-                old_block_builder: payer.pubkey(),
-                new_block_builder: Pubkey::new_unique(),
-                tip_payment_account_0: tip_pdas[0].0,
-                tip_payment_account_1: tip_pdas[1].0,
-                tip_payment_account_2: tip_pdas[2].0,
-                tip_payment_account_3: tip_pdas[3].0,
-                tip_payment_account_4: tip_pdas[4].0,
-                tip_payment_account_5: tip_pdas[5].0,
-                tip_payment_account_6: tip_pdas[6].0,
-                tip_payment_account_7: tip_pdas[7].0,
-                signer: payer.pubkey(),
-            }
-            .to_account_metas(None),
+            accounts: with_tip_accounts(
+                jito_tip_payment::accounts::ChangeBlockBuilder {
+                    config: config_pda.0,
+                    tip_receiver: Pubkey::new_unique(), // bad tip receiver
+                    old_block_builder: payer.pubkey(),
+                    new_block_builder: Pubkey::new_unique(),
+                    settlement_ledger: get_settlement_ledger_pda().0,
+                    tip_receiver_stranded_tips: Pubkey::new_unique(),
+                    block_builder_stranded_tips: get_stranded_tips_pda(&payer.pubkey()).0,
+                    signer: payer.pubkey(),
+                }
+                .to_account_metas(None),
+            ),
         };
         let tx = Transaction::new_signed_with_payer(
             &[change_block_builder_ix],
@@ -354,7 +395,6 @@ mod tests {
         initialize_program(&mut banks_client, &payer, last_blockhash).await;
 
         let config_pda = get_config_pda();
-        let tip_pdas = get_tip_pdas();
 
         let change_block_builder_ix = Instruction {
             program_id: jito_tip_payment::id(),
@@ -362,22 +402,19 @@ mod tests {
                 block_builder_commission: 101,
             }
             .data(),
-            accounts: jito_tip_payment::accounts::ChangeBlockBuilder {
-                config: config_pda.0,
-                tip_receiver: payer.pubkey(),
-                old_block_builder: payer.pubkey(),
-                new_block_builder: Pubkey::new_unique(),
-                tip_payment_account_0: tip_pdas[0].0,
-                tip_payment_account_1: tip_pdas[1].0,
-                tip_payment_account_2: tip_pdas[2].0,
-                tip_payment_account_3: tip_pdas[3].0,
-                tip_payment_account_4: tip_pdas[4].0,
-                tip_payment_account_5: tip_pdas[5].0,
-                tip_payment_account_6: tip_pdas[6].0,
-                tip_payment_account_7: tip_pdas[7].0,
-                signer: payer.pubkey(),
-            }
-            .to_account_metas(None),
+            accounts: with_tip_accounts(
+                jito_tip_payment::accounts::ChangeBlockBuilder {
+                    config: config_pda.0,
+                    tip_receiver: payer.pubkey(),
+                    old_block_builder: payer.pubkey(),
+                    new_block_builder: Pubkey::new_unique(),
+                    settlement_ledger: get_settlement_ledger_pda().0,
+                    tip_receiver_stranded_tips: get_stranded_tips_pda(&payer.pubkey()).0,
+                    block_builder_stranded_tips: get_stranded_tips_pda(&payer.pubkey()).0,
+                    signer: payer.pubkey(),
+                }
+                .to_account_metas(None),
+            ),
         };
         let tx = Transaction::new_signed_with_payer(
             &[change_block_builder_ix],
@@ -392,6 +429,107 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_change_block_builder_commission_units() {
+        let ProgramTestContext {
+            mut banks_client,
+            last_blockhash,
+            payer,
+            ..
+        } = get_test(&[]).await;
+        initialize_program(&mut banks_client, &payer, last_blockhash).await;
+
+        let change_block_builder_accounts = with_tip_accounts(
+            jito_tip_payment::accounts::ChangeBlockBuilder {
+                config: get_config_pda().0,
+                tip_receiver: payer.pubkey(),
+                old_block_builder: payer.pubkey(),
+                new_block_builder: payer.pubkey(),
+                settlement_ledger: get_settlement_ledger_pda().0,
+                tip_receiver_stranded_tips: get_stranded_tips_pda(&payer.pubkey()).0,
+                block_builder_stranded_tips: get_stranded_tips_pda(&payer.pubkey()).0,
+                signer: payer.pubkey(),
+            }
+            .to_account_metas(None),
+        );
+        // the original instruction keeps taking a whole percentage
+        banks_client
+            .process_transaction_with_commitment(
+                Transaction::new_signed_with_payer(
+                    &[Instruction {
+                        program_id: jito_tip_payment::id(),
+                        data: jito_tip_payment::instruction::ChangeBlockBuilder {
+                            block_builder_commission: 5,
+                        }
+                        .data(),
+                        accounts: change_block_builder_accounts.clone(),
+                    }],
+                    Some(&payer.pubkey()),
+                    &[&payer],
+                    last_blockhash,
+                ),
+                CommitmentLevel::Processed,
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            get_config(&mut banks_client)
+                .await
+                .block_builder_commission_bps,
+            500
+        );
+
+        banks_client
+            .process_transaction_with_commitment(
+                Transaction::new_signed_with_payer(
+                    &[Instruction {
+                        program_id: jito_tip_payment::id(),
+                        data: jito_tip_payment::instruction::ChangeBlockBuilderBps {
+                            block_builder_commission_bps: 5,
+                        }
+                        .data(),
+                        accounts: change_block_builder_accounts.clone(),
+                    }],
+                    Some(&payer.pubkey()),
+                    &[&payer],
+                    last_blockhash,
+                ),
+                CommitmentLevel::Processed,
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            get_config(&mut banks_client)
+                .await
+                .block_builder_commission_bps,
+            5
+        );
+
+        let err = banks_client
+            .process_transaction(Transaction::new_signed_with_payer(
+                &[Instruction {
+                    program_id: jito_tip_payment::id(),
+                    data: jito_tip_payment::instruction::ChangeBlockBuilderBps {
+                        block_builder_commission_bps: 10_001,
+                    }
+                    .data(),
+                    accounts: change_block_builder_accounts,
+                }],
+                Some(&payer.pubkey()),
+                &[&payer],
+                last_blockhash,
+            ))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.unwrap(),
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(ERROR_CODE_OFFSET + TipPaymentError::InvalidFee as u32)
+            )
+        );
+    }
+
     #[tokio::test]
     async fn test_change_tip_receiver_reserved_accounts() {
         let ProgramTestContext {
@@ -402,29 +540,25 @@ mod tests {
         } = get_test(&[]).await;
         initialize_program(&mut banks_client, &payer, last_blockhash).await;
 
-        let tip_pdas = get_tip_pdas();
         let config_pda = get_config_pda();
 
         for reserved_account in ReservedAccountKeys::all_keys_iter() {
             let change_tip_receiver_ix = Instruction {
                 program_id: jito_tip_payment::id(),
                 data: jito_tip_payment::instruction::ChangeTipReceiver {}.data(),
-                accounts: jito_tip_payment::accounts::ChangeTipReceiver {
-                    config: config_pda.0,
-                    old_tip_receiver: payer.pubkey(),
-                    new_tip_receiver: *reserved_account, // reserved account
-                    block_builder: payer.pubkey(),
-                    tip_payment_account_0: tip_pdas[0].0,
-                    tip_payment_account_1: tip_pdas[1].0,
-                    tip_payment_account_2: tip_pdas[2].0,
-                    tip_payment_account_3: tip_pdas[3].0,
-                    tip_payment_account_4: tip_pdas[4].0,
-                    tip_payment_account_5: tip_pdas[5].0,
-                    tip_payment_account_6: tip_pdas[6].0,
-                    tip_payment_account_7: tip_pdas[7].0,
-                    signer: payer.pubkey(),
-                }
-                .to_account_metas(None),
+                accounts: with_tip_accounts(
+                    jito_tip_payment::accounts::ChangeTipReceiver {
+                        config: config_pda.0,
+                        old_tip_receiver: payer.pubkey(),
+                        new_tip_receiver: *reserved_account, // reserved account
+                        block_builder: payer.pubkey(),
+                        settlement_ledger: get_settlement_ledger_pda().0,
+                        tip_receiver_stranded_tips: get_stranded_tips_pda(&payer.pubkey()).0,
+                        block_builder_stranded_tips: get_stranded_tips_pda(&payer.pubkey()).0,
+                        signer: payer.pubkey(),
+                    }
+                    .to_account_metas(None),
+                ),
             };
             let tx = Transaction::new_signed_with_payer(
                 &[change_tip_receiver_ix],
@@ -457,7 +591,6 @@ mod tests {
         } = get_test(&[]).await;
         initialize_program(&mut banks_client, &payer, last_blockhash).await;
 
-        let tip_pdas = get_tip_pdas();
         let config_pda = get_config_pda();
 
         for reserved_account in ReservedAccountKeys::all_keys_iter() {
@@ -467,22 +600,19 @@ mod tests {
                     block_builder_commission: 0,
                 }
                 .data(),
-                accounts: jito_tip_payment::accounts::ChangeBlockBuilder {
-                    config: config_pda.0,
-                    tip_receiver: payer.pubkey(),
-                    old_block_builder: payer.pubkey(),
-                    new_block_builder: *reserved_account,
-                    tip_payment_account_0: tip_pdas[0].0,
-                    tip_payment_account_1: tip_pdas[1].0,
-                    tip_payment_account_2: tip_pdas[2].0,
-                    tip_payment_account_3: tip_pdas[3].0,
-                    tip_payment_account_4: tip_pdas[4].0,
-                    tip_payment_account_5: tip_pdas[5].0,
-                    tip_payment_account_6: tip_pdas[6].0,
-                    tip_payment_account_7: tip_pdas[7].0,
-                    signer: payer.pubkey(),
-                }
-                .to_account_metas(None),
+                accounts: with_tip_accounts(
+                    jito_tip_payment::accounts::ChangeBlockBuilder {
+                        config: config_pda.0,
+                        tip_receiver: payer.pubkey(),
+                        old_block_builder: payer.pubkey(),
+                        new_block_builder: *reserved_account,
+                        settlement_ledger: get_settlement_ledger_pda().0,
+                        tip_receiver_stranded_tips: get_stranded_tips_pda(&payer.pubkey()).0,
+                        block_builder_stranded_tips: get_stranded_tips_pda(&payer.pubkey()).0,
+                        signer: payer.pubkey(),
+                    }
+                    .to_account_metas(None),
+                ),
             };
             let tx = Transaction::new_signed_with_payer(
                 &[change_block_builder_ix],
@@ -515,28 +645,24 @@ mod tests {
         } = get_test(&[]).await;
         initialize_program(&mut banks_client, &payer, last_blockhash).await;
 
-        let tip_pdas = get_tip_pdas();
         let config_pda = get_config_pda();
 
         let change_tip_receiver_ix = Instruction {
             program_id: jito_tip_payment::id(),
             data: jito_tip_payment::instruction::ChangeTipReceiver {}.data(),
-            accounts: jito_tip_payment::accounts::ChangeTipReceiver {
-                config: config_pda.0,
-                old_tip_receiver: payer.pubkey(),
-                new_tip_receiver: jito_tip_payment::id(), // demoted to read lock
-                block_builder: payer.pubkey(),
-                tip_payment_account_0: tip_pdas[0].0,
-                tip_payment_account_1: tip_pdas[1].0,
-                tip_payment_account_2: tip_pdas[2].0,
-                tip_payment_account_3: tip_pdas[3].0,
-                tip_payment_account_4: tip_pdas[4].0,
-                tip_payment_account_5: tip_pdas[5].0,
-                tip_payment_account_6: tip_pdas[6].0,
-                tip_payment_account_7: tip_pdas[7].0,
-                signer: payer.pubkey(),
-            }
-            .to_account_metas(None),
+            accounts: with_tip_accounts(
+                jito_tip_payment::accounts::ChangeTipReceiver {
+                    config: config_pda.0,
+                    old_tip_receiver: payer.pubkey(),
+                    new_tip_receiver: jito_tip_payment::id(), // demoted to read lock
+                    block_builder: payer.pubkey(),
+                    settlement_ledger: get_settlement_ledger_pda().0,
+                    tip_receiver_stranded_tips: get_stranded_tips_pda(&payer.pubkey()).0,
+                    block_builder_stranded_tips: get_stranded_tips_pda(&payer.pubkey()).0,
+                    signer: payer.pubkey(),
+                }
+                .to_account_metas(None),
+            ),
         };
         let tx = Transaction::new_signed_with_payer(
             &[change_tip_receiver_ix],
@@ -564,28 +690,24 @@ mod tests {
         } = get_test(&[]).await;
         initialize_program(&mut banks_client, &payer, last_blockhash).await;
 
-        let tip_pdas = get_tip_pdas();
         let config_pda = get_config_pda();
 
         let mut change_tip_receiver_ix = Instruction {
             program_id: jito_tip_payment::id(),
             data: jito_tip_payment::instruction::ChangeTipReceiver {}.data(),
-            accounts: jito_tip_payment::accounts::ChangeTipReceiver {
-                config: config_pda.0,
-                old_tip_receiver: payer.pubkey(),
-                new_tip_receiver: jito_tip_payment::id(),
-                block_builder: payer.pubkey(),
-                tip_payment_account_0: tip_pdas[0].0,
-                tip_payment_account_1: tip_pdas[1].0,
-                tip_payment_account_2: tip_pdas[2].0,
-                tip_payment_account_3: tip_pdas[3].0,
-                tip_payment_account_4: tip_pdas[4].0,
-                tip_payment_account_5: tip_pdas[5].0,
-                tip_payment_account_6: tip_pdas[6].0,
-                tip_payment_account_7: tip_pdas[7].0,
-                signer: payer.pubkey(),
-            }
-            .to_account_metas(None),
+            accounts: with_tip_accounts(
+                jito_tip_payment::accounts::ChangeTipReceiver {
+                    config: config_pda.0,
+                    old_tip_receiver: payer.pubkey(),
+                    new_tip_receiver: jito_tip_payment::id(),
+                    block_builder: payer.pubkey(),
+                    settlement_ledger: get_settlement_ledger_pda().0,
+                    tip_receiver_stranded_tips: get_stranded_tips_pda(&payer.pubkey()).0,
+                    block_builder_stranded_tips: get_stranded_tips_pda(&payer.pubkey()).0,
+                    signer: payer.pubkey(),
+                }
+                .to_account_metas(None),
+            ),
         };
         change_tip_receiver_ix
             .accounts
@@ -618,7 +740,6 @@ mod tests {
         } = get_test(&[]).await;
         initialize_program(&mut banks_client, &payer, last_blockhash).await;
 
-        let tip_pdas = get_tip_pdas();
         let config_pda = get_config_pda();
 
         let change_block_builder_ix = Instruction {
@@ -627,22 +748,19 @@ mod tests {
                 block_builder_commission: 0,
             }
             .data(),
-            accounts: jito_tip_payment::accounts::ChangeBlockBuilder {
-                config: config_pda.0,
-                tip_receiver: payer.pubkey(),
-                old_block_builder: payer.pubkey(),
-                new_block_builder: jito_tip_payment::id(),
-                tip_payment_account_0: tip_pdas[0].0,
-                tip_payment_account_1: tip_pdas[1].0,
-                tip_payment_account_2: tip_pdas[2].0,
-                tip_payment_account_3: tip_pdas[3].0,
-                tip_payment_account_4: tip_pdas[4].0,
-                tip_payment_account_5: tip_pdas[5].0,
-                tip_payment_account_6: tip_pdas[6].0,
-                tip_payment_account_7: tip_pdas[7].0,
-                signer: payer.pubkey(),
-            }
-            .to_account_metas(None),
+            accounts: with_tip_accounts(
+                jito_tip_payment::accounts::ChangeBlockBuilder {
+                    config: config_pda.0,
+                    tip_receiver: payer.pubkey(),
+                    old_block_builder: payer.pubkey(),
+                    new_block_builder: jito_tip_payment::id(),
+                    settlement_ledger: get_settlement_ledger_pda().0,
+                    tip_receiver_stranded_tips: get_stranded_tips_pda(&payer.pubkey()).0,
+                    block_builder_stranded_tips: get_stranded_tips_pda(&payer.pubkey()).0,
+                    signer: payer.pubkey(),
+                }
+                .to_account_metas(None),
+            ),
         };
         let tx = Transaction::new_signed_with_payer(
             &[change_block_builder_ix],
@@ -670,7 +788,6 @@ mod tests {
         } = get_test(&[]).await;
         initialize_program(&mut banks_client, &payer, last_blockhash).await;
 
-        let tip_pdas = get_tip_pdas();
         let config_pda = get_config_pda();
 
         let mut change_block_builder_ix = Instruction {
@@ -679,22 +796,19 @@ mod tests {
                 block_builder_commission: 0,
             }
             .data(),
-            accounts: jito_tip_payment::accounts::ChangeBlockBuilder {
-                config: config_pda.0,
-                tip_receiver: payer.pubkey(),
-                old_block_builder: payer.pubkey(),
-                new_block_builder: jito_tip_payment::id(),
-                tip_payment_account_0: tip_pdas[0].0,
-                tip_payment_account_1: tip_pdas[1].0,
-                tip_payment_account_2: tip_pdas[2].0,
-                tip_payment_account_3: tip_pdas[3].0,
-                tip_payment_account_4: tip_pdas[4].0,
-                tip_payment_account_5: tip_pdas[5].0,
-                tip_payment_account_6: tip_pdas[6].0,
-                tip_payment_account_7: tip_pdas[7].0,
-                signer: payer.pubkey(),
-            }
-            .to_account_metas(None),
+            accounts: with_tip_accounts(
+                jito_tip_payment::accounts::ChangeBlockBuilder {
+                    config: config_pda.0,
+                    tip_receiver: payer.pubkey(),
+                    old_block_builder: payer.pubkey(),
+                    new_block_builder: jito_tip_payment::id(),
+                    settlement_ledger: get_settlement_ledger_pda().0,
+                    tip_receiver_stranded_tips: get_stranded_tips_pda(&payer.pubkey()).0,
+                    block_builder_stranded_tips: get_stranded_tips_pda(&payer.pubkey()).0,
+                    signer: payer.pubkey(),
+                }
+                .to_account_metas(None),
+            ),
         };
         change_block_builder_ix
             .accounts
@@ -739,22 +853,19 @@ mod tests {
                 block_builder_commission: 100,
             }
             .data(),
-            accounts: jito_tip_payment::accounts::ChangeBlockBuilder {
-                config: config_pda.0,
-                tip_receiver: payer.pubkey(),
-                old_block_builder: payer.pubkey(),
-                new_block_builder: new_block_builder_1,
-                tip_payment_account_0: tip_pdas[0].0,
-                tip_payment_account_1: tip_pdas[1].0,
-                tip_payment_account_2: tip_pdas[2].0,
-                tip_payment_account_3: tip_pdas[3].0,
-                tip_payment_account_4: tip_pdas[4].0,
-                tip_payment_account_5: tip_pdas[5].0,
-                tip_payment_account_6: tip_pdas[6].0,
-                tip_payment_account_7: tip_pdas[7].0,
-                signer: payer.pubkey(),
-            }
-            .to_account_metas(None),
+            accounts: with_tip_accounts(
+                jito_tip_payment::accounts::ChangeBlockBuilder {
+                    config: config_pda.0,
+                    tip_receiver: payer.pubkey(),
+                    old_block_builder: payer.pubkey(),
+                    new_block_builder: new_block_builder_1,
+                    settlement_ledger: get_settlement_ledger_pda().0,
+                    tip_receiver_stranded_tips: get_stranded_tips_pda(&payer.pubkey()).0,
+                    block_builder_stranded_tips: get_stranded_tips_pda(&payer.pubkey()).0,
+                    signer: payer.pubkey(),
+                }
+                .to_account_metas(None),
+            ),
         };
         let tx = Transaction::new_signed_with_payer(
             &[
@@ -789,26 +900,23 @@ mod tests {
                 block_builder_commission: 100,
             }
             .data(),
-            accounts: jito_tip_payment::accounts::ChangeBlockBuilder {
-                config: config_pda.0,
-                tip_receiver: payer.pubkey(),
-                old_block_builder: new_block_builder_1,
-                new_block_builder: new_block_builder_2,
-                tip_payment_account_0: tip_pdas[0].0,
-                tip_payment_account_1: tip_pdas[1].0,
-                tip_payment_account_2: tip_pdas[2].0,
-                tip_payment_account_3: tip_pdas[3].0,
-                tip_payment_account_4: tip_pdas[4].0,
-                tip_payment_account_5: tip_pdas[5].0,
-                tip_payment_account_6: tip_pdas[6].0,
-                tip_payment_account_7: tip_pdas[7].0,
-                signer: payer.pubkey(),
-            }
-            .to_account_metas(None),
-        };
-
-        // send some lamports to a tip account then try to change the block builder account
-        let tx = Transaction::new_signed_with_payer(
+            accounts: with_tip_accounts(
+                jito_tip_payment::accounts::ChangeBlockBuilder {
+                    config: config_pda.0,
+                    tip_receiver: payer.pubkey(),
+                    old_block_builder: new_block_builder_1,
+                    new_block_builder: new_block_builder_2,
+                    settlement_ledger: get_settlement_ledger_pda().0,
+                    tip_receiver_stranded_tips: get_stranded_tips_pda(&payer.pubkey()).0,
+                    block_builder_stranded_tips: get_stranded_tips_pda(&new_block_builder_1).0,
+                    signer: payer.pubkey(),
+                }
+                .to_account_metas(None),
+            ),
+        };
+
+        // send some lamports to a tip account then try to change the block builder account
+        let tx = Transaction::new_signed_with_payer(
             &[change_block_builder_ix],
             Some(&payer.pubkey()),
             &[&payer],
@@ -884,22 +992,25 @@ mod tests {
                         Instruction {
                             program_id: jito_tip_payment::id(),
                             data: jito_tip_payment::instruction::ChangeTipReceiver {}.data(),
-                            accounts: jito_tip_payment::accounts::ChangeTipReceiver {
-                                config: config_pda.0,
-                                old_tip_receiver: payer.pubkey(),
-                                new_tip_receiver,
-                                block_builder: payer.pubkey(),
-                                tip_payment_account_0: tip_pdas[0].0,
-                                tip_payment_account_1: tip_pdas[1].0,
-                                tip_payment_account_2: tip_pdas[2].0,
-                                tip_payment_account_3: tip_pdas[3].0,
-                                tip_payment_account_4: tip_pdas[4].0,
-                                tip_payment_account_5: tip_pdas[5].0,
-                                tip_payment_account_6: tip_pdas[6].0,
-                                tip_payment_account_7: tip_pdas[7].0,
-                                signer: payer.pubkey(),
-                            }
-                            .to_account_metas(None),
+                            accounts: with_tip_accounts(
+                                jito_tip_payment::accounts::ChangeTipReceiver {
+                                    config: config_pda.0,
+                                    old_tip_receiver: payer.pubkey(),
+                                    new_tip_receiver,
+                                    block_builder: payer.pubkey(),
+                                    settlement_ledger: get_settlement_ledger_pda().0,
+                                    tip_receiver_stranded_tips: get_stranded_tips_pda(
+                                        &payer.pubkey(),
+                                    )
+                                    .0,
+                                    block_builder_stranded_tips: get_stranded_tips_pda(
+                                        &payer.pubkey(),
+                                    )
+                                    .0,
+                                    signer: payer.pubkey(),
+                                }
+                                .to_account_metas(None),
+                            ),
                         },
                         transfer(&payer.pubkey(), &tip_pdas[1].0, 1),
                     ],
@@ -933,22 +1044,23 @@ mod tests {
                             block_builder_commission: 0,
                         }
                         .data(),
-                        accounts: jito_tip_payment::accounts::ChangeBlockBuilder {
-                            config: config_pda.0,
-                            tip_receiver: new_tip_receiver,
-                            old_block_builder: payer.pubkey(),
-                            new_block_builder: Pubkey::new_unique(),
-                            tip_payment_account_0: tip_pdas[0].0,
-                            tip_payment_account_1: tip_pdas[1].0,
-                            tip_payment_account_2: tip_pdas[2].0,
-                            tip_payment_account_3: tip_pdas[3].0,
-                            tip_payment_account_4: tip_pdas[4].0,
-                            tip_payment_account_5: tip_pdas[5].0,
-                            tip_payment_account_6: tip_pdas[6].0,
-                            tip_payment_account_7: tip_pdas[7].0,
-                            signer: payer.pubkey(),
-                        }
-                        .to_account_metas(None),
+                        accounts: with_tip_accounts(
+                            jito_tip_payment::accounts::ChangeBlockBuilder {
+                                config: config_pda.0,
+                                tip_receiver: new_tip_receiver,
+                                old_block_builder: payer.pubkey(),
+                                new_block_builder: Pubkey::new_unique(),
+                                settlement_ledger: get_settlement_ledger_pda().0,
+                                tip_receiver_stranded_tips: get_stranded_tips_pda(
+                                    &new_tip_receiver,
+                                )
+                                .0,
+                                block_builder_stranded_tips: get_stranded_tips_pda(&payer.pubkey())
+                                    .0,
+                                signer: payer.pubkey(),
+                            }
+                            .to_account_metas(None),
+                        ),
                     }],
                     Some(&payer.pubkey()),
                     &[&payer],
@@ -1010,22 +1122,25 @@ mod tests {
                         Instruction {
                             program_id: jito_tip_payment::id(),
                             data: jito_tip_payment::instruction::ChangeTipReceiver {}.data(),
-                            accounts: jito_tip_payment::accounts::ChangeTipReceiver {
-                                config: config_pda.0,
-                                old_tip_receiver: payer.pubkey(),
-                                new_tip_receiver,
-                                block_builder: payer.pubkey(),
-                                tip_payment_account_0: tip_pdas[0].0,
-                                tip_payment_account_1: tip_pdas[1].0,
-                                tip_payment_account_2: tip_pdas[2].0,
-                                tip_payment_account_3: tip_pdas[3].0,
-                                tip_payment_account_4: tip_pdas[4].0,
-                                tip_payment_account_5: tip_pdas[5].0,
-                                tip_payment_account_6: tip_pdas[6].0,
-                                tip_payment_account_7: tip_pdas[7].0,
-                                signer: payer.pubkey(),
-                            }
-                            .to_account_metas(None),
+                            accounts: with_tip_accounts(
+                                jito_tip_payment::accounts::ChangeTipReceiver {
+                                    config: config_pda.0,
+                                    old_tip_receiver: payer.pubkey(),
+                                    new_tip_receiver,
+                                    block_builder: payer.pubkey(),
+                                    settlement_ledger: get_settlement_ledger_pda().0,
+                                    tip_receiver_stranded_tips: get_stranded_tips_pda(
+                                        &payer.pubkey(),
+                                    )
+                                    .0,
+                                    block_builder_stranded_tips: get_stranded_tips_pda(
+                                        &payer.pubkey(),
+                                    )
+                                    .0,
+                                    signer: payer.pubkey(),
+                                }
+                                .to_account_metas(None),
+                            ),
                         },
                         transfer(&payer.pubkey(), &tip_pdas[1].0, 1),
                     ],
@@ -1056,22 +1171,23 @@ mod tests {
                     &[Instruction {
                         program_id: jito_tip_payment::id(),
                         data: jito_tip_payment::instruction::ChangeTipReceiver {}.data(),
-                        accounts: jito_tip_payment::accounts::ChangeTipReceiver {
-                            config: config_pda.0,
-                            old_tip_receiver: new_tip_receiver,
-                            new_tip_receiver: Pubkey::new_unique(),
-                            block_builder: payer.pubkey(),
-                            tip_payment_account_0: tip_pdas[0].0,
-                            tip_payment_account_1: tip_pdas[1].0,
-                            tip_payment_account_2: tip_pdas[2].0,
-                            tip_payment_account_3: tip_pdas[3].0,
-                            tip_payment_account_4: tip_pdas[4].0,
-                            tip_payment_account_5: tip_pdas[5].0,
-                            tip_payment_account_6: tip_pdas[6].0,
-                            tip_payment_account_7: tip_pdas[7].0,
-                            signer: payer.pubkey(),
-                        }
-                        .to_account_metas(None),
+                        accounts: with_tip_accounts(
+                            jito_tip_payment::accounts::ChangeTipReceiver {
+                                config: config_pda.0,
+                                old_tip_receiver: new_tip_receiver,
+                                new_tip_receiver: Pubkey::new_unique(),
+                                block_builder: payer.pubkey(),
+                                settlement_ledger: get_settlement_ledger_pda().0,
+                                tip_receiver_stranded_tips: get_stranded_tips_pda(
+                                    &new_tip_receiver,
+                                )
+                                .0,
+                                block_builder_stranded_tips: get_stranded_tips_pda(&payer.pubkey())
+                                    .0,
+                                signer: payer.pubkey(),
+                            }
+                            .to_account_metas(None),
+                        ),
                     }],
                     Some(&payer.pubkey()),
                     &[&payer],
@@ -1136,22 +1252,25 @@ mod tests {
                                 block_builder_commission: 100,
                             }
                             .data(),
-                            accounts: jito_tip_payment::accounts::ChangeBlockBuilder {
-                                config: config_pda.0,
-                                tip_receiver: payer.pubkey(),
-                                old_block_builder: payer.pubkey(),
-                                new_block_builder,
-                                tip_payment_account_0: tip_pdas[0].0,
-                                tip_payment_account_1: tip_pdas[1].0,
-                                tip_payment_account_2: tip_pdas[2].0,
-                                tip_payment_account_3: tip_pdas[3].0,
-                                tip_payment_account_4: tip_pdas[4].0,
-                                tip_payment_account_5: tip_pdas[5].0,
-                                tip_payment_account_6: tip_pdas[6].0,
-                                tip_payment_account_7: tip_pdas[7].0,
-                                signer: payer.pubkey(),
-                            }
-                            .to_account_metas(None),
+                            accounts: with_tip_accounts(
+                                jito_tip_payment::accounts::ChangeBlockBuilder {
+                                    config: config_pda.0,
+                                    tip_receiver: payer.pubkey(),
+                                    old_block_builder: payer.pubkey(),
+                                    new_block_builder,
+                                    settlement_ledger: get_settlement_ledger_pda().0,
+                                    tip_receiver_stranded_tips: get_stranded_tips_pda(
+                                        &payer.pubkey(),
+                                    )
+                                    .0,
+                                    block_builder_stranded_tips: get_stranded_tips_pda(
+                                        &payer.pubkey(),
+                                    )
+                                    .0,
+                                    signer: payer.pubkey(),
+                                }
+                                .to_account_metas(None),
+                            ),
                         },
                         transfer(&payer.pubkey(), &tip_pdas[1].0, 1),
                     ],
@@ -1182,22 +1301,23 @@ mod tests {
                     &[Instruction {
                         program_id: jito_tip_payment::id(),
                         data: jito_tip_payment::instruction::ChangeTipReceiver {}.data(),
-                        accounts: jito_tip_payment::accounts::ChangeTipReceiver {
-                            config: config_pda.0,
-                            old_tip_receiver: payer.pubkey(),
-                            new_tip_receiver: Pubkey::new_unique(),
-                            block_builder: new_block_builder,
-                            tip_payment_account_0: tip_pdas[0].0,
-                            tip_payment_account_1: tip_pdas[1].0,
-                            tip_payment_account_2: tip_pdas[2].0,
-                            tip_payment_account_3: tip_pdas[3].0,
-                            tip_payment_account_4: tip_pdas[4].0,
-                            tip_payment_account_5: tip_pdas[5].0,
-                            tip_payment_account_6: tip_pdas[6].0,
-                            tip_payment_account_7: tip_pdas[7].0,
-                            signer: payer.pubkey(),
-                        }
-                        .to_account_metas(None),
+                        accounts: with_tip_accounts(
+                            jito_tip_payment::accounts::ChangeTipReceiver {
+                                config: config_pda.0,
+                                old_tip_receiver: payer.pubkey(),
+                                new_tip_receiver: Pubkey::new_unique(),
+                                block_builder: new_block_builder,
+                                settlement_ledger: get_settlement_ledger_pda().0,
+                                tip_receiver_stranded_tips: get_stranded_tips_pda(&payer.pubkey())
+                                    .0,
+                                block_builder_stranded_tips: get_stranded_tips_pda(
+                                    &new_block_builder,
+                                )
+                                .0,
+                                signer: payer.pubkey(),
+                            }
+                            .to_account_metas(None),
+                        ),
                     }],
                     Some(&payer.pubkey()),
                     &[&payer],
@@ -1263,42 +1383,48 @@ mod tests {
                                 block_builder_commission: 50,
                             }
                             .data(),
-                            accounts: jito_tip_payment::accounts::ChangeBlockBuilder {
-                                config: config_pda.0,
-                                tip_receiver: payer.pubkey(),
-                                old_block_builder: payer.pubkey(),
-                                new_block_builder,
-                                tip_payment_account_0: tip_pdas[0].0,
-                                tip_payment_account_1: tip_pdas[1].0,
-                                tip_payment_account_2: tip_pdas[2].0,
-                                tip_payment_account_3: tip_pdas[3].0,
-                                tip_payment_account_4: tip_pdas[4].0,
-                                tip_payment_account_5: tip_pdas[5].0,
-                                tip_payment_account_6: tip_pdas[6].0,
-                                tip_payment_account_7: tip_pdas[7].0,
-                                signer: payer.pubkey(),
-                            }
-                            .to_account_metas(None),
+                            accounts: with_tip_accounts(
+                                jito_tip_payment::accounts::ChangeBlockBuilder {
+                                    config: config_pda.0,
+                                    tip_receiver: payer.pubkey(),
+                                    old_block_builder: payer.pubkey(),
+                                    new_block_builder,
+                                    settlement_ledger: get_settlement_ledger_pda().0,
+                                    tip_receiver_stranded_tips: get_stranded_tips_pda(
+                                        &payer.pubkey(),
+                                    )
+                                    .0,
+                                    block_builder_stranded_tips: get_stranded_tips_pda(
+                                        &payer.pubkey(),
+                                    )
+                                    .0,
+                                    signer: payer.pubkey(),
+                                }
+                                .to_account_metas(None),
+                            ),
                         },
                         Instruction {
                             program_id: jito_tip_payment::id(),
                             data: jito_tip_payment::instruction::ChangeTipReceiver {}.data(),
-                            accounts: jito_tip_payment::accounts::ChangeTipReceiver {
-                                config: config_pda.0,
-                                old_tip_receiver: payer.pubkey(),
-                                new_tip_receiver,
-                                block_builder: new_block_builder,
-                                tip_payment_account_0: tip_pdas[0].0,
-                                tip_payment_account_1: tip_pdas[1].0,
-                                tip_payment_account_2: tip_pdas[2].0,
-                                tip_payment_account_3: tip_pdas[3].0,
-                                tip_payment_account_4: tip_pdas[4].0,
-                                tip_payment_account_5: tip_pdas[5].0,
-                                tip_payment_account_6: tip_pdas[6].0,
-                                tip_payment_account_7: tip_pdas[7].0,
-                                signer: payer.pubkey(),
-                            }
-                            .to_account_metas(None),
+                            accounts: with_tip_accounts(
+                                jito_tip_payment::accounts::ChangeTipReceiver {
+                                    config: config_pda.0,
+                                    old_tip_receiver: payer.pubkey(),
+                                    new_tip_receiver,
+                                    block_builder: new_block_builder,
+                                    settlement_ledger: get_settlement_ledger_pda().0,
+                                    tip_receiver_stranded_tips: get_stranded_tips_pda(
+                                        &payer.pubkey(),
+                                    )
+                                    .0,
+                                    block_builder_stranded_tips: get_stranded_tips_pda(
+                                        &new_block_builder,
+                                    )
+                                    .0,
+                                    signer: payer.pubkey(),
+                                }
+                                .to_account_metas(None),
+                            ),
                         },
                         transfer(&payer.pubkey(), &tip_pdas[1].0, 333_333_333),
                     ],
@@ -1317,22 +1443,25 @@ mod tests {
                     &[Instruction {
                         program_id: jito_tip_payment::id(),
                         data: jito_tip_payment::instruction::ChangeTipReceiver {}.data(),
-                        accounts: jito_tip_payment::accounts::ChangeTipReceiver {
-                            config: config_pda.0,
-                            old_tip_receiver: new_tip_receiver,
-                            new_tip_receiver,
-                            block_builder: new_block_builder,
-                            tip_payment_account_0: tip_pdas[0].0,
-                            tip_payment_account_1: tip_pdas[1].0,
-                            tip_payment_account_2: tip_pdas[2].0,
-                            tip_payment_account_3: tip_pdas[3].0,
-                            tip_payment_account_4: tip_pdas[4].0,
-                            tip_payment_account_5: tip_pdas[5].0,
-                            tip_payment_account_6: tip_pdas[6].0,
-                            tip_payment_account_7: tip_pdas[7].0,
-                            signer: payer.pubkey(),
-                        }
-                        .to_account_metas(None),
+                        accounts: with_tip_accounts(
+                            jito_tip_payment::accounts::ChangeTipReceiver {
+                                config: config_pda.0,
+                                old_tip_receiver: new_tip_receiver,
+                                new_tip_receiver,
+                                block_builder: new_block_builder,
+                                settlement_ledger: get_settlement_ledger_pda().0,
+                                tip_receiver_stranded_tips: get_stranded_tips_pda(
+                                    &new_tip_receiver,
+                                )
+                                .0,
+                                block_builder_stranded_tips: get_stranded_tips_pda(
+                                    &new_block_builder,
+                                )
+                                .0,
+                                signer: payer.pubkey(),
+                            }
+                            .to_account_metas(None),
+                        ),
                     }],
                     Some(&payer.pubkey()),
                     &[&payer],
@@ -1363,14 +1492,19 @@ mod tests {
     fn get_initial_accounts(config: Config) -> Vec<(Pubkey, Account)> {
         let config_data = config.try_to_vec().unwrap();
         let mut serialized_config = Vec::with_capacity(8 + config_data.len());
-        serialized_config.extend_from_slice(&Config::DISCRIMINATOR);
+        serialized_config.extend_from_slice(Config::DISCRIMINATOR);
         serialized_config.extend_from_slice(&config_data);
+        serialized_config.resize(Config::SIZE, 0);
         let (config_pubkey, _config_bump) =
-            Pubkey::find_program_address(&[&CONFIG_ACCOUNT_SEED], &jito_tip_payment::id());
+            Pubkey::find_program_address(&[CONFIG_ACCOUNT_SEED], &jito_tip_payment::id());
 
         let tpas = get_tip_pdas();
         let mut tpa_data = Vec::with_capacity(8);
         tpa_data.extend_from_slice(TipPaymentAccount::DISCRIMINATOR);
+        let mut settlement_ledger_data = vec![0; SettlementLedger::SIZE];
+        settlement_ledger_data[..8].copy_from_slice(SettlementLedger::DISCRIMINATOR);
+        // the bump follows the discriminator and num_settlements
+        settlement_ledger_data[16] = get_settlement_ledger_pda().1;
         let initial_accounts = vec![
             (
                 config_pubkey,
@@ -1462,6 +1596,16 @@ mod tests {
                     rent_epoch: u64::MAX,
                 },
             ),
+            (
+                get_settlement_ledger_pda().0,
+                Account {
+                    lamports: Rent::default().minimum_balance(SettlementLedger::SIZE),
+                    data: settlement_ledger_data,
+                    owner: jito_tip_payment::id(),
+                    executable: false,
+                    rent_epoch: u64::MAX,
+                },
+            ),
         ];
 
         initial_accounts
@@ -1470,25 +1614,20 @@ mod tests {
     #[tokio::test]
     async fn test_change_tip_receiver_pay_tip_receiver_program() {
         let (config_pubkey, config_bump) =
-            Pubkey::find_program_address(&[&CONFIG_ACCOUNT_SEED], &jito_tip_payment::id());
+            Pubkey::find_program_address(&[CONFIG_ACCOUNT_SEED], &jito_tip_payment::id());
         let tip_pdas = get_tip_pdas();
 
         let block_builder = Keypair::new();
         let config = Config {
             tip_receiver: jito_tip_distribution::id(),
             block_builder: block_builder.pubkey(),
-            block_builder_commission_pct: 50,
+            block_builder_commission_bps: 5_000,
             bumps: InitBumps {
                 config: config_bump,
-                tip_payment_account_0: tip_pdas[0].1,
-                tip_payment_account_1: tip_pdas[1].1,
-                tip_payment_account_2: tip_pdas[2].1,
-                tip_payment_account_3: tip_pdas[3].1,
-                tip_payment_account_4: tip_pdas[4].1,
-                tip_payment_account_5: tip_pdas[5].1,
-                tip_payment_account_6: tip_pdas[6].1,
-                tip_payment_account_7: tip_pdas[7].1,
+                tip_payment_accounts: get_tip_bumps(),
             },
+            num_tip_accounts: NUM_LEGACY_TIP_ACCOUNTS as u8,
+            ..Config::default()
         };
         let initial_accounts = get_initial_accounts(config);
 
@@ -1513,22 +1652,25 @@ mod tests {
                         Instruction {
                             program_id: jito_tip_payment::id(),
                             data: jito_tip_payment::instruction::ChangeTipReceiver {}.data(),
-                            accounts: jito_tip_payment::accounts::ChangeTipReceiver {
-                                config: config_pubkey,
-                                old_tip_receiver: jito_tip_distribution::id(),
-                                new_tip_receiver: Pubkey::new_unique(),
-                                block_builder: block_builder.pubkey(),
-                                tip_payment_account_0: tip_pdas[0].0,
-                                tip_payment_account_1: tip_pdas[1].0,
-                                tip_payment_account_2: tip_pdas[2].0,
-                                tip_payment_account_3: tip_pdas[3].0,
-                                tip_payment_account_4: tip_pdas[4].0,
-                                tip_payment_account_5: tip_pdas[5].0,
-                                tip_payment_account_6: tip_pdas[6].0,
-                                tip_payment_account_7: tip_pdas[7].0,
-                                signer: payer.pubkey(),
-                            }
-                            .to_account_metas(None),
+                            accounts: with_tip_accounts(
+                                jito_tip_payment::accounts::ChangeTipReceiver {
+                                    config: config_pubkey,
+                                    old_tip_receiver: jito_tip_distribution::id(),
+                                    new_tip_receiver: Pubkey::new_unique(),
+                                    block_builder: block_builder.pubkey(),
+                                    settlement_ledger: get_settlement_ledger_pda().0,
+                                    tip_receiver_stranded_tips: get_stranded_tips_pda(
+                                        &jito_tip_distribution::id(),
+                                    )
+                                    .0,
+                                    block_builder_stranded_tips: get_stranded_tips_pda(
+                                        &block_builder.pubkey(),
+                                    )
+                                    .0,
+                                    signer: payer.pubkey(),
+                                }
+                                .to_account_metas(None),
+                            ),
                         },
                     ],
                     Some(&payer.pubkey()),
@@ -1551,25 +1693,20 @@ mod tests {
     #[tokio::test]
     async fn test_change_tip_receiver_pay_block_builder_program() {
         let (config_pubkey, config_bump) =
-            Pubkey::find_program_address(&[&CONFIG_ACCOUNT_SEED], &jito_tip_payment::id());
+            Pubkey::find_program_address(&[CONFIG_ACCOUNT_SEED], &jito_tip_payment::id());
         let tip_pdas = get_tip_pdas();
 
         let tip_receiver = Keypair::new();
         let config = Config {
             tip_receiver: tip_receiver.pubkey(),
             block_builder: jito_tip_distribution::id(),
-            block_builder_commission_pct: 50,
+            block_builder_commission_bps: 5_000,
             bumps: InitBumps {
                 config: config_bump,
-                tip_payment_account_0: tip_pdas[0].1,
-                tip_payment_account_1: tip_pdas[1].1,
-                tip_payment_account_2: tip_pdas[2].1,
-                tip_payment_account_3: tip_pdas[3].1,
-                tip_payment_account_4: tip_pdas[4].1,
-                tip_payment_account_5: tip_pdas[5].1,
-                tip_payment_account_6: tip_pdas[6].1,
-                tip_payment_account_7: tip_pdas[7].1,
+                tip_payment_accounts: get_tip_bumps(),
             },
+            num_tip_accounts: NUM_LEGACY_TIP_ACCOUNTS as u8,
+            ..Config::default()
         };
         let initial_accounts = get_initial_accounts(config);
 
@@ -1594,22 +1731,25 @@ mod tests {
                         Instruction {
                             program_id: jito_tip_payment::id(),
                             data: jito_tip_payment::instruction::ChangeTipReceiver {}.data(),
-                            accounts: jito_tip_payment::accounts::ChangeTipReceiver {
-                                config: config_pubkey,
-                                old_tip_receiver: tip_receiver.pubkey(),
-                                new_tip_receiver: Pubkey::new_unique(),
-                                block_builder: jito_tip_distribution::id(),
-                                tip_payment_account_0: tip_pdas[0].0,
-                                tip_payment_account_1: tip_pdas[1].0,
-                                tip_payment_account_2: tip_pdas[2].0,
-                                tip_payment_account_3: tip_pdas[3].0,
-                                tip_payment_account_4: tip_pdas[4].0,
-                                tip_payment_account_5: tip_pdas[5].0,
-                                tip_payment_account_6: tip_pdas[6].0,
-                                tip_payment_account_7: tip_pdas[7].0,
-                                signer: payer.pubkey(),
-                            }
-                            .to_account_metas(None),
+                            accounts: with_tip_accounts(
+                                jito_tip_payment::accounts::ChangeTipReceiver {
+                                    config: config_pubkey,
+                                    old_tip_receiver: tip_receiver.pubkey(),
+                                    new_tip_receiver: Pubkey::new_unique(),
+                                    block_builder: jito_tip_distribution::id(),
+                                    settlement_ledger: get_settlement_ledger_pda().0,
+                                    tip_receiver_stranded_tips: get_stranded_tips_pda(
+                                        &tip_receiver.pubkey(),
+                                    )
+                                    .0,
+                                    block_builder_stranded_tips: get_stranded_tips_pda(
+                                        &jito_tip_distribution::id(),
+                                    )
+                                    .0,
+                                    signer: payer.pubkey(),
+                                }
+                                .to_account_metas(None),
+                            ),
                         },
                     ],
                     Some(&payer.pubkey()),
@@ -1632,25 +1772,20 @@ mod tests {
     #[tokio::test]
     async fn test_change_block_builder_pay_tip_receiver_program() {
         let (config_pubkey, config_bump) =
-            Pubkey::find_program_address(&[&CONFIG_ACCOUNT_SEED], &jito_tip_payment::id());
+            Pubkey::find_program_address(&[CONFIG_ACCOUNT_SEED], &jito_tip_payment::id());
         let tip_pdas = get_tip_pdas();
 
         let block_builder = Keypair::new();
         let config = Config {
             tip_receiver: jito_tip_distribution::id(),
             block_builder: block_builder.pubkey(),
-            block_builder_commission_pct: 50,
+            block_builder_commission_bps: 5_000,
             bumps: InitBumps {
                 config: config_bump,
-                tip_payment_account_0: tip_pdas[0].1,
-                tip_payment_account_1: tip_pdas[1].1,
-                tip_payment_account_2: tip_pdas[2].1,
-                tip_payment_account_3: tip_pdas[3].1,
-                tip_payment_account_4: tip_pdas[4].1,
-                tip_payment_account_5: tip_pdas[5].1,
-                tip_payment_account_6: tip_pdas[6].1,
-                tip_payment_account_7: tip_pdas[7].1,
+                tip_payment_accounts: get_tip_bumps(),
             },
+            num_tip_accounts: NUM_LEGACY_TIP_ACCOUNTS as u8,
+            ..Config::default()
         };
         let initial_accounts = get_initial_accounts(config);
 
@@ -1678,22 +1813,25 @@ mod tests {
                                 block_builder_commission: 50,
                             }
                             .data(),
-                            accounts: jito_tip_payment::accounts::ChangeBlockBuilder {
-                                config: config_pubkey,
-                                tip_receiver: jito_tip_distribution::id(),
-                                old_block_builder: block_builder.pubkey(),
-                                new_block_builder: Pubkey::new_unique(),
-                                tip_payment_account_0: tip_pdas[0].0,
-                                tip_payment_account_1: tip_pdas[1].0,
-                                tip_payment_account_2: tip_pdas[2].0,
-                                tip_payment_account_3: tip_pdas[3].0,
-                                tip_payment_account_4: tip_pdas[4].0,
-                                tip_payment_account_5: tip_pdas[5].0,
-                                tip_payment_account_6: tip_pdas[6].0,
-                                tip_payment_account_7: tip_pdas[7].0,
-                                signer: payer.pubkey(),
-                            }
-                            .to_account_metas(None),
+                            accounts: with_tip_accounts(
+                                jito_tip_payment::accounts::ChangeBlockBuilder {
+                                    config: config_pubkey,
+                                    tip_receiver: jito_tip_distribution::id(),
+                                    old_block_builder: block_builder.pubkey(),
+                                    new_block_builder: Pubkey::new_unique(),
+                                    settlement_ledger: get_settlement_ledger_pda().0,
+                                    tip_receiver_stranded_tips: get_stranded_tips_pda(
+                                        &jito_tip_distribution::id(),
+                                    )
+                                    .0,
+                                    block_builder_stranded_tips: get_stranded_tips_pda(
+                                        &block_builder.pubkey(),
+                                    )
+                                    .0,
+                                    signer: payer.pubkey(),
+                                }
+                                .to_account_metas(None),
+                            ),
                         },
                     ],
                     Some(&payer.pubkey()),
@@ -1716,25 +1854,20 @@ mod tests {
     #[tokio::test]
     async fn test_change_block_builder_pay_block_builder_program() {
         let (config_pubkey, config_bump) =
-            Pubkey::find_program_address(&[&CONFIG_ACCOUNT_SEED], &jito_tip_payment::id());
+            Pubkey::find_program_address(&[CONFIG_ACCOUNT_SEED], &jito_tip_payment::id());
         let tip_pdas = get_tip_pdas();
 
         let tip_receiver = Keypair::new();
         let config = Config {
             tip_receiver: tip_receiver.pubkey(),
             block_builder: jito_tip_distribution::id(),
-            block_builder_commission_pct: 50,
+            block_builder_commission_bps: 5_000,
             bumps: InitBumps {
                 config: config_bump,
-                tip_payment_account_0: tip_pdas[0].1,
-                tip_payment_account_1: tip_pdas[1].1,
-                tip_payment_account_2: tip_pdas[2].1,
-                tip_payment_account_3: tip_pdas[3].1,
-                tip_payment_account_4: tip_pdas[4].1,
-                tip_payment_account_5: tip_pdas[5].1,
-                tip_payment_account_6: tip_pdas[6].1,
-                tip_payment_account_7: tip_pdas[7].1,
+                tip_payment_accounts: get_tip_bumps(),
             },
+            num_tip_accounts: NUM_LEGACY_TIP_ACCOUNTS as u8,
+            ..Config::default()
         };
         let initial_accounts = get_initial_accounts(config);
 
@@ -1762,22 +1895,25 @@ mod tests {
                                 block_builder_commission: 50,
                             }
                             .data(),
-                            accounts: jito_tip_payment::accounts::ChangeBlockBuilder {
-                                config: config_pubkey,
-                                tip_receiver: tip_receiver.pubkey(),
-                                old_block_builder: jito_tip_distribution::id(),
-                                new_block_builder: Pubkey::new_unique(),
-                                tip_payment_account_0: tip_pdas[0].0,
-                                tip_payment_account_1: tip_pdas[1].0,
-                                tip_payment_account_2: tip_pdas[2].0,
-                                tip_payment_account_3: tip_pdas[3].0,
-                                tip_payment_account_4: tip_pdas[4].0,
-                                tip_payment_account_5: tip_pdas[5].0,
-                                tip_payment_account_6: tip_pdas[6].0,
-                                tip_payment_account_7: tip_pdas[7].0,
-                                signer: payer.pubkey(),
-                            }
-                            .to_account_metas(None),
+                            accounts: with_tip_accounts(
+                                jito_tip_payment::accounts::ChangeBlockBuilder {
+                                    config: config_pubkey,
+                                    tip_receiver: tip_receiver.pubkey(),
+                                    old_block_builder: jito_tip_distribution::id(),
+                                    new_block_builder: Pubkey::new_unique(),
+                                    settlement_ledger: get_settlement_ledger_pda().0,
+                                    tip_receiver_stranded_tips: get_stranded_tips_pda(
+                                        &tip_receiver.pubkey(),
+                                    )
+                                    .0,
+                                    block_builder_stranded_tips: get_stranded_tips_pda(
+                                        &jito_tip_distribution::id(),
+                                    )
+                                    .0,
+                                    signer: payer.pubkey(),
+                                }
+                                .to_account_metas(None),
+                            ),
                         },
                     ],
                     Some(&payer.pubkey()),
@@ -1796,4 +1932,1922 @@ mod tests {
             .unwrap();
         assert_eq!(program_after.lamports - program_before.lamports, 0);
     }
+
+    fn add_tip_accounts_ix(
+        payer: &Pubkey,
+        authority: &Pubkey,
+        new_tip_accounts: &[Pubkey],
+    ) -> Instruction {
+        let mut accounts = jito_tip_payment::accounts::AddTipAccounts {
+            config: get_config_pda().0,
+            program_data: get_program_data_account(authority).0,
+            authority: *authority,
+            system_program: system_program::id(),
+            payer: *payer,
+        }
+        .to_account_metas(None);
+        accounts.extend(
+            new_tip_accounts
+                .iter()
+                .map(|pubkey| AccountMeta::new(*pubkey, false)),
+        );
+
+        Instruction {
+            program_id: jito_tip_payment::id(),
+            data: jito_tip_payment::instruction::AddTipAccounts {}.data(),
+            accounts,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_add_tip_accounts_ok() {
+        let upgrade_authority = Keypair::new();
+        let ProgramTestContext {
+            mut banks_client,
+            last_blockhash,
+            payer,
+            ..
+        } = get_test(&[get_program_data_account(&upgrade_authority.pubkey())]).await;
+        initialize_program(&mut banks_client, &payer, last_blockhash).await;
+
+        let new_tip_pdas: Vec<(Pubkey, u8)> = (NUM_LEGACY_TIP_ACCOUNTS..MAX_TIP_ACCOUNTS)
+            .map(|index| {
+                Pubkey::find_program_address(&tip_account_seeds(index), &jito_tip_payment::id())
+            })
+            .collect();
+        let new_tip_pubkeys: Vec<Pubkey> = new_tip_pdas.iter().map(|(pubkey, _)| *pubkey).collect();
+
+        banks_client
+            .process_transaction_with_commitment(
+                Transaction::new_signed_with_payer(
+                    &[add_tip_accounts_ix(
+                        &payer.pubkey(),
+                        &upgrade_authority.pubkey(),
+                        &new_tip_pubkeys,
+                    )],
+                    Some(&payer.pubkey()),
+                    &[&payer, &upgrade_authority],
+                    last_blockhash,
+                ),
+                CommitmentLevel::Processed,
+            )
+            .await
+            .unwrap();
+
+        let config = banks_client
+            .get_account_with_commitment(get_config_pda().0, CommitmentLevel::Processed)
+            .await
+            .unwrap()
+            .unwrap();
+        let config = Config::try_deserialize(&mut config.data()).unwrap();
+        assert_eq!(config.num_tip_accounts as usize, MAX_TIP_ACCOUNTS);
+        for (index, (pubkey, bump)) in (NUM_LEGACY_TIP_ACCOUNTS..).zip(new_tip_pdas) {
+            assert_eq!(config.bumps.tip_payment_accounts[index], bump);
+            let account = banks_client
+                .get_account_with_commitment(pubkey, CommitmentLevel::Processed)
+                .await
+                .unwrap()
+                .unwrap();
+            TipPaymentAccount::try_deserialize(&mut account.data.as_slice()).unwrap();
+        }
+
+        // tips in the new accounts are drained along with the legacy ones
+        let last_tip_pda = *new_tip_pubkeys.last().unwrap();
+        let mut change_tip_receiver_ix = Instruction {
+            program_id: jito_tip_payment::id(),
+            data: jito_tip_payment::instruction::ChangeTipReceiver {}.data(),
+            accounts: with_tip_accounts(
+                jito_tip_payment::accounts::ChangeTipReceiver {
+                    config: get_config_pda().0,
+                    old_tip_receiver: payer.pubkey(),
+                    new_tip_receiver: Pubkey::new_unique(),
+                    block_builder: payer.pubkey(),
+                    settlement_ledger: get_settlement_ledger_pda().0,
+                    tip_receiver_stranded_tips: get_stranded_tips_pda(&payer.pubkey()).0,
+                    block_builder_stranded_tips: get_stranded_tips_pda(&payer.pubkey()).0,
+                    signer: payer.pubkey(),
+                }
+                .to_account_metas(None),
+            ),
+        };
+        change_tip_receiver_ix.accounts.extend(
+            new_tip_pubkeys
+                .iter()
+                .map(|pubkey| AccountMeta::new(*pubkey, false)),
+        );
+        banks_client
+            .process_transaction_with_commitment(
+                Transaction::new_signed_with_payer(
+                    &[
+                        transfer(&payer.pubkey(), &last_tip_pda, 1_000_000_000),
+                        change_tip_receiver_ix,
+                    ],
+                    Some(&payer.pubkey()),
+                    &[&payer],
+                    last_blockhash,
+                ),
+                CommitmentLevel::Processed,
+            )
+            .await
+            .unwrap();
+
+        let last_tip_pda_balance = banks_client
+            .get_account_with_commitment(last_tip_pda, CommitmentLevel::Processed)
+            .await
+            .unwrap()
+            .unwrap()
+            .lamports;
+        assert_eq!(
+            last_tip_pda_balance,
+            Rent::default().minimum_balance(TipPaymentAccount::SIZE)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_add_tip_accounts_max_exceeded_fails() {
+        let upgrade_authority = Keypair::new();
+        let ProgramTestContext {
+            mut banks_client,
+            last_blockhash,
+            payer,
+            ..
+        } = get_test(&[get_program_data_account(&upgrade_authority.pubkey())]).await;
+        initialize_program(&mut banks_client, &payer, last_blockhash).await;
+
+        let new_tip_pubkeys: Vec<Pubkey> = (NUM_LEGACY_TIP_ACCOUNTS..=MAX_TIP_ACCOUNTS)
+            .map(|index| {
+                Pubkey::find_program_address(
+                    &tip_account_seeds(index.min(MAX_TIP_ACCOUNTS - 1)),
+                    &jito_tip_payment::id(),
+                )
+                .0
+            })
+            .collect();
+
+        let err = banks_client
+            .process_transaction(Transaction::new_signed_with_payer(
+                &[add_tip_accounts_ix(
+                    &payer.pubkey(),
+                    &upgrade_authority.pubkey(),
+                    &new_tip_pubkeys,
+                )],
+                Some(&payer.pubkey()),
+                &[&payer, &upgrade_authority],
+                last_blockhash,
+            ))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.unwrap(),
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(
+                    ERROR_CODE_OFFSET + TipPaymentError::MaxTipAccountsExceeded as u32
+                )
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_add_tip_accounts_not_upgrade_authority_fails() {
+        let upgrade_authority = Keypair::new();
+        let ProgramTestContext {
+            mut banks_client,
+            last_blockhash,
+            payer,
+            ..
+        } = get_test(&[get_program_data_account(&upgrade_authority.pubkey())]).await;
+        initialize_program(&mut banks_client, &payer, last_blockhash).await;
+
+        let new_tip_pda = Pubkey::find_program_address(
+            &tip_account_seeds(NUM_LEGACY_TIP_ACCOUNTS),
+            &jito_tip_payment::id(),
+        )
+        .0;
+        let err = banks_client
+            .process_transaction(Transaction::new_signed_with_payer(
+                &[add_tip_accounts_ix(
+                    &payer.pubkey(),
+                    &payer.pubkey(),
+                    &[new_tip_pda],
+                )],
+                Some(&payer.pubkey()),
+                &[&payer],
+                last_blockhash,
+            ))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.unwrap(),
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(ERROR_CODE_OFFSET + TipPaymentError::Unauthorized as u32)
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_change_tip_receiver_missing_tip_account_fails() {
+        let ProgramTestContext {
+            mut banks_client,
+            last_blockhash,
+            payer,
+            ..
+        } = get_test(&[]).await;
+        initialize_program(&mut banks_client, &payer, last_blockhash).await;
+
+        let mut change_tip_receiver_ix = Instruction {
+            program_id: jito_tip_payment::id(),
+            data: jito_tip_payment::instruction::ChangeTipReceiver {}.data(),
+            accounts: with_tip_accounts(
+                jito_tip_payment::accounts::ChangeTipReceiver {
+                    config: get_config_pda().0,
+                    old_tip_receiver: payer.pubkey(),
+                    new_tip_receiver: Pubkey::new_unique(),
+                    block_builder: payer.pubkey(),
+                    settlement_ledger: get_settlement_ledger_pda().0,
+                    tip_receiver_stranded_tips: get_stranded_tips_pda(&payer.pubkey()).0,
+                    block_builder_stranded_tips: get_stranded_tips_pda(&payer.pubkey()).0,
+                    signer: payer.pubkey(),
+                }
+                .to_account_metas(None),
+            ),
+        };
+        change_tip_receiver_ix.accounts.pop();
+
+        let err = banks_client
+            .process_transaction(Transaction::new_signed_with_payer(
+                &[change_tip_receiver_ix],
+                Some(&payer.pubkey()),
+                &[&payer],
+                last_blockhash,
+            ))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.unwrap(),
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(
+                    ERROR_CODE_OFFSET + TipPaymentError::InvalidTipAccounts as u32
+                )
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_change_tip_receiver_out_of_order_tip_accounts_fails() {
+        let ProgramTestContext {
+            mut banks_client,
+            last_blockhash,
+            payer,
+            ..
+        } = get_test(&[]).await;
+        initialize_program(&mut banks_client, &payer, last_blockhash).await;
+
+        let mut change_tip_receiver_ix = Instruction {
+            program_id: jito_tip_payment::id(),
+            data: jito_tip_payment::instruction::ChangeTipReceiver {}.data(),
+            accounts: with_tip_accounts(
+                jito_tip_payment::accounts::ChangeTipReceiver {
+                    config: get_config_pda().0,
+                    old_tip_receiver: payer.pubkey(),
+                    new_tip_receiver: Pubkey::new_unique(),
+                    block_builder: payer.pubkey(),
+                    settlement_ledger: get_settlement_ledger_pda().0,
+                    tip_receiver_stranded_tips: get_stranded_tips_pda(&payer.pubkey()).0,
+                    block_builder_stranded_tips: get_stranded_tips_pda(&payer.pubkey()).0,
+                    signer: payer.pubkey(),
+                }
+                .to_account_metas(None),
+            ),
+        };
+        let num_accounts = change_tip_receiver_ix.accounts.len();
+        change_tip_receiver_ix
+            .accounts
+            .swap(num_accounts - 1, num_accounts - 2);
+
+        let err = banks_client
+            .process_transaction(Transaction::new_signed_with_payer(
+                &[change_tip_receiver_ix],
+                Some(&payer.pubkey()),
+                &[&payer],
+                last_blockhash,
+            ))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.unwrap(),
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(
+                    ERROR_CODE_OFFSET + TipPaymentError::InvalidTipAccounts as u32
+                )
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_migrate_config_from_legacy_layout() {
+        const LEGACY_CONFIG_SIZE: usize = 8 + 32 + 32 + 8 + 9;
+
+        let (config_pubkey, config_bump) = get_config_pda();
+        let block_builder = Pubkey::new_unique();
+        let config = Config {
+            tip_receiver: Pubkey::new_unique(),
+            block_builder,
+            // the legacy layout stored the commission as a whole percentage
+            block_builder_commission_bps: 5,
+            bumps: InitBumps {
+                config: config_bump,
+                tip_payment_accounts: get_tip_bumps(),
+            },
+            ..Config::default()
+        };
+        let mut initial_accounts = get_initial_accounts(config);
+        let (_, legacy_config) = &mut initial_accounts[0];
+        legacy_config.data.truncate(LEGACY_CONFIG_SIZE);
+        legacy_config.lamports = Rent::default().minimum_balance(LEGACY_CONFIG_SIZE);
+
+        let upgrade_authority = Keypair::new();
+        initial_accounts.push(get_program_data_account(&upgrade_authority.pubkey()));
+        let ProgramTestContext {
+            banks_client,
+            last_blockhash,
+            payer,
+            ..
+        } = get_test(&initial_accounts).await;
+
+        banks_client
+            .process_transaction_with_commitment(
+                Transaction::new_signed_with_payer(
+                    &[Instruction {
+                        program_id: jito_tip_payment::id(),
+                        data: jito_tip_payment::instruction::MigrateConfig {}.data(),
+                        accounts: jito_tip_payment::accounts::MigrateConfig {
+                            config: config_pubkey,
+                            program_data: get_program_data_account(&upgrade_authority.pubkey()).0,
+                            authority: upgrade_authority.pubkey(),
+                            system_program: system_program::id(),
+                            payer: payer.pubkey(),
+                        }
+                        .to_account_metas(None),
+                    }],
+                    Some(&payer.pubkey()),
+                    &[&payer, &upgrade_authority],
+                    last_blockhash,
+                ),
+                CommitmentLevel::Processed,
+            )
+            .await
+            .unwrap();
+
+        let config_account = banks_client
+            .get_account_with_commitment(config_pubkey, CommitmentLevel::Processed)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(config_account.data.len(), Config::SIZE);
+        assert!(Rent::default().is_exempt(config_account.lamports, Config::SIZE));
+
+        let config = Config::try_deserialize(&mut config_account.data()).unwrap();
+        assert_eq!(config.block_builder, block_builder);
+        assert_eq!(config.block_builder_commission_bps, 500);
+        assert_eq!(config.version, CONFIG_VERSION);
+        assert_eq!(config.num_tip_accounts as usize, NUM_LEGACY_TIP_ACCOUNTS);
+        assert_eq!(config.bumps.tip_payment_accounts, get_tip_bumps());
+        assert_eq!(config.admin, None);
+        assert!(!config.claims_paused);
+        assert_eq!(config.max_block_builder_commission_bps, None);
+        assert!(config.block_builder_allow_list.is_empty());
+    }
+
+    async fn set_admin(
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        upgrade_authority: &Keypair,
+        admin: Option<Pubkey>,
+        blockhash: Hash,
+    ) {
+        let set_admin_ix = Instruction {
+            program_id: jito_tip_payment::id(),
+            data: jito_tip_payment::instruction::SetAdmin { admin }.data(),
+            accounts: jito_tip_payment::accounts::SetAdmin {
+                config: get_config_pda().0,
+                program_data: get_program_data_account(&upgrade_authority.pubkey()).0,
+                authority: upgrade_authority.pubkey(),
+            }
+            .to_account_metas(None),
+        };
+        banks_client
+            .process_transaction_with_commitment(
+                Transaction::new_signed_with_payer(
+                    &[set_admin_ix],
+                    Some(&payer.pubkey()),
+                    &[payer, upgrade_authority],
+                    blockhash,
+                ),
+                CommitmentLevel::Processed,
+            )
+            .await
+            .unwrap();
+    }
+
+    fn admin_update_config_ix(admin: &Pubkey, data: Vec<u8>) -> Instruction {
+        Instruction {
+            program_id: jito_tip_payment::id(),
+            data,
+            accounts: jito_tip_payment::accounts::AdminUpdateConfig {
+                config: get_config_pda().0,
+                admin: *admin,
+            }
+            .to_account_metas(None),
+        }
+    }
+
+    fn change_block_builder_bps_ix(
+        payer: &Pubkey,
+        new_block_builder: &Pubkey,
+        block_builder_commission_bps: u64,
+    ) -> Instruction {
+        Instruction {
+            program_id: jito_tip_payment::id(),
+            data: jito_tip_payment::instruction::ChangeBlockBuilderBps {
+                block_builder_commission_bps,
+            }
+            .data(),
+            accounts: with_tip_accounts(
+                jito_tip_payment::accounts::ChangeBlockBuilder {
+                    config: get_config_pda().0,
+                    tip_receiver: *payer,
+                    old_block_builder: *payer,
+                    new_block_builder: *new_block_builder,
+                    settlement_ledger: get_settlement_ledger_pda().0,
+                    tip_receiver_stranded_tips: get_stranded_tips_pda(payer).0,
+                    block_builder_stranded_tips: get_stranded_tips_pda(payer).0,
+                    signer: *payer,
+                }
+                .to_account_metas(None),
+            ),
+        }
+    }
+
+    /// The new recipients are passed, in order, after the accounts paying out the current ones.
+    fn change_block_builder_with_recipients_ix(
+        payer: &Pubkey,
+        new_block_builder: &Pubkey,
+        block_builder_recipients: &[BlockBuilderRecipient],
+    ) -> Instruction {
+        let mut accounts = with_tip_accounts(
+            jito_tip_payment::accounts::ChangeBlockBuilder {
+                config: get_config_pda().0,
+                tip_receiver: *payer,
+                old_block_builder: *payer,
+                new_block_builder: *new_block_builder,
+                settlement_ledger: get_settlement_ledger_pda().0,
+                tip_receiver_stranded_tips: get_stranded_tips_pda(payer).0,
+                block_builder_stranded_tips: get_stranded_tips_pda(payer).0,
+                signer: *payer,
+            }
+            .to_account_metas(None),
+        );
+        accounts.extend(
+            block_builder_recipients
+                .iter()
+                .map(|recipient| AccountMeta::new(recipient.recipient, false)),
+        );
+        Instruction {
+            program_id: jito_tip_payment::id(),
+            data: jito_tip_payment::instruction::ChangeBlockBuilderWithRecipients {
+                block_builder_commission_bps: 10_000,
+                block_builder_recipients: block_builder_recipients.to_vec(),
+            }
+            .data(),
+            accounts,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_set_admin_not_upgrade_authority_fails() {
+        let upgrade_authority = Keypair::new();
+        let ProgramTestContext {
+            mut banks_client,
+            last_blockhash,
+            payer,
+            ..
+        } = get_test(&[get_program_data_account(&upgrade_authority.pubkey())]).await;
+        initialize_program(&mut banks_client, &payer, last_blockhash).await;
+
+        let err = banks_client
+            .process_transaction(Transaction::new_signed_with_payer(
+                &[Instruction {
+                    program_id: jito_tip_payment::id(),
+                    data: jito_tip_payment::instruction::SetAdmin {
+                        admin: Some(payer.pubkey()),
+                    }
+                    .data(),
+                    accounts: jito_tip_payment::accounts::SetAdmin {
+                        config: get_config_pda().0,
+                        program_data: get_program_data_account(&upgrade_authority.pubkey()).0,
+                        authority: payer.pubkey(),
+                    }
+                    .to_account_metas(None),
+                }],
+                Some(&payer.pubkey()),
+                &[&payer],
+                last_blockhash,
+            ))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.unwrap(),
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(ERROR_CODE_OFFSET + TipPaymentError::Unauthorized as u32)
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_admin_update_config_not_admin_fails() {
+        let upgrade_authority = Keypair::new();
+        let ProgramTestContext {
+            mut banks_client,
+            last_blockhash,
+            payer,
+            ..
+        } = get_test(&[get_program_data_account(&upgrade_authority.pubkey())]).await;
+        initialize_program(&mut banks_client, &payer, last_blockhash).await;
+        set_admin(
+            &mut banks_client,
+            &payer,
+            &upgrade_authority,
+            Some(Pubkey::new_unique()),
+            last_blockhash,
+        )
+        .await;
+
+        let err = banks_client
+            .process_transaction(Transaction::new_signed_with_payer(
+                &[admin_update_config_ix(
+                    &payer.pubkey(),
+                    jito_tip_payment::instruction::SetClaimsPaused {
+                        claims_paused: true,
+                    }
+                    .data(),
+                )],
+                Some(&payer.pubkey()),
+                &[&payer],
+                last_blockhash,
+            ))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.unwrap(),
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(ERROR_CODE_OFFSET + TipPaymentError::Unauthorized as u32)
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_claims_paused() {
+        let upgrade_authority = Keypair::new();
+        let ProgramTestContext {
+            mut banks_client,
+            last_blockhash,
+            payer,
+            ..
+        } = get_test(&[get_program_data_account(&upgrade_authority.pubkey())]).await;
+        initialize_program(&mut banks_client, &payer, last_blockhash).await;
+        set_admin(
+            &mut banks_client,
+            &payer,
+            &upgrade_authority,
+            Some(payer.pubkey()),
+            last_blockhash,
+        )
+        .await;
+
+        let tip_pdas = get_tip_pdas();
+        let paused_tip_receiver = Pubkey::new_unique();
+        let next_tip_receiver = Pubkey::new_unique();
+        let change_tip_receiver_ix =
+            |old_tip_receiver: Pubkey, new_tip_receiver: Pubkey| Instruction {
+                program_id: jito_tip_payment::id(),
+                data: jito_tip_payment::instruction::ChangeTipReceiver {}.data(),
+                accounts: with_tip_accounts(
+                    jito_tip_payment::accounts::ChangeTipReceiver {
+                        config: get_config_pda().0,
+                        old_tip_receiver,
+                        new_tip_receiver,
+                        block_builder: payer.pubkey(),
+                        settlement_ledger: get_settlement_ledger_pda().0,
+                        tip_receiver_stranded_tips: get_stranded_tips_pda(&old_tip_receiver).0,
+                        block_builder_stranded_tips: get_stranded_tips_pda(&payer.pubkey()).0,
+                        signer: payer.pubkey(),
+                    }
+                    .to_account_metas(None),
+                ),
+            };
+        let claim_tips_ix = Instruction {
+            program_id: jito_tip_payment::id(),
+            data: jito_tip_payment::instruction::ClaimTips {}.data(),
+            accounts: with_tip_accounts(
+                jito_tip_payment::accounts::ClaimTips {
+                    config: get_config_pda().0,
+                    tip_receiver: paused_tip_receiver,
+                    block_builder: payer.pubkey(),
+                    settlement_ledger: get_settlement_ledger_pda().0,
+                    tip_receiver_stranded_tips: get_stranded_tips_pda(&paused_tip_receiver).0,
+                    block_builder_stranded_tips: get_stranded_tips_pda(&payer.pubkey()).0,
+                    signer: payer.pubkey(),
+                }
+                .to_account_metas(None),
+            ),
+        };
+
+        banks_client
+            .process_transaction_with_commitment(
+                Transaction::new_signed_with_payer(
+                    &[
+                        change_tip_receiver_ix(payer.pubkey(), paused_tip_receiver),
+                        admin_update_config_ix(
+                            &payer.pubkey(),
+                            jito_tip_payment::instruction::SetClaimsPaused {
+                                claims_paused: true,
+                            }
+                            .data(),
+                        ),
+                    ],
+                    Some(&payer.pubkey()),
+                    &[&payer],
+                    last_blockhash,
+                ),
+                CommitmentLevel::Processed,
+            )
+            .await
+            .unwrap();
+
+        let err = banks_client
+            .process_transaction_with_commitment(
+                Transaction::new_signed_with_payer(
+                    &[
+                        transfer(&payer.pubkey(), &tip_pdas[0].0, 1_000_000_000),
+                        claim_tips_ix.clone(),
+                    ],
+                    Some(&payer.pubkey()),
+                    &[&payer],
+                    last_blockhash,
+                ),
+                CommitmentLevel::Processed,
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.unwrap(),
+            TransactionError::InstructionError(
+                1,
+                InstructionError::Custom(ERROR_CODE_OFFSET + TipPaymentError::ClaimsPaused as u32)
+            )
+        );
+
+        // rotating the tip receiver while paused settles the tips to the receiver they were tipped to
+        banks_client
+            .process_transaction_with_commitment(
+                Transaction::new_signed_with_payer(
+                    &[
+                        transfer(&payer.pubkey(), &tip_pdas[0].0, 1_000_000_000),
+                        change_tip_receiver_ix(paused_tip_receiver, next_tip_receiver),
+                    ],
+                    Some(&payer.pubkey()),
+                    &[&payer],
+                    last_blockhash,
+                ),
+                CommitmentLevel::Processed,
+            )
+            .await
+            .unwrap();
+        let paused_tip_receiver_balance = banks_client
+            .get_account_with_commitment(paused_tip_receiver, CommitmentLevel::Processed)
+            .await
+            .unwrap()
+            .unwrap()
+            .lamports;
+        assert_eq!(paused_tip_receiver_balance, 1_000_000_000);
+        let config = banks_client
+            .get_account_with_commitment(get_config_pda().0, CommitmentLevel::Processed)
+            .await
+            .unwrap()
+            .unwrap();
+        let config = Config::try_deserialize(&mut config.data()).unwrap();
+        assert!(config.claims_paused);
+        assert_eq!(config.tip_receiver, next_tip_receiver);
+    }
+
+    #[tokio::test]
+    async fn test_max_block_builder_commission_bps() {
+        let upgrade_authority = Keypair::new();
+        let ProgramTestContext {
+            mut banks_client,
+            last_blockhash,
+            payer,
+            ..
+        } = get_test(&[get_program_data_account(&upgrade_authority.pubkey())]).await;
+        initialize_program(&mut banks_client, &payer, last_blockhash).await;
+        set_admin(
+            &mut banks_client,
+            &payer,
+            &upgrade_authority,
+            Some(payer.pubkey()),
+            last_blockhash,
+        )
+        .await;
+
+        let set_max_ix = admin_update_config_ix(
+            &payer.pubkey(),
+            jito_tip_payment::instruction::SetMaxBlockBuilderCommissionBps {
+                max_block_builder_commission_bps: Some(500),
+            }
+            .data(),
+        );
+        let err = banks_client
+            .process_transaction(Transaction::new_signed_with_payer(
+                &[
+                    set_max_ix.clone(),
+                    change_block_builder_bps_ix(&payer.pubkey(), &Pubkey::new_unique(), 600),
+                ],
+                Some(&payer.pubkey()),
+                &[&payer],
+                last_blockhash,
+            ))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.unwrap(),
+            TransactionError::InstructionError(
+                1,
+                InstructionError::Custom(ERROR_CODE_OFFSET + TipPaymentError::InvalidFee as u32)
+            )
+        );
+
+        let new_block_builder = Pubkey::new_unique();
+        banks_client
+            .process_transaction_with_commitment(
+                Transaction::new_signed_with_payer(
+                    &[
+                        set_max_ix,
+                        change_block_builder_bps_ix(&payer.pubkey(), &new_block_builder, 500),
+                    ],
+                    Some(&payer.pubkey()),
+                    &[&payer],
+                    last_blockhash,
+                ),
+                CommitmentLevel::Processed,
+            )
+            .await
+            .unwrap();
+
+        let config = banks_client
+            .get_account_with_commitment(get_config_pda().0, CommitmentLevel::Processed)
+            .await
+            .unwrap()
+            .unwrap();
+        let config = Config::try_deserialize(&mut config.data()).unwrap();
+        assert_eq!(config.block_builder, new_block_builder);
+        assert_eq!(config.block_builder_commission_bps, 500);
+        assert_eq!(config.max_block_builder_commission_bps, Some(500));
+    }
+
+    #[tokio::test]
+    async fn test_block_builder_allow_list() {
+        let upgrade_authority = Keypair::new();
+        let ProgramTestContext {
+            mut banks_client,
+            last_blockhash,
+            payer,
+            ..
+        } = get_test(&[get_program_data_account(&upgrade_authority.pubkey())]).await;
+        initialize_program(&mut banks_client, &payer, last_blockhash).await;
+        set_admin(
+            &mut banks_client,
+            &payer,
+            &upgrade_authority,
+            Some(payer.pubkey()),
+            last_blockhash,
+        )
+        .await;
+
+        let allowed_block_builder = Pubkey::new_unique();
+        let set_allow_list_ix = admin_update_config_ix(
+            &payer.pubkey(),
+            jito_tip_payment::instruction::SetBlockBuilderAllowList {
+                block_builder_allow_list: vec![allowed_block_builder],
+            }
+            .data(),
+        );
+        let err = banks_client
+            .process_transaction(Transaction::new_signed_with_payer(
+                &[
+                    set_allow_list_ix.clone(),
+                    change_block_builder_bps_ix(&payer.pubkey(), &Pubkey::new_unique(), 500),
+                ],
+                Some(&payer.pubkey()),
+                &[&payer],
+                last_blockhash,
+            ))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.unwrap(),
+            TransactionError::InstructionError(
+                1,
+                InstructionError::Custom(
+                    ERROR_CODE_OFFSET + TipPaymentError::BlockBuilderNotAllowed as u32
+                )
+            )
+        );
+
+        banks_client
+            .process_transaction(Transaction::new_signed_with_payer(
+                &[
+                    set_allow_list_ix,
+                    change_block_builder_bps_ix(&payer.pubkey(), &allowed_block_builder, 500),
+                ],
+                Some(&payer.pubkey()),
+                &[&payer],
+                last_blockhash,
+            ))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_change_tip_receiver_without_settlement_ledger_fails() {
+        let ProgramTestContext {
+            mut banks_client,
+            last_blockhash,
+            payer,
+            ..
+        } = get_test(&[]).await;
+        initialize_program(&mut banks_client, &payer, last_blockhash).await;
+
+        let change_tip_receiver_ix = |settlement_ledger| Instruction {
+            program_id: jito_tip_payment::id(),
+            data: jito_tip_payment::instruction::ChangeTipReceiver {}.data(),
+            accounts: with_tip_accounts(
+                jito_tip_payment::accounts::ChangeTipReceiver {
+                    config: get_config_pda().0,
+                    old_tip_receiver: payer.pubkey(),
+                    new_tip_receiver: Pubkey::new_unique(),
+                    block_builder: payer.pubkey(),
+                    settlement_ledger,
+                    tip_receiver_stranded_tips: get_stranded_tips_pda(&payer.pubkey()).0,
+                    block_builder_stranded_tips: get_stranded_tips_pda(&payer.pubkey()).0,
+                    signer: payer.pubkey(),
+                }
+                .to_account_metas(None),
+            ),
+        };
+
+        // the placeholder anchor passes for an omitted optional account
+        let err = banks_client
+            .process_transaction(Transaction::new_signed_with_payer(
+                &[change_tip_receiver_ix(jito_tip_payment::id())],
+                Some(&payer.pubkey()),
+                &[&payer],
+                last_blockhash,
+            ))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.unwrap(),
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(ErrorCode::AccountOwnedByWrongProgram as u32)
+            )
+        );
+
+        let err = banks_client
+            .process_transaction(Transaction::new_signed_with_payer(
+                &[change_tip_receiver_ix(Pubkey::new_unique())],
+                Some(&payer.pubkey()),
+                &[&payer],
+                last_blockhash,
+            ))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.unwrap(),
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(ErrorCode::AccountNotInitialized as u32)
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_claim_tips_ok() {
+        let ProgramTestContext {
+            mut banks_client,
+            last_blockhash,
+            payer,
+            ..
+        } = get_test(&[]).await;
+        initialize_program(&mut banks_client, &payer, last_blockhash).await;
+
+        let config_pda = get_config_pda();
+        let tip_pdas = get_tip_pdas();
+
+        let new_block_builder = Pubkey::new_unique();
+        let new_tip_receiver = Pubkey::new_unique();
+        banks_client
+            .process_transaction_with_commitment(
+                Transaction::new_signed_with_payer(
+                    &[
+                        change_block_builder_bps_ix(&payer.pubkey(), &new_block_builder, 1_250),
+                        Instruction {
+                            program_id: jito_tip_payment::id(),
+                            data: jito_tip_payment::instruction::ChangeTipReceiver {}.data(),
+                            accounts: with_tip_accounts(
+                                jito_tip_payment::accounts::ChangeTipReceiver {
+                                    config: config_pda.0,
+                                    old_tip_receiver: payer.pubkey(),
+                                    new_tip_receiver,
+                                    block_builder: new_block_builder,
+                                    settlement_ledger: get_settlement_ledger_pda().0,
+                                    tip_receiver_stranded_tips: get_stranded_tips_pda(
+                                        &payer.pubkey(),
+                                    )
+                                    .0,
+                                    block_builder_stranded_tips: get_stranded_tips_pda(
+                                        &new_block_builder,
+                                    )
+                                    .0,
+                                    signer: payer.pubkey(),
+                                }
+                                .to_account_metas(None),
+                            ),
+                        },
+                        transfer(&payer.pubkey(), &tip_pdas[0].0, 1_000_000_000),
+                    ],
+                    Some(&payer.pubkey()),
+                    &[&payer],
+                    last_blockhash,
+                ),
+                CommitmentLevel::Processed,
+            )
+            .await
+            .unwrap();
+
+        banks_client
+            .process_transaction_with_commitment(
+                Transaction::new_signed_with_payer(
+                    &[Instruction {
+                        program_id: jito_tip_payment::id(),
+                        data: jito_tip_payment::instruction::ClaimTips {}.data(),
+                        accounts: with_tip_accounts(
+                            jito_tip_payment::accounts::ClaimTips {
+                                config: config_pda.0,
+                                tip_receiver: new_tip_receiver,
+                                block_builder: new_block_builder,
+                                settlement_ledger: get_settlement_ledger_pda().0,
+                                tip_receiver_stranded_tips: get_stranded_tips_pda(
+                                    &new_tip_receiver,
+                                )
+                                .0,
+                                block_builder_stranded_tips: get_stranded_tips_pda(
+                                    &new_block_builder,
+                                )
+                                .0,
+                                signer: payer.pubkey(),
+                            }
+                            .to_account_metas(None),
+                        ),
+                    }],
+                    Some(&payer.pubkey()),
+                    &[&payer],
+                    last_blockhash,
+                ),
+                CommitmentLevel::Processed,
+            )
+            .await
+            .unwrap();
+
+        let tip_receiver_balance = banks_client
+            .get_account_with_commitment(new_tip_receiver, CommitmentLevel::Processed)
+            .await
+            .unwrap()
+            .unwrap()
+            .lamports;
+        assert_eq!(tip_receiver_balance, 875_000_000);
+        let block_builder_balance = banks_client
+            .get_account_with_commitment(new_block_builder, CommitmentLevel::Processed)
+            .await
+            .unwrap()
+            .unwrap()
+            .lamports;
+        assert_eq!(block_builder_balance, 125_000_000);
+
+        let config = banks_client
+            .get_account_with_commitment(config_pda.0, CommitmentLevel::Processed)
+            .await
+            .unwrap()
+            .unwrap();
+        let config = Config::try_deserialize(&mut config.data()).unwrap();
+        assert_eq!(config.tip_receiver, new_tip_receiver);
+        assert_eq!(config.block_builder, new_block_builder);
+        let settlement_ledger = get_settlement_ledger(&mut banks_client).await;
+        assert_eq!(settlement_ledger.num_settlements, 1);
+        let Settlement {
+            slot: _,
+            tip_receiver,
+            block_builder,
+            total_tips,
+            tip_receiver_amount,
+            block_builder_amount,
+        } = settlement_ledger.settlements[0];
+        assert_eq!(tip_receiver, new_tip_receiver);
+        assert_eq!(block_builder, new_block_builder);
+        assert_eq!(total_tips, 1_000_000_000);
+        assert_eq!(tip_receiver_amount, 875_000_000);
+        assert_eq!(block_builder_amount, 125_000_000);
+    }
+
+    #[tokio::test]
+    async fn test_claim_tips_different_tip_receiver_fails() {
+        let ProgramTestContext {
+            mut banks_client,
+            last_blockhash,
+            payer,
+            ..
+        } = get_test(&[]).await;
+        initialize_program(&mut banks_client, &payer, last_blockhash).await;
+
+        let err = banks_client
+            .process_transaction(Transaction::new_signed_with_payer(
+                &[Instruction {
+                    program_id: jito_tip_payment::id(),
+                    data: jito_tip_payment::instruction::ClaimTips {}.data(),
+                    accounts: with_tip_accounts(
+                        jito_tip_payment::accounts::ClaimTips {
+                            config: get_config_pda().0,
+                            tip_receiver: Pubkey::new_unique(),
+                            block_builder: payer.pubkey(),
+                            settlement_ledger: get_settlement_ledger_pda().0,
+                            tip_receiver_stranded_tips: Pubkey::new_unique(),
+                            block_builder_stranded_tips: get_stranded_tips_pda(&payer.pubkey()).0,
+                            signer: payer.pubkey(),
+                        }
+                        .to_account_metas(None),
+                    ),
+                }],
+                Some(&payer.pubkey()),
+                &[&payer],
+                last_blockhash,
+            ))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.unwrap(),
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(ErrorCode::ConstraintRaw as u32)
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_change_block_builder_with_recipients_split() {
+        let ProgramTestContext {
+            mut banks_client,
+            last_blockhash,
+            payer,
+            ..
+        } = get_test(&[]).await;
+        initialize_program(&mut banks_client, &payer, last_blockhash).await;
+
+        let config_pda = get_config_pda();
+        let tip_pdas = get_tip_pdas();
+
+        let new_block_builder = Pubkey::new_unique();
+        let recipients = [
+            BlockBuilderRecipient {
+                recipient: Pubkey::new_unique(),
+                weight: 1,
+            },
+            BlockBuilderRecipient {
+                recipient: Pubkey::new_unique(),
+                weight: 2,
+            },
+        ];
+        banks_client
+            .process_transaction_with_commitment(
+                Transaction::new_signed_with_payer(
+                    &[
+                        change_block_builder_with_recipients_ix(
+                            &payer.pubkey(),
+                            &new_block_builder,
+                            &recipients,
+                        ),
+                        transfer(&payer.pubkey(), &tip_pdas[0].0, 1_000_000_000),
+                    ],
+                    Some(&payer.pubkey()),
+                    &[&payer],
+                    last_blockhash,
+                ),
+                CommitmentLevel::Processed,
+            )
+            .await
+            .unwrap();
+
+        let claim_tips_accounts = with_tip_accounts(
+            jito_tip_payment::accounts::ClaimTips {
+                config: config_pda.0,
+                tip_receiver: payer.pubkey(),
+                block_builder: new_block_builder,
+                settlement_ledger: get_settlement_ledger_pda().0,
+                tip_receiver_stranded_tips: get_stranded_tips_pda(&payer.pubkey()).0,
+                block_builder_stranded_tips: get_stranded_tips_pda(&new_block_builder).0,
+                signer: payer.pubkey(),
+            }
+            .to_account_metas(None),
+        );
+
+        // the block builder recipients must follow the tip accounts
+        let err = banks_client
+            .process_transaction(Transaction::new_signed_with_payer(
+                &[Instruction {
+                    program_id: jito_tip_payment::id(),
+                    data: jito_tip_payment::instruction::ClaimTips {}.data(),
+                    accounts: claim_tips_accounts.clone(),
+                }],
+                Some(&payer.pubkey()),
+                &[&payer],
+                last_blockhash,
+            ))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.unwrap(),
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(
+                    ERROR_CODE_OFFSET + TipPaymentError::InvalidTipAccounts as u32
+                )
+            )
+        );
+
+        // each followed, in the same order, by their stranded tips accounts
+        let mut accounts = claim_tips_accounts.clone();
+        accounts.extend(
+            recipients
+                .iter()
+                .map(|recipient| AccountMeta::new(recipient.recipient, false)),
+        );
+        accounts.extend(recipients.iter().rev().map(|recipient| {
+            AccountMeta::new(get_stranded_tips_pda(&recipient.recipient).0, false)
+        }));
+        let err = banks_client
+            .process_transaction(Transaction::new_signed_with_payer(
+                &[Instruction {
+                    program_id: jito_tip_payment::id(),
+                    data: jito_tip_payment::instruction::ClaimTips {}.data(),
+                    accounts: accounts.clone(),
+                }],
+                Some(&payer.pubkey()),
+                &[&payer],
+                last_blockhash,
+            ))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.unwrap(),
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(
+                    ERROR_CODE_OFFSET + TipPaymentError::InvalidStrandedTipsAccount as u32
+                )
+            )
+        );
+
+        let mut accounts = claim_tips_accounts;
+        accounts.extend(
+            recipients
+                .iter()
+                .map(|recipient| AccountMeta::new(recipient.recipient, false)),
+        );
+        accounts.extend(recipients.iter().map(|recipient| {
+            AccountMeta::new(get_stranded_tips_pda(&recipient.recipient).0, false)
+        }));
+        banks_client
+            .process_transaction_with_commitment(
+                Transaction::new_signed_with_payer(
+                    &[Instruction {
+                        program_id: jito_tip_payment::id(),
+                        data: jito_tip_payment::instruction::ClaimTips {}.data(),
+                        accounts,
+                    }],
+                    Some(&payer.pubkey()),
+                    &[&payer],
+                    last_blockhash,
+                ),
+                CommitmentLevel::Processed,
+            )
+            .await
+            .unwrap();
+
+        // the rounding remainder goes to the first recipient
+        for (recipient, expected_balance) in recipients.iter().zip([333_333_334, 666_666_666]) {
+            let balance = banks_client
+                .get_account_with_commitment(recipient.recipient, CommitmentLevel::Processed)
+                .await
+                .unwrap()
+                .unwrap()
+                .lamports;
+            assert_eq!(balance, expected_balance);
+        }
+
+        let settlement_ledger = get_settlement_ledger(&mut banks_client).await;
+        assert_eq!(
+            settlement_ledger.settlements[0].block_builder,
+            new_block_builder
+        );
+        assert_eq!(
+            settlement_ledger.settlements[0].block_builder_amount,
+            1_000_000_000
+        );
+    }
+
+    #[tokio::test]
+    async fn test_change_block_builder_with_zero_weight_recipient_fails() {
+        let ProgramTestContext {
+            mut banks_client,
+            last_blockhash,
+            payer,
+            ..
+        } = get_test(&[]).await;
+        initialize_program(&mut banks_client, &payer, last_blockhash).await;
+
+        let err = banks_client
+            .process_transaction(Transaction::new_signed_with_payer(
+                &[Instruction {
+                    program_id: jito_tip_payment::id(),
+                    data: jito_tip_payment::instruction::ChangeBlockBuilderWithRecipients {
+                        block_builder_commission_bps: 500,
+                        block_builder_recipients: vec![BlockBuilderRecipient {
+                            recipient: Pubkey::new_unique(),
+                            weight: 0,
+                        }],
+                    }
+                    .data(),
+                    accounts: with_tip_accounts(
+                        jito_tip_payment::accounts::ChangeBlockBuilder {
+                            config: get_config_pda().0,
+                            tip_receiver: payer.pubkey(),
+                            old_block_builder: payer.pubkey(),
+                            new_block_builder: Pubkey::new_unique(),
+                            settlement_ledger: get_settlement_ledger_pda().0,
+                            tip_receiver_stranded_tips: get_stranded_tips_pda(&payer.pubkey()).0,
+                            block_builder_stranded_tips: get_stranded_tips_pda(&payer.pubkey()).0,
+                            signer: payer.pubkey(),
+                        }
+                        .to_account_metas(None),
+                    ),
+                }],
+                Some(&payer.pubkey()),
+                &[&payer],
+                last_blockhash,
+            ))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.unwrap(),
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(
+                    ERROR_CODE_OFFSET + TipPaymentError::InvalidBlockBuilderRecipients as u32
+                )
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_change_block_builder_with_recipients_reserved_accounts() {
+        let ProgramTestContext {
+            mut banks_client,
+            last_blockhash,
+            payer,
+            ..
+        } = get_test(&[]).await;
+        initialize_program(&mut banks_client, &payer, last_blockhash).await;
+
+        for reserved_account in ReservedAccountKeys::all_keys_iter() {
+            let recipients = [
+                BlockBuilderRecipient {
+                    recipient: Pubkey::new_unique(),
+                    weight: 1,
+                },
+                BlockBuilderRecipient {
+                    recipient: *reserved_account,
+                    weight: 1,
+                },
+            ];
+            let err = banks_client
+                .process_transaction(Transaction::new_signed_with_payer(
+                    &[change_block_builder_with_recipients_ix(
+                        &payer.pubkey(),
+                        &Pubkey::new_unique(),
+                        &recipients,
+                    )],
+                    Some(&payer.pubkey()),
+                    &[&payer],
+                    last_blockhash,
+                ))
+                .await
+                .unwrap_err();
+            assert_eq!(
+                err.unwrap(),
+                TransactionError::InstructionError(
+                    0,
+                    InstructionError::Custom(ErrorCode::ConstraintMut as u32) // reserved accounts are demoted to read locks
+                )
+            );
+        }
+
+        // the recipients must be passed as accounts
+        let recipients = [BlockBuilderRecipient {
+            recipient: Pubkey::new_unique(),
+            weight: 1,
+        }];
+        let mut change_block_builder_ix = change_block_builder_with_recipients_ix(
+            &payer.pubkey(),
+            &Pubkey::new_unique(),
+            &recipients,
+        );
+        change_block_builder_ix.accounts.pop();
+        let err = banks_client
+            .process_transaction(Transaction::new_signed_with_payer(
+                &[change_block_builder_ix],
+                Some(&payer.pubkey()),
+                &[&payer],
+                last_blockhash,
+            ))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.unwrap(),
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(
+                    ERROR_CODE_OFFSET + TipPaymentError::InvalidBlockBuilderRecipients as u32
+                )
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_change_block_builder_with_recipients_allow_list() {
+        let upgrade_authority = Keypair::new();
+        let ProgramTestContext {
+            mut banks_client,
+            last_blockhash,
+            payer,
+            ..
+        } = get_test(&[get_program_data_account(&upgrade_authority.pubkey())]).await;
+        initialize_program(&mut banks_client, &payer, last_blockhash).await;
+        set_admin(
+            &mut banks_client,
+            &payer,
+            &upgrade_authority,
+            Some(payer.pubkey()),
+            last_blockhash,
+        )
+        .await;
+
+        let allowed_block_builder = Pubkey::new_unique();
+        let set_allow_list_ix = admin_update_config_ix(
+            &payer.pubkey(),
+            jito_tip_payment::instruction::SetBlockBuilderAllowList {
+                block_builder_allow_list: vec![allowed_block_builder],
+            }
+            .data(),
+        );
+
+        // every recipient takes a cut of the commission, so each must be allowed
+        let recipients = [
+            BlockBuilderRecipient {
+                recipient: allowed_block_builder,
+                weight: 1,
+            },
+            BlockBuilderRecipient {
+                recipient: Pubkey::new_unique(),
+                weight: 1,
+            },
+        ];
+        let err = banks_client
+            .process_transaction(Transaction::new_signed_with_payer(
+                &[
+                    set_allow_list_ix.clone(),
+                    change_block_builder_with_recipients_ix(
+                        &payer.pubkey(),
+                        &allowed_block_builder,
+                        &recipients,
+                    ),
+                ],
+                Some(&payer.pubkey()),
+                &[&payer],
+                last_blockhash,
+            ))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.unwrap(),
+            TransactionError::InstructionError(
+                1,
+                InstructionError::Custom(
+                    ERROR_CODE_OFFSET + TipPaymentError::BlockBuilderNotAllowed as u32
+                )
+            )
+        );
+
+        banks_client
+            .process_transaction(Transaction::new_signed_with_payer(
+                &[
+                    set_allow_list_ix,
+                    change_block_builder_with_recipients_ix(
+                        &payer.pubkey(),
+                        &allowed_block_builder,
+                        &recipients[..1],
+                    ),
+                ],
+                Some(&payer.pubkey()),
+                &[&payer],
+                last_blockhash,
+            ))
+            .await
+            .unwrap();
+    }
+
+    fn get_stranded_tips_pda(recipient: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[STRANDED_TIPS_SEED, recipient.as_ref()],
+            &jito_tip_payment::id(),
+        )
+    }
+
+    fn claim_tips_ix(payer: &Pubkey, tip_receiver: &Pubkey) -> Instruction {
+        Instruction {
+            program_id: jito_tip_payment::id(),
+            data: jito_tip_payment::instruction::ClaimTips {}.data(),
+            accounts: with_tip_accounts(
+                jito_tip_payment::accounts::ClaimTips {
+                    config: get_config_pda().0,
+                    tip_receiver: *tip_receiver,
+                    block_builder: *payer,
+                    settlement_ledger: get_settlement_ledger_pda().0,
+                    tip_receiver_stranded_tips: get_stranded_tips_pda(tip_receiver).0,
+                    block_builder_stranded_tips: get_stranded_tips_pda(payer).0,
+                    signer: *payer,
+                }
+                .to_account_metas(None),
+            ),
+        }
+    }
+
+    fn claim_stranded_tips_ix(recipient: &Pubkey) -> Instruction {
+        Instruction {
+            program_id: jito_tip_payment::id(),
+            data: jito_tip_payment::instruction::ClaimStrandedTips {}.data(),
+            accounts: jito_tip_payment::accounts::ClaimStrandedTips {
+                stranded_tips: get_stranded_tips_pda(recipient).0,
+                recipient: *recipient,
+            }
+            .to_account_metas(None),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stranded_tips_ok() {
+        let ProgramTestContext {
+            mut banks_client,
+            last_blockhash,
+            payer,
+            ..
+        } = get_test(&[]).await;
+        initialize_program(&mut banks_client, &payer, last_blockhash).await;
+
+        let tip_pdas = get_tip_pdas();
+        let new_tip_receiver = Pubkey::new_unique();
+        let stranded_tips_pda = get_stranded_tips_pda(&new_tip_receiver).0;
+        banks_client
+            .process_transaction_with_commitment(
+                Transaction::new_signed_with_payer(
+                    &[
+                        Instruction {
+                            program_id: jito_tip_payment::id(),
+                            data: jito_tip_payment::instruction::InitializeStrandedTips {}.data(),
+                            accounts: jito_tip_payment::accounts::InitializeStrandedTips {
+                                stranded_tips: stranded_tips_pda,
+                                recipient: new_tip_receiver,
+                                system_program: system_program::id(),
+                                payer: payer.pubkey(),
+                            }
+                            .to_account_metas(None),
+                        },
+                        Instruction {
+                            program_id: jito_tip_payment::id(),
+                            data: jito_tip_payment::instruction::ChangeTipReceiver {}.data(),
+                            accounts: with_tip_accounts(
+                                jito_tip_payment::accounts::ChangeTipReceiver {
+                                    config: get_config_pda().0,
+                                    old_tip_receiver: payer.pubkey(),
+                                    new_tip_receiver,
+                                    block_builder: payer.pubkey(),
+                                    settlement_ledger: get_settlement_ledger_pda().0,
+                                    tip_receiver_stranded_tips: get_stranded_tips_pda(
+                                        &payer.pubkey(),
+                                    )
+                                    .0,
+                                    block_builder_stranded_tips: get_stranded_tips_pda(
+                                        &payer.pubkey(),
+                                    )
+                                    .0,
+                                    signer: payer.pubkey(),
+                                }
+                                .to_account_metas(None),
+                            ),
+                        },
+                        // not enough for the tip receiver to be rent exempt
+                        transfer(&payer.pubkey(), &tip_pdas[0].0, 1_000),
+                        claim_tips_ix(&payer.pubkey(), &new_tip_receiver),
+                    ],
+                    Some(&payer.pubkey()),
+                    &[&payer],
+                    last_blockhash,
+                ),
+                CommitmentLevel::Processed,
+            )
+            .await
+            .unwrap();
+
+        let stranded_tips = banks_client
+            .get_account_with_commitment(stranded_tips_pda, CommitmentLevel::Processed)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            stranded_tips.lamports,
+            Rent::default().minimum_balance(StrandedTips::SIZE) + 1_000
+        );
+        let stranded_tips = StrandedTips::try_deserialize(&mut stranded_tips.data()).unwrap();
+        assert_eq!(stranded_tips.recipient, new_tip_receiver);
+        assert_eq!(stranded_tips.amount, 1_000);
+
+        let err = banks_client
+            .process_transaction(Transaction::new_signed_with_payer(
+                &[claim_stranded_tips_ix(&new_tip_receiver)],
+                Some(&payer.pubkey()),
+                &[&payer],
+                last_blockhash,
+            ))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.unwrap(),
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(
+                    ERROR_CODE_OFFSET + TipPaymentError::RecipientNotRentExempt as u32
+                )
+            )
+        );
+
+        let rent_exempt_minimum = Rent::default().minimum_balance(0);
+        banks_client
+            .process_transaction_with_commitment(
+                Transaction::new_signed_with_payer(
+                    &[
+                        transfer(&payer.pubkey(), &tip_pdas[0].0, rent_exempt_minimum),
+                        claim_tips_ix(&payer.pubkey(), &new_tip_receiver),
+                        claim_stranded_tips_ix(&new_tip_receiver),
+                    ],
+                    Some(&payer.pubkey()),
+                    &[&payer],
+                    last_blockhash,
+                ),
+                CommitmentLevel::Processed,
+            )
+            .await
+            .unwrap();
+
+        let tip_receiver_balance = banks_client
+            .get_account_with_commitment(new_tip_receiver, CommitmentLevel::Processed)
+            .await
+            .unwrap()
+            .unwrap()
+            .lamports;
+        assert_eq!(tip_receiver_balance, rent_exempt_minimum + 1_000);
+        let stranded_tips = banks_client
+            .get_account_with_commitment(stranded_tips_pda, CommitmentLevel::Processed)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            stranded_tips.lamports,
+            Rent::default().minimum_balance(StrandedTips::SIZE)
+        );
+        let stranded_tips = StrandedTips::try_deserialize(&mut stranded_tips.data()).unwrap();
+        assert_eq!(stranded_tips.amount, 0);
+    }
+
+    #[tokio::test]
+    async fn test_block_builder_recipient_stranded_tips_ok() {
+        let ProgramTestContext {
+            mut banks_client,
+            last_blockhash,
+            payer,
+            ..
+        } = get_test(&[]).await;
+        initialize_program(&mut banks_client, &payer, last_blockhash).await;
+
+        let tip_pdas = get_tip_pdas();
+        let new_block_builder = Pubkey::new_unique();
+        let recipient = Pubkey::new_unique();
+        let stranded_tips_pda = get_stranded_tips_pda(&recipient).0;
+        let mut claim_tips_accounts = with_tip_accounts(
+            jito_tip_payment::accounts::ClaimTips {
+                config: get_config_pda().0,
+                tip_receiver: payer.pubkey(),
+                block_builder: new_block_builder,
+                settlement_ledger: get_settlement_ledger_pda().0,
+                tip_receiver_stranded_tips: get_stranded_tips_pda(&payer.pubkey()).0,
+                block_builder_stranded_tips: get_stranded_tips_pda(&new_block_builder).0,
+                signer: payer.pubkey(),
+            }
+            .to_account_metas(None),
+        );
+        claim_tips_accounts.extend([
+            AccountMeta::new(recipient, false),
+            AccountMeta::new(stranded_tips_pda, false),
+        ]);
+        banks_client
+            .process_transaction_with_commitment(
+                Transaction::new_signed_with_payer(
+                    &[
+                        Instruction {
+                            program_id: jito_tip_payment::id(),
+                            data: jito_tip_payment::instruction::InitializeStrandedTips {}.data(),
+                            accounts: jito_tip_payment::accounts::InitializeStrandedTips {
+                                stranded_tips: stranded_tips_pda,
+                                recipient,
+                                system_program: system_program::id(),
+                                payer: payer.pubkey(),
+                            }
+                            .to_account_metas(None),
+                        },
+                        change_block_builder_with_recipients_ix(
+                            &payer.pubkey(),
+                            &new_block_builder,
+                            &[BlockBuilderRecipient {
+                                recipient,
+                                weight: 1,
+                            }],
+                        ),
+                        // not enough for the recipient to be rent exempt
+                        transfer(&payer.pubkey(), &tip_pdas[0].0, 1_000),
+                        Instruction {
+                            program_id: jito_tip_payment::id(),
+                            data: jito_tip_payment::instruction::ClaimTips {}.data(),
+                            accounts: claim_tips_accounts,
+                        },
+                    ],
+                    Some(&payer.pubkey()),
+                    &[&payer],
+                    last_blockhash,
+                ),
+                CommitmentLevel::Processed,
+            )
+            .await
+            .unwrap();
+
+        let stranded_tips = banks_client
+            .get_account_with_commitment(stranded_tips_pda, CommitmentLevel::Processed)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            stranded_tips.lamports,
+            Rent::default().minimum_balance(StrandedTips::SIZE) + 1_000
+        );
+        let stranded_tips = StrandedTips::try_deserialize(&mut stranded_tips.data()).unwrap();
+        assert_eq!(stranded_tips.recipient, recipient);
+        assert_eq!(stranded_tips.amount, 1_000);
+    }
+
+    #[tokio::test]
+    async fn test_stranded_tips_program_recipient_fails() {
+        let ProgramTestContext {
+            mut banks_client,
+            last_blockhash,
+            payer,
+            ..
+        } = get_test(&[]).await;
+        initialize_program(&mut banks_client, &payer, last_blockhash).await;
+
+        let err = banks_client
+            .process_transaction(Transaction::new_signed_with_payer(
+                &[Instruction {
+                    program_id: jito_tip_payment::id(),
+                    data: jito_tip_payment::instruction::InitializeStrandedTips {}.data(),
+                    accounts: jito_tip_payment::accounts::InitializeStrandedTips {
+                        stranded_tips: get_stranded_tips_pda(&jito_tip_distribution::id()).0,
+                        recipient: jito_tip_distribution::id(),
+                        system_program: system_program::id(),
+                        payer: payer.pubkey(),
+                    }
+                    .to_account_metas(None),
+                }],
+                Some(&payer.pubkey()),
+                &[&payer],
+                last_blockhash,
+            ))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.unwrap(),
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(
+                    ERROR_CODE_OFFSET + TipPaymentError::InvalidStrandedTipsRecipient as u32
+                )
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stranded_tips_not_held_for_program() {
+        let (config_pubkey, config_bump) =
+            Pubkey::find_program_address(&[CONFIG_ACCOUNT_SEED], &jito_tip_payment::id());
+        let tip_pdas = get_tip_pdas();
+
+        let block_builder = Pubkey::new_unique();
+        let config = Config {
+            tip_receiver: jito_tip_distribution::id(),
+            block_builder,
+            bumps: InitBumps {
+                config: config_bump,
+                tip_payment_accounts: get_tip_bumps(),
+            },
+            num_tip_accounts: NUM_LEGACY_TIP_ACCOUNTS as u8,
+            ..Config::default()
+        };
+        let mut initial_accounts = get_initial_accounts(config);
+
+        // opened for the program before programs were rejected
+        let (stranded_tips_pda, stranded_tips_bump) =
+            get_stranded_tips_pda(&jito_tip_distribution::id());
+        let mut stranded_tips_data = Vec::new();
+        StrandedTips {
+            recipient: jito_tip_distribution::id(),
+            amount: 0,
+            bump: stranded_tips_bump,
+        }
+        .try_serialize(&mut stranded_tips_data)
+        .unwrap();
+        initial_accounts.push((
+            stranded_tips_pda,
+            Account {
+                lamports: Rent::default().minimum_balance(StrandedTips::SIZE),
+                data: stranded_tips_data,
+                owner: jito_tip_payment::id(),
+                executable: false,
+                rent_epoch: 0,
+            },
+        ));
+
+        let ProgramTestContext {
+            banks_client,
+            last_blockhash,
+            payer,
+            ..
+        } = get_test(&initial_accounts).await;
+
+        let tip_pda_before = banks_client
+            .get_account_with_commitment(tip_pdas[0].0, CommitmentLevel::Processed)
+            .await
+            .unwrap()
+            .unwrap()
+            .lamports;
+        banks_client
+            .process_transaction_with_commitment(
+                Transaction::new_signed_with_payer(
+                    &[
+                        transfer(&payer.pubkey(), &tip_pdas[1].0, 1_000_000),
+                        Instruction {
+                            program_id: jito_tip_payment::id(),
+                            data: jito_tip_payment::instruction::ClaimTips {}.data(),
+                            accounts: with_tip_accounts(
+                                jito_tip_payment::accounts::ClaimTips {
+                                    config: config_pubkey,
+                                    tip_receiver: jito_tip_distribution::id(),
+                                    block_builder,
+                                    settlement_ledger: get_settlement_ledger_pda().0,
+                                    tip_receiver_stranded_tips: stranded_tips_pda,
+                                    block_builder_stranded_tips: get_stranded_tips_pda(
+                                        &block_builder,
+                                    )
+                                    .0,
+                                    signer: payer.pubkey(),
+                                }
+                                .to_account_metas(None),
+                            ),
+                        },
+                    ],
+                    Some(&payer.pubkey()),
+                    &[&payer],
+                    last_blockhash,
+                ),
+                CommitmentLevel::Processed,
+            )
+            .await
+            .unwrap();
+
+        // the tips fall back to the first tip payment account instead
+        let stranded_tips = banks_client
+            .get_account_with_commitment(stranded_tips_pda, CommitmentLevel::Processed)
+            .await
+            .unwrap()
+            .unwrap();
+        let stranded_tips = StrandedTips::try_deserialize(&mut stranded_tips.data()).unwrap();
+        assert_eq!(stranded_tips.amount, 0);
+        let tip_pda_after = banks_client
+            .get_account_with_commitment(tip_pdas[0].0, CommitmentLevel::Processed)
+            .await
+            .unwrap()
+            .unwrap()
+            .lamports;
+        assert_eq!(tip_pda_after - tip_pda_before, 1_000_000);
+    }
+
+    #[tokio::test]
+    async fn test_stranded_tips_wrong_recipient_fails() {
+        let ProgramTestContext {
+            mut banks_client,
+            last_blockhash,
+            payer,
+            ..
+        } = get_test(&[]).await;
+        initialize_program(&mut banks_client, &payer, last_blockhash).await;
+
+        let recipient = Pubkey::new_unique();
+        let stranded_tips_pda = get_stranded_tips_pda(&recipient).0;
+        let err = banks_client
+            .process_transaction(Transaction::new_signed_with_payer(
+                &[
+                    Instruction {
+                        program_id: jito_tip_payment::id(),
+                        data: jito_tip_payment::instruction::InitializeStrandedTips {}.data(),
+                        accounts: jito_tip_payment::accounts::InitializeStrandedTips {
+                            stranded_tips: stranded_tips_pda,
+                            recipient,
+                            system_program: system_program::id(),
+                            payer: payer.pubkey(),
+                        }
+                        .to_account_metas(None),
+                    },
+                    Instruction {
+                        program_id: jito_tip_payment::id(),
+                        data: jito_tip_payment::instruction::ClaimTips {}.data(),
+                        accounts: with_tip_accounts(
+                            jito_tip_payment::accounts::ClaimTips {
+                                config: get_config_pda().0,
+                                tip_receiver: payer.pubkey(),
+                                block_builder: payer.pubkey(),
+                                settlement_ledger: get_settlement_ledger_pda().0,
+                                tip_receiver_stranded_tips: get_stranded_tips_pda(&payer.pubkey())
+                                    .0,
+                                // initialized, but held for someone else
+                                block_builder_stranded_tips: stranded_tips_pda,
+                                signer: payer.pubkey(),
+                            }
+                            .to_account_metas(None),
+                        ),
+                    },
+                ],
+                Some(&payer.pubkey()),
+                &[&payer],
+                last_blockhash,
+            ))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.unwrap(),
+            TransactionError::InstructionError(
+                1,
+                InstructionError::Custom(ErrorCode::ConstraintSeeds as u32)
+            )
+        );
+    }
 }
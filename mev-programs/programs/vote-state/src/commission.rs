@@ -0,0 +1,63 @@
+use anchor_lang::prelude::{borsh, AccountInfo, AnchorDeserialize, AnchorSerialize};
+
+use crate::VoteState;
+
+/// How the commission validators charge relates to the commission in their vote account.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum VoteCommissionPolicy {
+    /// Validators pick any commission up to the max commission.
+    #[default]
+    Unrestricted,
+
+    /// The commission can't exceed the commission in the validator's vote account.
+    Cap,
+
+    /// The commission is set to the commission in the validator's vote account, whatever the
+    /// validator asks for.
+    Mirror,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum VoteCommissionError {
+    /// The vote account's commission couldn't be read.
+    VoteAccount(anchor_lang::error::Error),
+    /// The requested commission exceeds the vote account's, under [VoteCommissionPolicy::Cap].
+    VoteAccountCommissionExceeded,
+    /// The commission exceeds the max commission.
+    MaxCommissionExceeded,
+}
+
+impl VoteCommissionPolicy {
+    /// The commission a validator charges on a new or updated distribution account, given the
+    /// one they asked for and the max commission, both in basis points.
+    pub fn validator_commission_bps(
+        self,
+        requested_commission_bps: u16,
+        max_commission_bps: u16,
+        validator_vote_account: &AccountInfo,
+    ) -> Result<u16, VoteCommissionError> {
+        let commission_bps = match self {
+            Self::Unrestricted => requested_commission_bps,
+            Self::Cap => {
+                if requested_commission_bps > vote_commission_bps(validator_vote_account)? {
+                    return Err(VoteCommissionError::VoteAccountCommissionExceeded);
+                }
+                requested_commission_bps
+            }
+            Self::Mirror => vote_commission_bps(validator_vote_account)?,
+        };
+
+        if commission_bps > max_commission_bps {
+            return Err(VoteCommissionError::MaxCommissionExceeded);
+        }
+
+        Ok(commission_bps)
+    }
+}
+
+/// The vote account's commission is a percentage, so it always fits in basis points.
+fn vote_commission_bps(validator_vote_account: &AccountInfo) -> Result<u16, VoteCommissionError> {
+    let commission = VoteState::deserialize_commission(validator_vote_account)
+        .map_err(VoteCommissionError::VoteAccount)?;
+    Ok(u16::from(commission).saturating_mul(100))
+}
@@ -1,21 +1,334 @@
+use std::{cell::Ref, fmt};
+
 use anchor_lang::{
     error::ErrorCode::{AccountDidNotDeserialize, ConstraintOwner},
     prelude::{AccountInfo, Pubkey, Result},
 };
-use bincode::deserialize;
+
+mod commission;
+
+pub use commission::{VoteCommissionError, VoteCommissionPolicy};
+
+/// The `node_pubkey` directly follows the `VoteStateVersions` discriminant in every version.
+const NODE_PUBKEY_OFFSET: usize = 4;
+
+/// The `authorized_withdrawer` follows the `node_pubkey` since V1_14_11.
+const AUTHORIZED_WITHDRAWER_OFFSET: usize = NODE_PUBKEY_OFFSET + 32;
+
+/// V0_23_5 has the `authorized_voter`, `authorized_voter_epoch` and `prior_voters` ahead of the
+/// `authorized_withdrawer`.
+const AUTHORIZED_WITHDRAWER_0_23_5_OFFSET: usize =
+    NODE_PUBKEY_OFFSET + 32 + 32 + 8 + PRIOR_VOTERS_0_23_5_LEN;
+
+/// Number of entries in a vote state's `prior_voters` circular buffer.
+const MAX_PRIOR_VOTERS: usize = 32;
+
+/// `(Pubkey, Epoch, Epoch, Slot)` entries followed by the `idx: usize` pointer.
+const PRIOR_VOTERS_0_23_5_LEN: usize = MAX_PRIOR_VOTERS * (32 + 8 + 8 + 8) + 8;
+
+/// `(Pubkey, Epoch, Epoch)` entries followed by the `idx: usize` pointer and `is_empty: bool`.
+const PRIOR_VOTERS_LEN: usize = MAX_PRIOR_VOTERS * (32 + 8 + 8) + 8 + 1;
+
+/// `Lockout { slot: u64, confirmation_count: u32 }`
+const LOCKOUT_LEN: usize = 8 + 4;
+
+/// `LandedVote { latency: u8, lockout: Lockout }`
+const LANDED_VOTE_LEN: usize = 1 + LOCKOUT_LEN;
+
+/// `(Epoch, Pubkey)` entries of the `AuthorizedVoters` map.
+const AUTHORIZED_VOTER_LEN: usize = 8 + 32;
+
+/// `(Epoch, credits, prev_credits)`
+const EPOCH_CREDITS_LEN: usize = 8 + 8 + 8;
+
+/// `BlockTimestamp { slot: u64, timestamp: i64 }`
+const LAST_TIMESTAMP_LEN: usize = 8 + 8;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VoteStateError {
+    /// The account isn't owned by the vote program.
+    InvalidOwner,
+    /// The `VoteStateVersions` discriminant isn't a known version.
+    UnknownVersion(u32),
+    /// The data ends before the vote state does, or holds an invalid value.
+    InvalidData,
+}
+
+impl fmt::Display for VoteStateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidOwner => write!(f, "account isn't owned by the vote program"),
+            Self::UnknownVersion(version) => write!(f, "unknown vote state version {version}"),
+            Self::InvalidData => write!(f, "invalid vote state data"),
+        }
+    }
+}
+
+impl std::error::Error for VoteStateError {}
+
+impl From<VoteStateError> for anchor_lang::error::Error {
+    fn from(e: VoteStateError) -> Self {
+        match e {
+            VoteStateError::InvalidOwner => ConstraintOwner.into(),
+            VoteStateError::UnknownVersion(_) | VoteStateError::InvalidData => {
+                AccountDidNotDeserialize.into()
+            }
+        }
+    }
+}
+
+/// The layouts of the `VoteStateVersions` enum the vote program stores.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VoteStateVersion {
+    V0_23_5,
+    V1_14_11,
+    Current,
+}
+
+/// Reads the fields of a bincode encoded `VoteStateVersions` in place, for any version. The
+/// layout is validated once by [VoteStateView::new], so reading fields can't fail.
+#[derive(Clone, Debug)]
+pub struct VoteStateView<'a> {
+    version: VoteStateVersion,
+    node_pubkey: &'a [u8],
+    authorized_withdrawer: &'a [u8],
+    commission: u8,
+    root_slot: Option<u64>,
+    authorized_voters: AuthorizedVoters<'a>,
+    epoch_credits: &'a [u8],
+}
+
+#[derive(Clone, Debug)]
+enum AuthorizedVoters<'a> {
+    /// V0_23_5 only tracks a single voter, authorized from `epoch`.
+    Single { epoch: u64, voter: &'a [u8] },
+    /// Entries of the `AuthorizedVoters` map, in ascending epoch order.
+    Map(&'a [u8]),
+}
+
+impl<'a> VoteStateView<'a> {
+    pub fn new(data: &'a [u8]) -> std::result::Result<Self, VoteStateError> {
+        let mut reader = Reader::new(data);
+
+        let version = match reader.read_u32()? {
+            0 => VoteStateVersion::V0_23_5,
+            1 => VoteStateVersion::V1_14_11,
+            2 => VoteStateVersion::Current,
+            version => return Err(VoteStateError::UnknownVersion(version)),
+        };
+        let node_pubkey = reader.take(32)?;
+
+        let (authorized_voters, authorized_withdrawer) = match version {
+            VoteStateVersion::V0_23_5 => {
+                let voter = reader.take(32)?;
+                let epoch = reader.read_u64()?;
+                reader.take(PRIOR_VOTERS_0_23_5_LEN)?;
+                (AuthorizedVoters::Single { epoch, voter }, reader.take(32)?)
+            }
+            // the authorized voters follow the votes and root slot, see below
+            VoteStateVersion::V1_14_11 | VoteStateVersion::Current => {
+                (AuthorizedVoters::Map(&[]), reader.take(32)?)
+            }
+        };
+        let commission = reader.read_u8()?;
+
+        let vote_len = match version {
+            VoteStateVersion::V0_23_5 | VoteStateVersion::V1_14_11 => LOCKOUT_LEN,
+            VoteStateVersion::Current => LANDED_VOTE_LEN,
+        };
+        reader.read_seq(vote_len)?;
+        let root_slot = match reader.read_u8()? {
+            0 => None,
+            1 => Some(reader.read_u64()?),
+            _ => return Err(VoteStateError::InvalidData),
+        };
+
+        let authorized_voters = match authorized_voters {
+            AuthorizedVoters::Map(_) => {
+                let authorized_voters =
+                    AuthorizedVoters::Map(reader.read_seq(AUTHORIZED_VOTER_LEN)?);
+                reader.take(PRIOR_VOTERS_LEN)?;
+                authorized_voters
+            }
+            single => single,
+        };
+
+        let epoch_credits = reader.read_seq(EPOCH_CREDITS_LEN)?;
+        reader.take(LAST_TIMESTAMP_LEN)?;
+
+        Ok(Self {
+            version,
+            node_pubkey,
+            authorized_withdrawer,
+            commission,
+            root_slot,
+            authorized_voters,
+            epoch_credits,
+        })
+    }
+
+    pub fn version(&self) -> VoteStateVersion {
+        self.version
+    }
+
+    pub fn node_pubkey(&self) -> Pubkey {
+        to_pubkey(self.node_pubkey)
+    }
+
+    pub fn authorized_withdrawer(&self) -> Pubkey {
+        to_pubkey(self.authorized_withdrawer)
+    }
+
+    /// Percentage (0-100) of inflation rewards the validator keeps.
+    pub fn commission(&self) -> u8 {
+        self.commission
+    }
+
+    pub fn root_slot(&self) -> Option<u64> {
+        self.root_slot
+    }
+
+    /// The authorized voters as `(epoch, voter)` pairs, each voter being authorized from its
+    /// epoch onwards, in ascending epoch order.
+    pub fn authorized_voters(&self) -> impl Iterator<Item = (u64, Pubkey)> + 'a {
+        let (single, map) = match self.authorized_voters {
+            AuthorizedVoters::Single { epoch, voter } => (Some((epoch, to_pubkey(voter))), &[][..]),
+            AuthorizedVoters::Map(map) => (None, map),
+        };
+        single.into_iter().chain(
+            map.chunks_exact(AUTHORIZED_VOTER_LEN)
+                .map(|entry| (to_u64(&entry[..8]), to_pubkey(&entry[8..]))),
+        )
+    }
+
+    /// The voter authorized for `epoch`, the same as the vote program's
+    /// `VoteState::get_authorized_voter`.
+    pub fn authorized_voter(&self, epoch: u64) -> Option<Pubkey> {
+        self.authorized_voters()
+            .take_while(|(voter_epoch, _)| *voter_epoch <= epoch)
+            .last()
+            .map(|(_, voter)| voter)
+    }
+
+    /// The credits history as `(epoch, credits, prev_credits)`, oldest first.
+    pub fn epoch_credits(&self) -> impl Iterator<Item = (u64, u64, u64)> + 'a {
+        self.epoch_credits
+            .chunks_exact(EPOCH_CREDITS_LEN)
+            .map(|entry| {
+                (
+                    to_u64(&entry[..8]),
+                    to_u64(&entry[8..16]),
+                    to_u64(&entry[16..]),
+                )
+            })
+    }
+}
 
 pub struct VoteState;
 
 impl VoteState {
+    /// Reads the node pubkey at its fixed offset, without decoding the rest of the vote state.
     pub fn deserialize_node_pubkey(account_info: &AccountInfo) -> Result<Pubkey> {
+        let data = Self::data(account_info)?;
+        let node_pubkey = data
+            .get(NODE_PUBKEY_OFFSET..NODE_PUBKEY_OFFSET + 32)
+            .ok_or(VoteStateError::InvalidData)?;
+        Ok(to_pubkey(node_pubkey))
+    }
+
+    /// The authority allowed to withdraw from the vote account, read at its offset in the account's
+    /// version. `None` if the version is unknown, so that callers can fall back to the node
+    /// pubkey, whose offset doesn't change.
+    pub fn deserialize_authorized_withdrawer(account_info: &AccountInfo) -> Result<Option<Pubkey>> {
+        let data = Self::data(account_info)?;
+        let version = data
+            .get(..NODE_PUBKEY_OFFSET)
+            .ok_or(VoteStateError::InvalidData)?;
+        let offset = match u32::from_le_bytes(version.try_into().unwrap()) {
+            0 => AUTHORIZED_WITHDRAWER_0_23_5_OFFSET,
+            1 | 2 => AUTHORIZED_WITHDRAWER_OFFSET,
+            _ => return Ok(None),
+        };
+        let authorized_withdrawer = data
+            .get(offset..)
+            .and_then(|data| data.get(..32))
+            .ok_or(VoteStateError::InvalidData)?;
+        Ok(Some(to_pubkey(authorized_withdrawer)))
+    }
+
+    /// See [VoteStateView::authorized_voters].
+    pub fn deserialize_authorized_voters(account_info: &AccountInfo) -> Result<Vec<(u64, Pubkey)>> {
+        Self::read(account_info, |view| view.authorized_voters().collect())
+    }
+
+    /// See [VoteStateView::commission].
+    pub fn deserialize_commission(account_info: &AccountInfo) -> Result<u8> {
+        Self::read(account_info, |view| view.commission())
+    }
+
+    /// Reads from the vote state of `account_info`, checking it's a vote account.
+    pub fn read<T>(account_info: &AccountInfo, f: impl FnOnce(&VoteStateView) -> T) -> Result<T> {
+        let data = Self::data(account_info)?;
+        Ok(f(&VoteStateView::new(&data)?))
+    }
+
+    /// Borrows the data of `account_info`, checking it's a vote account.
+    fn data<'a>(account_info: &'a AccountInfo) -> Result<Ref<'a, &'a mut [u8]>> {
         if Pubkey::from(account_info.owner.to_bytes())
             != Pubkey::from(solana_sdk_ids::vote::id().to_bytes())
         {
-            return Err(ConstraintOwner.into());
+            return Err(VoteStateError::InvalidOwner.into());
         }
 
-        // The first 4 bytes are the enumeration type and the next 32 bytes of the vote state are the node pubkey.
-        let data = account_info.data.borrow();
-        deserialize::<Pubkey>(&data[4..36]).map_err(|_| AccountDidNotDeserialize.into())
+        Ok(account_info.try_borrow_data()?)
+    }
+}
+
+/// Reads bincode encoded vote state fields in order.
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    fn take(&mut self, len: usize) -> std::result::Result<&'a [u8], VoteStateError> {
+        let (head, tail) = self
+            .data
+            .split_at_checked(len)
+            .ok_or(VoteStateError::InvalidData)?;
+        self.data = tail;
+        Ok(head)
+    }
+
+    fn read_u8(&mut self) -> std::result::Result<u8, VoteStateError> {
+        Ok(self.take(1)?[0])
     }
+
+    fn read_u32(&mut self) -> std::result::Result<u32, VoteStateError> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn read_u64(&mut self) -> std::result::Result<u64, VoteStateError> {
+        Ok(to_u64(self.take(8)?))
+    }
+
+    /// The items of a length prefixed sequence of `item_len` byte items.
+    fn read_seq(&mut self, item_len: usize) -> std::result::Result<&'a [u8], VoteStateError> {
+        let len = usize::try_from(self.read_u64()?).map_err(|_| VoteStateError::InvalidData)?;
+        self.take(
+            len.checked_mul(item_len)
+                .ok_or(VoteStateError::InvalidData)?,
+        )
+    }
+}
+
+fn to_u64(bytes: &[u8]) -> u64 {
+    u64::from_le_bytes(bytes.try_into().unwrap())
+}
+
+fn to_pubkey(bytes: &[u8]) -> Pubkey {
+    Pubkey::new_from_array(bytes.try_into().unwrap())
 }
@@ -0,0 +1,328 @@
+use anchor_lang::prelude::{AccountInfo, Pubkey};
+use jito_programs_vote_state::{
+    VoteCommissionError, VoteCommissionPolicy, VoteState, VoteStateError, VoteStateVersion,
+    VoteStateView,
+};
+use solana_vote_interface::state::{
+    LandedVote, Lockout, VoteInit, VoteState1_14_11, VoteStateVersions,
+};
+
+struct Keys {
+    node_pubkey: Pubkey,
+    authorized_voter: Pubkey,
+    authorized_withdrawer: Pubkey,
+}
+
+impl Keys {
+    fn new() -> Self {
+        Self {
+            node_pubkey: Pubkey::new_unique(),
+            authorized_voter: Pubkey::new_unique(),
+            authorized_withdrawer: Pubkey::new_unique(),
+        }
+    }
+
+    fn current(&self) -> solana_vote_interface::state::VoteState {
+        let mut vote_state = solana_vote_interface::state::VoteState::new(
+            &VoteInit {
+                node_pubkey: self.node_pubkey,
+                authorized_voter: self.authorized_voter,
+                authorized_withdrawer: self.authorized_withdrawer,
+                commission: 5,
+            },
+            &anchor_lang::prelude::Clock {
+                epoch: 7,
+                ..Default::default()
+            },
+        );
+        // variable length fields ahead of the authorized voters
+        for slot in 10..15 {
+            vote_state.votes.push_back(LandedVote {
+                latency: 1,
+                lockout: Lockout::new(slot),
+            });
+        }
+        vote_state.root_slot = Some(9);
+        vote_state.increment_credits(7, 3);
+        vote_state.increment_credits(8, 2);
+        vote_state
+            .set_new_authorized_voter(&self.node_pubkey, 7, 9, |_| Ok(()))
+            .unwrap();
+        vote_state
+    }
+
+    /// `VoteState0_23_5` isn't exported, so its bincode layout is written out by hand.
+    fn v0_23_5(&self) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend(0u32.to_le_bytes());
+        data.extend(self.node_pubkey.to_bytes());
+        data.extend(self.authorized_voter.to_bytes());
+        data.extend(7u64.to_le_bytes());
+        // prior_voters: 32 (Pubkey, Epoch, Epoch, Slot) entries and idx
+        data.extend([0; 32 * 56 + 8]);
+        data.extend(self.authorized_withdrawer.to_bytes());
+        data.push(5);
+        // votes, root_slot, epoch_credits and last_timestamp
+        data.extend(1u64.to_le_bytes());
+        data.extend(bincode::serialize(&Lockout::new(10)).unwrap());
+        data.push(0);
+        data.extend(1u64.to_le_bytes());
+        data.extend(7u64.to_le_bytes());
+        data.extend(3u64.to_le_bytes());
+        data.extend(0u64.to_le_bytes());
+        data.extend([0; 16]);
+        data
+    }
+}
+
+fn assert_view(keys: &Keys, data: &[u8], version: VoteStateVersion) {
+    let view = VoteStateView::new(data).unwrap();
+    assert_eq!(view.version(), version);
+    assert_eq!(view.node_pubkey(), keys.node_pubkey);
+    assert_eq!(view.authorized_withdrawer(), keys.authorized_withdrawer);
+    assert_eq!(view.commission(), 5);
+    assert_eq!(view.authorized_voter(6), None);
+    assert_eq!(view.authorized_voter(7), Some(keys.authorized_voter));
+
+    if version == VoteStateVersion::V0_23_5 {
+        assert_eq!(view.root_slot(), None);
+        assert_eq!(view.epoch_credits().collect::<Vec<_>>(), vec![(7, 3, 0)]);
+        assert_eq!(view.authorized_voter(9), Some(keys.authorized_voter));
+    } else {
+        assert_eq!(view.root_slot(), Some(9));
+        assert_eq!(
+            view.epoch_credits().collect::<Vec<_>>(),
+            vec![(7, 3, 0), (8, 5, 3)]
+        );
+        assert_eq!(view.authorized_voter(8), Some(keys.authorized_voter));
+        assert_eq!(view.authorized_voter(9), Some(keys.node_pubkey));
+    }
+}
+
+fn assert_decodes(keys: &Keys, mut data: Vec<u8>) {
+    let key = Pubkey::new_unique();
+    let owner = solana_sdk_ids::vote::id();
+    let mut lamports = 0;
+    let account_info = AccountInfo::new(
+        &key,
+        false,
+        false,
+        &mut lamports,
+        &mut data,
+        &owner,
+        false,
+        0,
+    );
+
+    assert_eq!(
+        VoteState::deserialize_node_pubkey(&account_info).unwrap(),
+        keys.node_pubkey
+    );
+    assert_eq!(
+        VoteState::deserialize_authorized_withdrawer(&account_info).unwrap(),
+        Some(keys.authorized_withdrawer)
+    );
+    assert_eq!(
+        VoteState::deserialize_authorized_voters(&account_info).unwrap()[0],
+        (7, keys.authorized_voter)
+    );
+    assert_eq!(VoteState::deserialize_commission(&account_info).unwrap(), 5);
+}
+
+#[test]
+fn test_decodes_current() {
+    let keys = Keys::new();
+    let data = bincode::serialize(&VoteStateVersions::new_current(keys.current())).unwrap();
+    assert_view(&keys, &data, VoteStateVersion::Current);
+    assert_decodes(&keys, data);
+}
+
+#[test]
+fn test_decodes_v1_14_11() {
+    let keys = Keys::new();
+    let data = bincode::serialize(&VoteStateVersions::V1_14_11(Box::new(
+        VoteState1_14_11::from(keys.current()),
+    )))
+    .unwrap();
+    assert_view(&keys, &data, VoteStateVersion::V1_14_11);
+    assert_decodes(&keys, data);
+}
+
+#[test]
+fn test_decodes_v0_23_5() {
+    let keys = Keys::new();
+    let data = keys.v0_23_5();
+
+    // matches what the vote program decodes
+    let VoteStateVersions::V0_23_5(vote_state) = bincode::deserialize(&data).unwrap() else {
+        panic!("expected a V0_23_5 vote state");
+    };
+    assert_eq!(vote_state.authorized_withdrawer, keys.authorized_withdrawer);
+
+    assert_view(&keys, &data, VoteStateVersion::V0_23_5);
+    assert_decodes(&keys, data);
+}
+
+#[test]
+fn test_rejects_invalid_accounts() {
+    let key = Pubkey::new_unique();
+    let mut lamports = 0;
+    let mut data =
+        bincode::serialize(&VoteStateVersions::new_current(Keys::new().current())).unwrap();
+
+    let owner = Pubkey::new_unique();
+    let account_info = AccountInfo::new(
+        &key,
+        false,
+        false,
+        &mut lamports,
+        &mut data,
+        &owner,
+        false,
+        0,
+    );
+    assert!(VoteState::deserialize_authorized_withdrawer(&account_info).is_err());
+
+    let owner = solana_sdk_ids::vote::id();
+    let mut truncated = data[..40].to_vec();
+    let account_info = AccountInfo::new(
+        &key,
+        false,
+        false,
+        &mut lamports,
+        &mut truncated,
+        &owner,
+        false,
+        0,
+    );
+    assert!(VoteState::deserialize_authorized_withdrawer(&account_info).is_err());
+    assert!(VoteState::deserialize_authorized_voters(&account_info).is_err());
+
+    assert_eq!(
+        VoteStateView::new(&truncated).unwrap_err(),
+        VoteStateError::InvalidData
+    );
+
+    let mut unknown_version = data.clone();
+    unknown_version[..4].copy_from_slice(&4u32.to_le_bytes());
+    assert_eq!(
+        VoteStateView::new(&unknown_version).unwrap_err(),
+        VoteStateError::UnknownVersion(4)
+    );
+    let account_info = AccountInfo::new(
+        &key,
+        false,
+        false,
+        &mut lamports,
+        &mut unknown_version,
+        &owner,
+        false,
+        0,
+    );
+    assert!(VoteState::deserialize_authorized_voters(&account_info).is_err());
+}
+
+#[test]
+fn test_node_pubkey_and_withdrawer_of_unknown_version() {
+    let keys = Keys::new();
+    let mut data = bincode::serialize(&VoteStateVersions::new_current(keys.current())).unwrap();
+    data[..4].copy_from_slice(&3u32.to_le_bytes());
+
+    let key = Pubkey::new_unique();
+    let owner = solana_sdk_ids::vote::id();
+    let mut lamports = 0;
+    let account_info = AccountInfo::new(
+        &key,
+        false,
+        false,
+        &mut lamports,
+        &mut data,
+        &owner,
+        false,
+        0,
+    );
+
+    // the node pubkey's offset is the same for every version
+    assert_eq!(
+        VoteState::deserialize_node_pubkey(&account_info).unwrap(),
+        keys.node_pubkey
+    );
+    // callers fall back to the node pubkey rather than guessing the withdrawer's offset
+    assert_eq!(
+        VoteState::deserialize_authorized_withdrawer(&account_info).unwrap(),
+        None
+    );
+    assert!(VoteState::deserialize_commission(&account_info).is_err());
+}
+
+#[test]
+fn test_vote_commission_policy() {
+    // the vote account's commission is 5%
+    let mut data =
+        bincode::serialize(&VoteStateVersions::new_current(Keys::new().current())).unwrap();
+    let key = Pubkey::new_unique();
+    let owner = solana_sdk_ids::vote::id();
+    let mut lamports = 0;
+    let account_info = AccountInfo::new(
+        &key,
+        false,
+        false,
+        &mut lamports,
+        &mut data,
+        &owner,
+        false,
+        0,
+    );
+
+    let policy = VoteCommissionPolicy::Unrestricted;
+    assert_eq!(
+        policy.validator_commission_bps(800, 1000, &account_info),
+        Ok(800)
+    );
+    assert_eq!(
+        policy.validator_commission_bps(1200, 1000, &account_info),
+        Err(VoteCommissionError::MaxCommissionExceeded)
+    );
+
+    let policy = VoteCommissionPolicy::Cap;
+    assert_eq!(
+        policy.validator_commission_bps(500, 1000, &account_info),
+        Ok(500)
+    );
+    assert_eq!(
+        policy.validator_commission_bps(501, 1000, &account_info),
+        Err(VoteCommissionError::VoteAccountCommissionExceeded)
+    );
+
+    let policy = VoteCommissionPolicy::Mirror;
+    assert_eq!(
+        policy.validator_commission_bps(0, 1000, &account_info),
+        Ok(500)
+    );
+    assert_eq!(
+        policy.validator_commission_bps(0, 400, &account_info),
+        Err(VoteCommissionError::MaxCommissionExceeded)
+    );
+
+    // Unrestricted never reads the vote account
+    let owner = Pubkey::new_unique();
+    let mut data = vec![];
+    let account_info = AccountInfo::new(
+        &key,
+        false,
+        false,
+        &mut lamports,
+        &mut data,
+        &owner,
+        false,
+        0,
+    );
+    assert_eq!(
+        VoteCommissionPolicy::Unrestricted.validator_commission_bps(800, 1000, &account_info),
+        Ok(800)
+    );
+    assert!(matches!(
+        VoteCommissionPolicy::Mirror.validator_commission_bps(0, 1000, &account_info),
+        Err(VoteCommissionError::VoteAccount(_))
+    ));
+}
@@ -1018,6 +1018,108 @@ describe("tests priority_fee_distribution", () => {
     );
   });
 
+  it("#update_merkle_root_upload_authority happy path", async () => {
+    const {
+      validatorVoteAccount,
+      maxValidatorCommissionBps,
+      priorityFeeDistributionAccount,
+      validatorIdentityKeypair,
+      bump,
+    } = await setup_initTipDistributionAccount();
+    await call_initTipDistributionAccount({
+      validatorCommissionBps: maxValidatorCommissionBps,
+      config: configAccount,
+      validatorIdentityKeypair,
+      systemProgram: SystemProgram.programId,
+      merkleRootUploadAuthority: validatorVoteAccount.publicKey,
+      validatorVoteAccount,
+      priorityFeeDistributionAccount,
+      bump,
+    });
+
+    const newAuthority = anchor.web3.Keypair.generate().publicKey;
+    const updateAuthority = (signer) =>
+      priorityFeeDistribution.methods
+        .updateMerkleRootUploadAuthority(newAuthority)
+        .accounts({
+          priorityFeeDistributionAccount,
+          validatorVoteAccount: validatorVoteAccount.publicKey,
+          signer: signer.publicKey,
+        })
+        .signers([signer])
+        .rpc();
+
+    try {
+      await updateAuthority(await generateAccount(LAMPORTS_PER_SOL));
+      assert.fail("expected exception to be thrown");
+    } catch (e) {
+      const err: AnchorError = e;
+      assert(err.error.errorCode.code === "Unauthorized");
+    }
+
+    await updateAuthority(validatorIdentityKeypair);
+    const account = await priorityFeeDistribution.account.priorityFeeDistributionAccount.fetch(
+      priorityFeeDistributionAccount,
+    );
+    assert.equal(
+      account.merkleRootUploadAuthority.toString(),
+      newAuthority.toString(),
+    );
+  });
+
+  it("#init_priority_fee_distribution_account follows the config's vote_commission_policy", async () => {
+    const currentConfig = await priorityFeeDistribution.account.config.fetch(
+      configAccount
+    );
+    const updateConfig = (voteCommissionPolicy) =>
+      priorityFeeDistribution.methods
+        .updateConfig({ ...currentConfig, voteCommissionPolicy })
+        .accounts({ config: configAccount, authority: currentConfig.authority })
+        .signers([authority])
+        .rpc();
+    const initDistributionAccount = async (validatorCommissionBps) => {
+      const {
+        validatorVoteAccount,
+        priorityFeeDistributionAccount,
+        validatorIdentityKeypair,
+        bump,
+      } = await setup_initTipDistributionAccount();
+      await call_initTipDistributionAccount({
+        validatorCommissionBps,
+        config: configAccount,
+        validatorIdentityKeypair,
+        systemProgram: SystemProgram.programId,
+        merkleRootUploadAuthority: validatorVoteAccount.publicKey,
+        validatorVoteAccount,
+        priorityFeeDistributionAccount,
+        bump,
+      });
+      return priorityFeeDistributionAccount;
+    };
+
+    // the vote accounts are created with a 0% commission
+    await updateConfig({ cap: {} });
+    try {
+      await initDistributionAccount(1);
+      assert.fail("expected exception to be thrown");
+    } catch (e) {
+      const err: AnchorError = e;
+      assert(err.error.errorCode.code === "VoteAccountCommissionExceeded");
+    }
+
+    await updateConfig({ mirror: {} });
+    const priorityFeeDistributionAccount = await initDistributionAccount(
+      currentConfig.maxValidatorCommissionBps
+    );
+    const account =
+      await priorityFeeDistribution.account.priorityFeeDistributionAccount.fetch(
+        priorityFeeDistributionAccount
+      );
+    assert.equal(account.validatorCommissionBps, 0);
+
+    await updateConfig({ unrestricted: {} });
+  });
+
   it("#migrate_tda_merkle_root_upload_authority happy path", async () => {
     const {
       validatorVoteAccount,
@@ -1230,6 +1332,79 @@ describe("tests priority_fee_distribution", () => {
     );
   });
 
+  it("#attest_priority_fee_revenue records the validator's revenue and committed share", async () => {
+    const {
+      validatorVoteAccount,
+      maxValidatorCommissionBps,
+      priorityFeeDistributionAccount,
+      validatorIdentityKeypair,
+      bump,
+    } = await setup_initTipDistributionAccount();
+    await call_initTipDistributionAccount({
+      validatorCommissionBps: maxValidatorCommissionBps,
+      config: configAccount,
+      validatorIdentityKeypair,
+      systemProgram: SystemProgram.programId,
+      merkleRootUploadAuthority: validatorVoteAccount.publicKey,
+      validatorVoteAccount,
+      priorityFeeDistributionAccount,
+      bump,
+    });
+
+    const randomSigner = await generateAccount(LAMPORTS_PER_SOL);
+    try {
+      await priorityFeeDistribution.methods
+        .attestPriorityFeeRevenue(new anchor.BN(LAMPORTS_PER_SOL), 5_000)
+        .accounts({
+          priorityFeeDistributionAccount,
+          validatorVoteAccount: validatorVoteAccount.publicKey,
+          signer: randomSigner.publicKey,
+        })
+        .signers([randomSigner])
+        .rpc();
+      assert.fail("expected exception to be thrown");
+    } catch (e) {
+      const err: AnchorError = e;
+      assert(err.error.errorCode.code === "Unauthorized");
+    }
+
+    try {
+      await priorityFeeDistribution.methods
+        .attestPriorityFeeRevenue(new anchor.BN(LAMPORTS_PER_SOL), 10_001)
+        .accounts({
+          priorityFeeDistributionAccount,
+          validatorVoteAccount: validatorVoteAccount.publicKey,
+          signer: validatorIdentityKeypair.publicKey,
+        })
+        .signers([validatorIdentityKeypair])
+        .rpc();
+      assert.fail("expected exception to be thrown");
+    } catch (e) {
+      const err: AnchorError = e;
+      assert(err.error.errorCode.code === "AccountValidationFailure");
+    }
+
+    await priorityFeeDistribution.methods
+      .attestPriorityFeeRevenue(new anchor.BN(LAMPORTS_PER_SOL), 5_000)
+      .accounts({
+        priorityFeeDistributionAccount,
+        validatorVoteAccount: validatorVoteAccount.publicKey,
+        signer: validatorIdentityKeypair.publicKey,
+      })
+      .signers([validatorIdentityKeypair])
+      .rpc();
+
+    const actual =
+      await priorityFeeDistribution.account.priorityFeeDistributionAccount.fetch(
+        priorityFeeDistributionAccount
+      );
+    assert.equal(
+      actual.priorityFeeRevenueAttestation.priorityFeeRevenueLamports.toString(),
+      LAMPORTS_PER_SOL.toString()
+    );
+    assert.equal(actual.priorityFeeRevenueAttestation.committedShareBps, 5_000);
+  });
+
   describe("After go_live_epoch", async () => {
     before(async () => {
       // Update Config.go_live_epoch to use current epoch
@@ -1341,6 +1516,104 @@ describe("tests priority_fee_distribution", () => {
         assert(err.error.errorCode.code === "AccountValidationFailure");
       }
     });
+
+    it("#transfer_priorty_fee_tips with strict_transfer_source only accepts the validator identity", async () => {
+      const currentConfig = await priorityFeeDistribution.account.config.fetch(
+        configAccount
+      );
+      await priorityFeeDistribution.methods
+        .updateConfig({ ...currentConfig, strictTransferSource: true })
+        .accounts({ config: configAccount, authority: currentConfig.authority })
+        .signers([authority])
+        .rpc();
+
+      const randomPayer = await generateAccount(10 * LAMPORTS_PER_SOL);
+      await sleepForEpochs(1);
+      const {
+        validatorVoteAccount,
+        maxValidatorCommissionBps,
+        priorityFeeDistributionAccount,
+        validatorIdentityKeypair,
+        bump,
+      } = await setup_initTipDistributionAccount();
+      await call_initTipDistributionAccount({
+        validatorCommissionBps: maxValidatorCommissionBps,
+        config: configAccount,
+        validatorIdentityKeypair,
+        systemProgram: SystemProgram.programId,
+        merkleRootUploadAuthority: validatorVoteAccount.publicKey,
+        validatorVoteAccount,
+        priorityFeeDistributionAccount,
+        bump,
+      });
+
+      const lamportsToTransfer = 0.1 * LAMPORTS_PER_SOL;
+      try {
+        await priorityFeeDistribution.methods
+          .transferPriorityFeeTips(new anchor.BN(lamportsToTransfer))
+          .accounts({
+            config: configAccount,
+            priorityFeeDistributionAccount,
+            from: randomPayer.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            validatorVoteAccount: validatorVoteAccount.publicKey,
+          })
+          .signers([randomPayer])
+          .rpc();
+        assert.fail("expected exception to be thrown");
+      } catch (e) {
+        const err: AnchorError = e;
+        assert(err.error.errorCode.code === "Unauthorized");
+      }
+
+      try {
+        await priorityFeeDistribution.methods
+          .transferPriorityFeeTips(new anchor.BN(lamportsToTransfer))
+          .accounts({
+            config: configAccount,
+            priorityFeeDistributionAccount,
+            from: validatorIdentityKeypair.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([validatorIdentityKeypair])
+          .rpc();
+        assert.fail("expected exception to be thrown");
+      } catch (e) {
+        const err: AnchorError = e;
+        assert(err.error.errorCode.code === "AccountValidationFailure");
+      }
+
+      const distributionAccountInfoBefore =
+        await priorityFeeDistribution.provider.connection.getAccountInfo(
+          priorityFeeDistributionAccount
+        );
+      await priorityFeeDistribution.methods
+        .transferPriorityFeeTips(new anchor.BN(lamportsToTransfer))
+        .accounts({
+          config: configAccount,
+          priorityFeeDistributionAccount,
+          from: validatorIdentityKeypair.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          validatorVoteAccount: validatorVoteAccount.publicKey,
+        })
+        .signers([validatorIdentityKeypair])
+        .rpc();
+      const distributionAccountInfoAfter =
+        await priorityFeeDistribution.provider.connection.getAccountInfo(
+          priorityFeeDistributionAccount
+        );
+      assert.equal(
+        distributionAccountInfoAfter.lamports -
+        distributionAccountInfoBefore.lamports,
+        lamportsToTransfer
+      );
+
+      await priorityFeeDistribution.methods
+        .updateConfig({ ...currentConfig, strictTransferSource: false })
+        .accounts({ config: configAccount, authority: currentConfig.authority })
+        .signers([authority])
+        .rpc();
+    });
   });
 });
 
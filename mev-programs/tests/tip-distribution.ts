@@ -16,6 +16,7 @@ const CONFIG_ACCOUNT_SEED = "CONFIG_ACCOUNT";
 const TIP_DISTRIBUTION_ACCOUNT_LEN = 168;
 const CLAIM_STATUS_SEED = "CLAIM_STATUS";
 const CLAIM_STATUS_LEN = 104;
+const CLAIM_BITMAP_SEED = "CLAIM_BITMAP";
 const ROOT_UPLOAD_CONFIG_SEED = "ROOT_UPLOAD_CONFIG";
 const JITO_MERKLE_UPLOAD_AUTHORITY = new anchor.web3.PublicKey(
     "GZctHpWXmsZC1YHACTGGcHhYxjdRqQvTpYkb9LMvxDib",
@@ -924,6 +925,580 @@ describe("tests tip_distribution", () => {
         }
     });
 
+    it("#claim_batch happy path", async () => {
+        const {
+            amount0,
+            amount1,
+            preBalance0,
+            tipDistributionAccount,
+            tree,
+            user0,
+            user1,
+            validatorVoteAccount,
+        } = await setupWithUploadedMerkleRoot();
+
+        const claims = [user0, user1].map((claimant, index) => {
+            const [claimStatus] = anchor.web3.PublicKey.findProgramAddressSync(
+                [
+                    Buffer.from(CLAIM_STATUS_SEED, "utf8"),
+                    claimant.publicKey.toBuffer(),
+                    tipDistributionAccount.toBuffer(),
+                ],
+                tipDistribution.programId,
+            );
+            return {
+                claimStatus,
+                claim: {
+                    claimant: claimant.publicKey,
+                    amount: new anchor.BN([amount0, amount1][index]),
+                    proof: convertBufProofToNumber(tree.getProof(index)),
+                },
+            };
+        });
+        const remainingAccounts = claims.flatMap(({claimStatus, claim}) => [
+            {pubkey: claimStatus, isSigner: false, isWritable: true},
+            {pubkey: claim.claimant, isSigner: false, isWritable: true},
+        ]);
+
+        await tipDistribution.methods
+            .claimBatch(claims.map(({claim}) => claim))
+            .accounts({
+                config: configAccount,
+                tipDistributionAccount,
+                merkleRootUploadAuthority: validatorVoteAccount.publicKey,
+                payer: user1.publicKey,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            })
+            .remainingAccounts(remainingAccounts)
+            .signers([user1, validatorVoteAccount])
+            .rpc();
+
+        const user0Info = await tipDistribution.provider.connection.getAccountInfo(
+            user0.publicKey,
+        );
+        assert.equal(user0Info.lamports, preBalance0 + amount0);
+        const tda = await tipDistribution.account.tipDistributionAccount.fetch(
+            tipDistributionAccount,
+        );
+        assert.equal(
+            tda.merkleRoot.totalFundsClaimed.toNumber(),
+            amount0 + amount1,
+        );
+        assert.equal(tda.merkleRoot.numNodesClaimed.toNumber(), 2);
+
+        try {
+            await tipDistribution.methods
+                .claimBatch([claims[0].claim])
+                .accounts({
+                    config: configAccount,
+                    tipDistributionAccount,
+                    merkleRootUploadAuthority: validatorVoteAccount.publicKey,
+                    payer: user1.publicKey,
+                    systemProgram: anchor.web3.SystemProgram.programId,
+                })
+                .remainingAccounts(remainingAccounts.slice(0, 2))
+                .signers([user1, validatorVoteAccount])
+                .rpc();
+            assert.fail("expected exception to be thrown");
+        } catch (e) {
+            const err: AnchorError = e;
+            assert(err.error.errorCode.code === "FundsAlreadyClaimed");
+        }
+    });
+
+    it("#claim_batch fails with a non-canonical claim status", async () => {
+        const {amount0, tipDistributionAccount, tree, user0, user1, validatorVoteAccount} =
+            await setupWithUploadedMerkleRoot();
+
+        const seeds = [
+            Buffer.from(CLAIM_STATUS_SEED, "utf8"),
+            user0.publicKey.toBuffer(),
+            tipDistributionAccount.toBuffer(),
+        ];
+        const [, canonicalBump] = anchor.web3.PublicKey.findProgramAddressSync(
+            seeds,
+            tipDistribution.programId,
+        );
+        // the next bump down that also derives a valid PDA
+        let bump = canonicalBump;
+        let claimStatus: anchor.web3.PublicKey;
+        while (!claimStatus) {
+            bump--;
+            try {
+                claimStatus = anchor.web3.PublicKey.createProgramAddressSync(
+                    [...seeds, Buffer.from([bump])],
+                    tipDistribution.programId,
+                );
+            } catch (e) {
+                // on the curve, try the next bump
+            }
+        }
+
+        try {
+            await tipDistribution.methods
+                .claimBatch([
+                    {
+                        claimant: user0.publicKey,
+                        amount: new anchor.BN(amount0),
+                        proof: convertBufProofToNumber(tree.getProof(0)),
+                    },
+                ])
+                .accounts({
+                    config: configAccount,
+                    tipDistributionAccount,
+                    merkleRootUploadAuthority: validatorVoteAccount.publicKey,
+                    payer: user1.publicKey,
+                    systemProgram: anchor.web3.SystemProgram.programId,
+                })
+                .remainingAccounts([
+                    {pubkey: claimStatus, isSigner: false, isWritable: true},
+                    {pubkey: user0.publicKey, isSigner: false, isWritable: true},
+                ])
+                .signers([user1, validatorVoteAccount])
+                .rpc();
+            assert.fail("expected exception to be thrown");
+        } catch (e) {
+            const err: AnchorError = e;
+            assert(err.error.errorCode.code === "ConstraintSeeds");
+        }
+    });
+
+    it("#claim_batch_multi happy path", async () => {
+        const {
+            amount0,
+            amount1,
+            preBalance0,
+            tipDistributionAccount,
+            user0,
+            user1,
+            validatorVoteAccount,
+        } = await setupWithUploadedMerkleRoot();
+
+        const claims = [user0, user1].map((claimant, index) => {
+            const [claimStatus] = anchor.web3.PublicKey.findProgramAddressSync(
+                [
+                    Buffer.from(CLAIM_STATUS_SEED, "utf8"),
+                    claimant.publicKey.toBuffer(),
+                    tipDistributionAccount.toBuffer(),
+                ],
+                tipDistribution.programId,
+            );
+            return {
+                claimStatus,
+                claim: {
+                    claimant: claimant.publicKey,
+                    amount: new anchor.BN([amount0, amount1][index]),
+                    index: new anchor.BN(index),
+                },
+            };
+        });
+        const remainingAccounts = claims.flatMap(({claimStatus, claim}) => [
+            {pubkey: claimStatus, isSigner: false, isWritable: true},
+            {pubkey: claim.claimant, isSigner: false, isWritable: true},
+        ]);
+
+        // both leaves of a two leaf tree hash straight to the root, so no proof is needed
+        await tipDistribution.methods
+            .claimBatchMulti(
+                claims.map(({claim}) => claim),
+                new anchor.BN(2),
+                [],
+            )
+            .accounts({
+                config: configAccount,
+                tipDistributionAccount,
+                merkleRootUploadAuthority: validatorVoteAccount.publicKey,
+                payer: user1.publicKey,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            })
+            .remainingAccounts(remainingAccounts)
+            .signers([user1, validatorVoteAccount])
+            .rpc();
+
+        const user0Info = await tipDistribution.provider.connection.getAccountInfo(
+            user0.publicKey,
+        );
+        assert.equal(user0Info.lamports, preBalance0 + amount0);
+        const tda = await tipDistribution.account.tipDistributionAccount.fetch(
+            tipDistributionAccount,
+        );
+        assert.equal(tda.merkleRoot.numNodesClaimed.toNumber(), 2);
+    });
+
+    it("#claim_batch_multi fails with an invalid proof", async () => {
+        const {
+            amount0,
+            tipDistributionAccount,
+            user0,
+            user1,
+            validatorVoteAccount,
+        } = await setupWithUploadedMerkleRoot();
+
+        const [claimStatus] = anchor.web3.PublicKey.findProgramAddressSync(
+            [
+                Buffer.from(CLAIM_STATUS_SEED, "utf8"),
+                user0.publicKey.toBuffer(),
+                tipDistributionAccount.toBuffer(),
+            ],
+            tipDistribution.programId,
+        );
+
+        try {
+            // proving a single leaf needs its sibling
+            await tipDistribution.methods
+                .claimBatchMulti(
+                    [
+                        {
+                            claimant: user0.publicKey,
+                            amount: new anchor.BN(amount0),
+                            index: new anchor.BN(0),
+                        },
+                    ],
+                    new anchor.BN(2),
+                    [],
+                )
+                .accounts({
+                    config: configAccount,
+                    tipDistributionAccount,
+                    merkleRootUploadAuthority: validatorVoteAccount.publicKey,
+                    payer: user1.publicKey,
+                    systemProgram: anchor.web3.SystemProgram.programId,
+                })
+                .remainingAccounts([
+                    {pubkey: claimStatus, isSigner: false, isWritable: true},
+                    {pubkey: user0.publicKey, isSigner: false, isWritable: true},
+                ])
+                .signers([user1, validatorVoteAccount])
+                .rpc();
+            assert.fail("expected exception to be thrown");
+        } catch (e) {
+            const err: AnchorError = e;
+            assert(err.error.errorCode.code === "InvalidProof");
+        }
+    });
+
+    it("#claim_with_bitmap happy path", async () => {
+        const {
+            amount0,
+            preBalance0,
+            tipDistributionAccount,
+            tree,
+            user0,
+            user1,
+            validatorVoteAccount,
+        } = await setupWithUploadedMerkleRoot({indexed: true});
+        const [claimBitmap] = anchor.web3.PublicKey.findProgramAddressSync(
+            [
+                Buffer.from(CLAIM_BITMAP_SEED, "utf8"),
+                tipDistributionAccount.toBuffer(),
+            ],
+            tipDistribution.programId,
+        );
+
+        await tipDistribution.methods
+            .initializeClaimBitmap()
+            .accounts({
+                config: configAccount,
+                tipDistributionAccount,
+                claimBitmap,
+                merkleRootUploadAuthority: validatorVoteAccount.publicKey,
+                payer: user1.publicKey,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            })
+            .signers([user1, validatorVoteAccount])
+            .rpc();
+
+        const claimWithBitmap = () =>
+            tipDistribution.methods
+                .claimWithBitmap(
+                    new anchor.BN(0),
+                    new anchor.BN(amount0),
+                    convertBufProofToNumber(tree.getProof(0)),
+                )
+                .accounts({
+                    config: configAccount,
+                    tipDistributionAccount,
+                    claimBitmap,
+                    merkleRootUploadAuthority: validatorVoteAccount.publicKey,
+                    claimant: user0.publicKey,
+                })
+                .signers([validatorVoteAccount])
+                .rpc();
+        await claimWithBitmap();
+
+        const user0Info = await tipDistribution.provider.connection.getAccountInfo(
+            user0.publicKey,
+        );
+        assert.equal(user0Info.lamports, preBalance0 + amount0);
+        const tda = await tipDistribution.account.tipDistributionAccount.fetch(
+            tipDistributionAccount,
+        );
+        assert.deepEqual(tda.claimMode, {bitmap: {}});
+        assert.equal(tda.merkleRoot.numNodesClaimed.toNumber(), 1);
+        const bitmap = await tipDistribution.account.claimBitmap.fetch(claimBitmap);
+        assert.deepEqual(Array.from(bitmap.bitmap), [1]);
+
+        try {
+            await claimWithBitmap();
+            assert.fail("expected exception to be thrown");
+        } catch (e) {
+            const err: AnchorError = e;
+            assert(err.error.errorCode.code === "NodeAlreadyClaimed");
+        }
+    });
+
+    it("#upload_merkle_root fails once a claim bitmap is initialized", async () => {
+        const {root, tipDistributionAccount, user1, validatorVoteAccount} =
+            await setupWithUploadedMerkleRoot({indexed: true});
+        const [claimBitmap] = anchor.web3.PublicKey.findProgramAddressSync(
+            [
+                Buffer.from(CLAIM_BITMAP_SEED, "utf8"),
+                tipDistributionAccount.toBuffer(),
+            ],
+            tipDistribution.programId,
+        );
+        await tipDistribution.methods
+            .initializeClaimBitmap()
+            .accounts({
+                config: configAccount,
+                tipDistributionAccount,
+                claimBitmap,
+                merkleRootUploadAuthority: validatorVoteAccount.publicKey,
+                payer: user1.publicKey,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            })
+            .signers([user1, validatorVoteAccount])
+            .rpc();
+
+        try {
+            // more nodes than the bitmap has bits for
+            await tipDistribution.methods
+                .uploadMerkleRoot(root.toJSON().data, new anchor.BN(1_000_000), new anchor.BN(100))
+                .accounts({
+                    tipDistributionAccount,
+                    merkleRootUploadAuthority: validatorVoteAccount.publicKey,
+                    config: configAccount,
+                })
+                .signers([validatorVoteAccount])
+                .rpc();
+            assert.fail("expected exception to be thrown");
+        } catch (e) {
+            const err: AnchorError = e;
+            assert(err.error.errorCode.code === "InvalidClaimMode");
+        }
+    });
+
+    it("#claim fails once a claim bitmap is initialized", async () => {
+        const {
+            amount0,
+            tipDistributionAccount,
+            tree,
+            user0,
+            user1,
+            validatorVoteAccount,
+        } = await setupWithUploadedMerkleRoot();
+        const [claimBitmap] = anchor.web3.PublicKey.findProgramAddressSync(
+            [
+                Buffer.from(CLAIM_BITMAP_SEED, "utf8"),
+                tipDistributionAccount.toBuffer(),
+            ],
+            tipDistribution.programId,
+        );
+        await tipDistribution.methods
+            .initializeClaimBitmap()
+            .accounts({
+                config: configAccount,
+                tipDistributionAccount,
+                claimBitmap,
+                merkleRootUploadAuthority: validatorVoteAccount.publicKey,
+                payer: user1.publicKey,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            })
+            .signers([user1, validatorVoteAccount])
+            .rpc();
+
+        const [claimStatus, bump] = anchor.web3.PublicKey.findProgramAddressSync(
+            [
+                Buffer.from(CLAIM_STATUS_SEED, "utf8"),
+                user0.publicKey.toBuffer(),
+                tipDistributionAccount.toBuffer(),
+            ],
+            tipDistribution.programId,
+        );
+        try {
+            await tipDistribution.methods
+                .claim(bump, new anchor.BN(amount0), convertBufProofToNumber(tree.getProof(0)))
+                .accounts({
+                    config: configAccount,
+                    tipDistributionAccount,
+                    merkleRootUploadAuthority: validatorVoteAccount.publicKey,
+                    claimStatus,
+                    claimant: user0.publicKey,
+                    payer: user1.publicKey,
+                    systemProgram: anchor.web3.SystemProgram.programId,
+                })
+                .signers([user1, validatorVoteAccount])
+                .rpc();
+            assert.fail("expected exception to be thrown");
+        } catch (e) {
+            const err: AnchorError = e;
+            assert(err.error.errorCode.code === "InvalidClaimMode");
+        }
+    });
+
+    it("#update_validator_commission_bps happy path", async () => {
+        const {
+            validatorVoteAccount,
+            maxValidatorCommissionBps,
+            tipDistributionAccount,
+            validatorIdentityKeypair,
+            bump,
+        } = await setup_initTipDistributionAccount();
+        await call_initTipDistributionAccount({
+            validatorCommissionBps: maxValidatorCommissionBps,
+            config: configAccount,
+            validatorIdentityKeypair,
+            systemProgram: SystemProgram.programId,
+            merkleRootUploadAuthority: validatorVoteAccount.publicKey,
+            validatorVoteAccount,
+            tipDistributionAccount,
+            bump,
+        });
+
+        const updateCommission = (validatorCommissionBps, signer) =>
+            tipDistribution.methods
+                .updateValidatorCommissionBps(validatorCommissionBps)
+                .accounts({
+                    config: configAccount,
+                    tipDistributionAccount,
+                    validatorVoteAccount: validatorVoteAccount.publicKey,
+                    signer: signer.publicKey,
+                })
+                .signers([signer])
+                .rpc();
+
+        await updateCommission(maxValidatorCommissionBps - 1, validatorIdentityKeypair);
+        const tda = await tipDistribution.account.tipDistributionAccount.fetch(
+            tipDistributionAccount,
+        );
+        assert.equal(tda.validatorCommissionBps, maxValidatorCommissionBps - 1);
+
+        try {
+            await updateCommission(maxValidatorCommissionBps + 1, validatorIdentityKeypair);
+            assert.fail("expected exception to be thrown");
+        } catch (e) {
+            const err: AnchorError = e;
+            assert(err.error.errorCode.code === "MaxValidatorCommissionFeeBpsExceeded");
+        }
+
+        try {
+            await updateCommission(0, await generateAccount(100000000));
+            assert.fail("expected exception to be thrown");
+        } catch (e) {
+            const err: AnchorError = e;
+            assert(err.error.errorCode.code === "Unauthorized");
+        }
+    });
+
+    it("#init_tip_distribution_account follows the config's vote_commission_policy", async () => {
+        const currentConfig = await tipDistribution.account.config.fetch(configAccount);
+        const updateConfig = (voteCommissionPolicy) =>
+            tipDistribution.methods
+                .updateConfig({...currentConfig, voteCommissionPolicy})
+                .accounts({config: configAccount, authority: currentConfig.authority})
+                .signers([authority])
+                .rpc();
+        const initTipDistributionAccount = async (validatorCommissionBps) => {
+            const {
+                validatorVoteAccount,
+                tipDistributionAccount,
+                validatorIdentityKeypair,
+                bump,
+            } = await setup_initTipDistributionAccount();
+            await call_initTipDistributionAccount({
+                validatorCommissionBps,
+                config: configAccount,
+                validatorIdentityKeypair,
+                systemProgram: SystemProgram.programId,
+                merkleRootUploadAuthority: validatorVoteAccount.publicKey,
+                validatorVoteAccount,
+                tipDistributionAccount,
+                bump,
+            });
+            return tipDistributionAccount;
+        };
+
+        // the vote accounts are created with a 0% commission
+        await updateConfig({cap: {}});
+        try {
+            await initTipDistributionAccount(1);
+            assert.fail("expected exception to be thrown");
+        } catch (e) {
+            const err: AnchorError = e;
+            assert(err.error.errorCode.code === "VoteAccountCommissionExceeded");
+        }
+
+        await updateConfig({mirror: {}});
+        const tipDistributionAccount = await initTipDistributionAccount(
+            currentConfig.maxValidatorCommissionBps,
+        );
+        const tda = await tipDistribution.account.tipDistributionAccount.fetch(
+            tipDistributionAccount,
+        );
+        assert.equal(tda.validatorCommissionBps, 0);
+
+        await updateConfig({unrestricted: {}});
+    });
+
+    it("#update_merkle_root_upload_authority happy path", async () => {
+        const {
+            validatorVoteAccount,
+            maxValidatorCommissionBps,
+            tipDistributionAccount,
+            validatorIdentityKeypair,
+            bump,
+        } = await setup_initTipDistributionAccount();
+        await call_initTipDistributionAccount({
+            validatorCommissionBps: maxValidatorCommissionBps,
+            config: configAccount,
+            validatorIdentityKeypair,
+            systemProgram: SystemProgram.programId,
+            merkleRootUploadAuthority: validatorVoteAccount.publicKey,
+            validatorVoteAccount,
+            tipDistributionAccount,
+            bump,
+        });
+
+        const newAuthority = anchor.web3.Keypair.generate().publicKey;
+        const updateAuthority = (signer) =>
+            tipDistribution.methods
+                .updateMerkleRootUploadAuthority(newAuthority)
+                .accounts({
+                    tipDistributionAccount,
+                    validatorVoteAccount: validatorVoteAccount.publicKey,
+                    signer: signer.publicKey,
+                })
+                .signers([signer])
+                .rpc();
+
+        try {
+            await updateAuthority(await generateAccount(LAMPORTS_PER_SOL));
+            assert.fail("expected exception to be thrown");
+        } catch (e) {
+            const err: AnchorError = e;
+            assert(err.error.errorCode.code === "Unauthorized");
+        }
+
+        await updateAuthority(validatorIdentityKeypair);
+        const account = await tipDistribution.account.tipDistributionAccount.fetch(
+            tipDistributionAccount,
+        );
+        assert.equal(
+            account.merkleRootUploadAuthority.toString(),
+            newAuthority.toString(),
+        );
+    });
+
     it("#initialize_merkle_root_upload_conifg happy path", async () => {
         await setup_initTipDistributionAccount();
 
@@ -1331,7 +1906,8 @@ const call_initTipDistributionAccount = async ({
     );
 };
 
-const setupWithUploadedMerkleRoot = async () => {
+// indexed leaves also commit to their position in the tree, for bitmap claim tracking
+const setupWithUploadedMerkleRoot = async ({indexed = false} = {}) => {
     const {
         validatorVoteAccount,
         maxValidatorCommissionBps,
@@ -1365,7 +1941,14 @@ const setupWithUploadedMerkleRoot = async () => {
     const demoData = [
         {account: user0.publicKey, amount: new u64(amount0)},
         {account: user1.publicKey, amount: new u64(amount1)},
-    ].map(({account, amount}) => balanceToBuffer(account, amount));
+    ].map(({account, amount}, index) =>
+        indexed
+            ? Buffer.concat([
+                  balanceToBuffer(account, amount),
+                  new u64(index).toArrayLike(Buffer, "le", 8),
+              ])
+            : balanceToBuffer(account, amount),
+    );
 
     const tree = new MerkleTree(demoData);
     const root = tree.getRoot();
@@ -1,10 +1,11 @@
 use std::str::FromStr;
 
-use anchor_lang::AccountDeserialize;
-use clap::{Parser, Subcommand};
-use jito_tip_distribution::state::{ClaimStatus, Config, TipDistributionAccount};
+use clap::{Parser, Subcommand, ValueEnum};
+use jito_programs_client::blocking::Client;
+use jito_tip_distribution::state::{Config, VoteCommissionPolicy};
 use jito_tip_distribution_sdk::{
-    derive_config_account_address, derive_tip_distribution_account_address,
+    derive_claim_status_account_address, derive_config_account_address,
+    derive_tip_distribution_account_address,
     instruction::{update_config_ix, UpdateConfigAccounts, UpdateConfigArgs},
 };
 use solana_client::rpc_client::RpcClient;
@@ -81,23 +82,45 @@ enum Commands {
         /// Bump
         #[arg(long)]
         bump: u8,
+
+        /// How validator commissions relate to the commission in their vote account
+        #[arg(long, value_enum, default_value = "unrestricted")]
+        vote_commission_policy: VoteCommissionPolicyArg,
     },
 }
 
+/// [VoteCommissionPolicy] as a command line value.
+#[derive(Clone, Copy, ValueEnum)]
+enum VoteCommissionPolicyArg {
+    Unrestricted,
+    Cap,
+    Mirror,
+}
+
+impl From<VoteCommissionPolicyArg> for VoteCommissionPolicy {
+    fn from(arg: VoteCommissionPolicyArg) -> Self {
+        match arg {
+            VoteCommissionPolicyArg::Unrestricted => Self::Unrestricted,
+            VoteCommissionPolicyArg::Cap => Self::Cap,
+            VoteCommissionPolicyArg::Mirror => Self::Mirror,
+        }
+    }
+}
+
 fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
 
     let program_id = Pubkey::from_str(&cli.program_id)?;
 
-    let client = RpcClient::new(cli.rpc_url);
+    let client = Client::new(RpcClient::new(cli.rpc_url));
+    let tip_distribution = client.tip_distribution(program_id);
 
     match cli.command {
         Commands::GetConfig => {
             let (config_pda, _) = derive_config_account_address(&program_id);
             println!("Config Account Address: {}", config_pda);
 
-            let config_data = client.get_account(&config_pda)?.data;
-            let config: Config = Config::try_deserialize(&mut config_data.as_slice())?;
+            let config = tip_distribution.get_config()?;
 
             println!("Config Account Data:");
             println!("  Authority: {}", config.authority);
@@ -107,6 +130,10 @@ fn main() -> anyhow::Result<()> {
                 "  Max Validator Commission BPS: {}",
                 config.max_validator_commission_bps
             );
+            println!(
+                "  Vote Commission Policy: {:?}",
+                config.vote_commission_policy
+            );
             println!("  Bump: {}", config.bump);
         }
 
@@ -119,9 +146,7 @@ fn main() -> anyhow::Result<()> {
                 derive_tip_distribution_account_address(&program_id, &vote_pubkey, epoch);
             println!("Tip Distribution Account Address: {}", tip_dist_pda);
 
-            let account_data = client.get_account(&tip_dist_pda)?.data;
-            let tip_dist: TipDistributionAccount =
-                TipDistributionAccount::try_deserialize(&mut account_data.as_slice())?;
+            let tip_dist = tip_distribution.get_tip_distribution_account(&vote_pubkey, epoch)?;
 
             println!("Tip Distribution Account Data:");
             println!("  Vote Account: {}", tip_dist.validator_vote_account);
@@ -164,20 +189,12 @@ fn main() -> anyhow::Result<()> {
             let (tip_dist_pda, _) =
                 derive_tip_distribution_account_address(&program_id, &vote_pubkey, epoch);
 
-            // Then derive claim status PDA using same seeds as in the program
-            let (claim_status_pda, _) = Pubkey::find_program_address(
-                &[
-                    ClaimStatus::SEED,
-                    claimant_pubkey.as_ref(),
-                    tip_dist_pda.as_ref(),
-                ],
-                &program_id,
-            );
+            let (claim_status_pda, _) =
+                derive_claim_status_account_address(&program_id, &claimant_pubkey, &tip_dist_pda);
             println!("Claim Status Account Address: {}", claim_status_pda);
 
-            let account_data = client.get_account(&claim_status_pda)?.data;
-            let claim_status: ClaimStatus =
-                ClaimStatus::try_deserialize(&mut account_data.as_slice())?;
+            let claim_status =
+                tip_distribution.get_claim_status(&claimant_pubkey, &tip_dist_pda)?;
 
             println!("Claim Status Data:");
             println!("  Is Claimed: {}", claim_status.is_claimed);
@@ -195,6 +212,7 @@ fn main() -> anyhow::Result<()> {
             num_epochs_valid,
             max_validator_commission_bps,
             bump,
+            vote_commission_policy,
         } => {
             let authority_pubkey = Pubkey::from_str(&authority)?;
             let expired_funds_account_pubkey = Pubkey::from_str(&expired_funds_account)?;
@@ -205,10 +223,10 @@ fn main() -> anyhow::Result<()> {
                 num_epochs_valid,
                 max_validator_commission_bps,
                 bump,
+                vote_commission_policy: vote_commission_policy.into(),
             };
 
             let accounts = UpdateConfigAccounts {
-                config: Pubkey::default(),
                 authority: authority_pubkey,
             };
 
@@ -1,8 +1,12 @@
 use std::str::FromStr;
 
-use anchor_lang::AccountDeserialize;
 use clap::{Parser, Subcommand};
-use jito_tip_payment::Config;
+use jito_programs_client::blocking::Client;
+use jito_tip_payment::MAX_TIP_ACCOUNTS;
+use jito_tip_payment_sdk::{
+    derive_settlement_ledger_account_address, derive_tip_payment_account_address,
+    settlement_ledger::decode_settlement_ledger,
+};
 use solana_client::rpc_client::RpcClient;
 use solana_sdk::pubkey::Pubkey;
 
@@ -36,58 +40,65 @@ enum Commands {
 
     /// Get a specific tip payment account
     GetTipAccount {
-        /// Index of the tip account (0-7)
-        #[arg(value_parser = clap::value_parser!(u8).range(0..8))]
+        /// Index of the tip account (0-15)
+        #[arg(value_parser = clap::value_parser!(u8).range(0..MAX_TIP_ACCOUNTS as i64))]
         index: u8,
     },
+
+    /// Get the most recent tip settlements, oldest first
+    GetSettlementLedger,
 }
 
 fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
 
     let program_id = Pubkey::from_str(&cli.program_id)?;
-    let client = RpcClient::new(cli.rpc_url);
+    let client = Client::new(RpcClient::new(cli.rpc_url));
+    let tip_payment = client.tip_payment(program_id);
 
     match cli.command {
         Commands::GetConfig => {
-            let config_pda =
-                Pubkey::find_program_address(&[jito_tip_payment::CONFIG_ACCOUNT_SEED], &program_id)
-                    .0;
-            let config_data = client.get_account(&config_pda)?.data;
-            let config: Config = Config::try_deserialize(&mut config_data.as_slice())?;
+            let config = tip_payment.get_config()?;
             println!("Config Account:");
             println!("  Tip Receiver: {}", config.tip_receiver);
             println!("  Block Builder: {}", config.block_builder);
             println!(
-                "  Block Builder Commission %: {}",
-                config.block_builder_commission_pct
+                "  Block Builder Commission BPS: {}",
+                config.block_builder_commission_bps
+            );
+            println!("  Number of Tip Accounts: {}", config.num_tip_accounts);
+            println!("  Block Builder Recipients:");
+            for recipient in &config.block_builder_recipients {
+                println!("    {} (weight {})", recipient.recipient, recipient.weight);
+            }
+            println!("  Admin: {:?}", config.admin);
+            println!("  Claims Paused: {}", config.claims_paused);
+            println!(
+                "  Max Block Builder Commission BPS: {:?}",
+                config.max_block_builder_commission_bps
+            );
+            println!(
+                "  Block Builder Allow List: {:?}",
+                config.block_builder_allow_list
             );
             println!("  Bumps:");
             println!("    Config: {}", config.bumps.config);
-            println!("    Tip Account 0: {}", config.bumps.tip_payment_account_0);
-            println!("    Tip Account 1: {}", config.bumps.tip_payment_account_1);
-            println!("    Tip Account 2: {}", config.bumps.tip_payment_account_2);
-            println!("    Tip Account 3: {}", config.bumps.tip_payment_account_3);
-            println!("    Tip Account 4: {}", config.bumps.tip_payment_account_4);
-            println!("    Tip Account 5: {}", config.bumps.tip_payment_account_5);
-            println!("    Tip Account 6: {}", config.bumps.tip_payment_account_6);
-            println!("    Tip Account 7: {}", config.bumps.tip_payment_account_7);
+            for (i, bump) in config
+                .bumps
+                .tip_payment_accounts
+                .iter()
+                .take(config.num_tip_accounts as usize)
+                .enumerate()
+            {
+                println!("    Tip Account {}: {}", i, bump);
+            }
         }
         Commands::GetAllTipAccounts => {
-            let tip_account_seeds = [
-                jito_tip_payment::TIP_ACCOUNT_SEED_0,
-                jito_tip_payment::TIP_ACCOUNT_SEED_1,
-                jito_tip_payment::TIP_ACCOUNT_SEED_2,
-                jito_tip_payment::TIP_ACCOUNT_SEED_3,
-                jito_tip_payment::TIP_ACCOUNT_SEED_4,
-                jito_tip_payment::TIP_ACCOUNT_SEED_5,
-                jito_tip_payment::TIP_ACCOUNT_SEED_6,
-                jito_tip_payment::TIP_ACCOUNT_SEED_7,
-            ];
-
-            for (i, seed) in tip_account_seeds.iter().enumerate() {
-                let tip_pda = Pubkey::find_program_address(&[seed], &program_id).0;
-                let lamports = client.get_account(&tip_pda)?.lamports;
+            let config = tip_payment.get_config()?;
+
+            for i in 0..config.num_tip_accounts as usize {
+                let tip_pda = derive_tip_payment_account_address(&program_id, i).0;
+                let lamports = client.rpc().get_account(&tip_pda)?.lamports;
 
                 println!("Tip Payment Account {}:", i);
                 println!("  Address: {}", tip_pda);
@@ -95,25 +106,30 @@ fn main() -> anyhow::Result<()> {
             }
         }
         Commands::GetTipAccount { index } => {
-            let seed = match index {
-                0 => jito_tip_payment::TIP_ACCOUNT_SEED_0,
-                1 => jito_tip_payment::TIP_ACCOUNT_SEED_1,
-                2 => jito_tip_payment::TIP_ACCOUNT_SEED_2,
-                3 => jito_tip_payment::TIP_ACCOUNT_SEED_3,
-                4 => jito_tip_payment::TIP_ACCOUNT_SEED_4,
-                5 => jito_tip_payment::TIP_ACCOUNT_SEED_5,
-                6 => jito_tip_payment::TIP_ACCOUNT_SEED_6,
-                7 => jito_tip_payment::TIP_ACCOUNT_SEED_7,
-                _ => unreachable!(),
-            };
-
-            let tip_pda = Pubkey::find_program_address(&[seed], &program_id).0;
-            let lamports = client.get_account(&tip_pda)?.lamports;
+            let tip_pda = derive_tip_payment_account_address(&program_id, index as usize).0;
+            let lamports = client.rpc().get_account(&tip_pda)?.lamports;
 
             println!("Tip Payment Account {}:", index);
             println!("  Address: {}", tip_pda);
             println!("  Lamports: {}", lamports);
         }
+        Commands::GetSettlementLedger => {
+            let settlement_ledger_pda = derive_settlement_ledger_account_address(&program_id).0;
+            let settlement_ledger_data = client.rpc().get_account(&settlement_ledger_pda)?.data;
+            let settlements = decode_settlement_ledger(&settlement_ledger_data)?;
+
+            for settlement in settlements {
+                println!("Slot {}:", settlement.slot);
+                println!("  Tip Receiver: {}", settlement.tip_receiver);
+                println!("  Block Builder: {}", settlement.block_builder);
+                println!("  Total Tips: {}", settlement.total_tips);
+                println!("  Tip Receiver Amount: {}", settlement.tip_receiver_amount);
+                println!(
+                    "  Block Builder Amount: {}",
+                    settlement.block_builder_amount
+                );
+            }
+        }
     }
 
     Ok(())